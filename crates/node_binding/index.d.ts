@@ -3,13 +3,188 @@
 
 /* auto-generated by NAPI-RS */
 
+export interface JsCompilerOptions {
+  resolve?: JsResolveOptions
+  jsx?: JsJsxOptions
+  parserBackend?: 'swc' | 'oxc'
+  output?: JsOutputOptions
+  module?: JsModuleOptions
+  /** values are code, e.g. `{ "process.env.NODE_ENV": JSON.stringify("production") }` */
+  define?: Record<string, string>
+  target?: 'web' | 'node'
+  devtool?: 'source-map' | 'inline-source-map' | 'hidden-source-map' | false
+  optimization?: JsOptimizationOptions
+}
+export interface JsOptimizationOptions {
+  /** leave out the exports which are not imported by any module, defaults to `true` */
+  usedExports?: boolean
+  /**
+   * leave out the modules flagged by `sideEffects: false` if none of their exports is used,
+   * defaults to `true`
+   */
+  sideEffects?: boolean
+  /** merge esm modules of a chunk into a single scope, defaults to `true` */
+  concatenateModules?: boolean
+  /** minify emitted chunks with swc's minifier, defaults to `false` */
+  minimize?: boolean
+  minimizer?: JsMinimizerOptions
+  /** move modules shared by chunks into new chunks, defaults to `false` */
+  splitChunks?: boolean | JsSplitChunksOptions
+  /** defaults to `"natural"` */
+  moduleIds?: 'natural' | 'named' | 'deterministic'
+  /** defaults to `"natural"` */
+  chunkIds?: 'natural' | 'named' | 'deterministic'
+}
+export interface JsSplitChunksOptions {
+  /** defaults to `20000` */
+  minSize?: number
+  /** defaults to `1` */
+  minChunks?: number
+  /** merged with the built-in `default` and `vendors` groups, `false` disables a group */
+  cacheGroups?: Record<string, boolean | JsCacheGroup>
+}
+export interface JsCacheGroup {
+  test?: string | RegExp | Array<string | RegExp>
+  minSize?: number
+  minChunks?: number
+  /** defaults to `0` */
+  priority?: number
+  name?: string
+}
+export interface JsMinimizerOptions {
+  /** defaults to `true` */
+  compress?: boolean
+  /** defaults to `true` */
+  mangle?: boolean
+}
+export interface JsOutputOptions {
+  path?: string
+  /** `"auto"` infers the public path from the url of current script */
+  publicPath?: string
+  filename?: string
+  cssFilename?: string
+  assetModuleFilename?: string
+  /** defaults to `"xxhash64"` */
+  hashFunction?: 'xxhash64' | 'md5' | 'sha256'
+  /** defaults to `20` */
+  hashDigestLength?: number
+  /** filename of a json file mapping `[name].js` and `[name].css` of chunks to the emitted files */
+  manifest?: string
+  environment?: JsEnvironmentOptions
+}
+export interface JsEnvironmentOptions {
+  /** required by top level await */
+  asyncFunction?: boolean
+}
+export interface JsModuleOptions {
+  parser?: JsParserOptions
+  rules?: Array<JsModuleRule>
+}
+export interface JsModuleRule {
+  test?: string | RegExp | Array<string | RegExp>
+  include?: string | RegExp | Array<string | RegExp>
+  exclude?: string | RegExp | Array<string | RegExp>
+  resourceQuery?: string | RegExp | Array<string | RegExp>
+  /** e.g. `"asset/resource"`, `"css/module"` */
+  type?: string
+  /** overrides the `sideEffects` field of package.json */
+  sideEffects?: boolean
+  /**
+   * loaders run from the last to the first, the first argument is always `null` as in node
+   * style callbacks, a loader which throws or rejects fails the module
+   */
+  use?: Array<(err: null, content: Buffer, context: JsLoaderContext) => string | Buffer | JsLoaderOutput | Promise<string | Buffer | JsLoaderOutput>>
+}
+export interface JsParserOptions {
+  asset?: JsAssetParserOptions
+}
+export interface JsAssetParserOptions {
+  dataUrlCondition?: JsAssetParserDataUrlCondition
+}
+export interface JsAssetParserDataUrlCondition {
+  maxSize?: number
+}
+export interface JsJsxOptions {
+  runtime?: 'classic' | 'automatic'
+  pragma?: string
+  pragmaFrag?: string
+  importSource?: string
+  development?: boolean
+}
+export interface JsResolveOptions {
+  alias?: Record<string, string | false | string[]>
+  extensions?: Array<string>
+  mainFields?: Array<string>
+  conditionNames?: Array<string>
+  modules?: Array<string>
+  symlinks?: boolean
+  tsconfig?: string | JsTsconfigOptions
+  fallback?: Record<string, string | false | string[]>
+  /**
+   * a `false` value in these fields produces an empty module, defaults to `["browser"]` for
+   * `target: "web"`
+   */
+  aliasFields?: Array<string>
+}
+export interface JsTsconfigOptions {
+  configFile: string
+  /** `"auto"` or a list of referenced tsconfig paths */
+  references?: 'auto' | string[]
+}
+export interface JsLoaderContext {
+  resourcePath: string
+  /** e.g. `?inline`, empty if the request has no query */
+  resourceQuery: string
+}
+/** code with the map back to the content the loader received */
+export interface JsLoaderOutput {
+  code: string | Buffer
+  /** source map in json */
+  map?: string
+}
+/** resolved path with the flags of the module */
+export interface JsResolveResult {
+  path: string
+  /** `false` if the module is side effect free, it's left out if none of its exports is used */
+  sideEffects?: boolean
+}
 export interface JsPluginAdapter {
   onResolve?: (arg: string) => any
   onLoad?: (arg: string) => any
   thisCompilation?: (arg: JsCompilation) => any
 }
+export interface JsStats {
+  modules: Array<JsStatsModule>
+  chunks: Array<JsStatsChunk>
+  /** all the emitted files */
+  assets: Array<string>
+  /** `[fullhash]` of the compilation */
+  hash: string
+}
+export interface JsStatsChunk {
+  id: string
+  name?: string
+  /** emitted files with the hashes filled in */
+  files: Array<string>
+  /** `[chunkhash]` of the chunk */
+  hash: string
+}
+export interface JsStatsModule {
+  identifier: string
+  /** `null` if the exports are unknown statically, e.g. commonjs */
+  providedExports?: Array<string>
+  /** `null` if every export is used, e.g. by a namespace import */
+  usedExports?: Array<string>
+  unusedExports: Array<string>
+  /** why optimizations are not applied, e.g. the module can't be concatenated */
+  optimizationBailout: Array<string>
+}
 export declare class JsCompilation { }
 export declare class JsCompiler {
-  constructor(context: string, entry: string, plugins: Array<JsPluginAdapter>)
+  constructor(context: string, entry: string | Record<string, string>, plugins: Array<JsPluginAdapter>, options?: JsCompilerOptions | undefined | null)
   build(callback: (err: Error | null, ) => any): Promise<void>
+  /** stats of the last build */
+  getStats(): JsStats
+  /** messages of the errors and warnings of the last build, each followed by its causes */
+  getDiagnostics(): Array<string>
 }
@@ -1,18 +1,19 @@
-use std::{cell::UnsafeCell, ptr::NonNull, sync::Arc};
+use std::sync::Arc;
 
 use napi::bindgen_prelude::External;
 use napi_derive::napi;
-use unpack::{compilation::Compilation, plugin::CompilationCell};
+use unpack::plugin::CompilationCell;
 
 #[napi]
 pub struct JsCompilation {
+    #[allow(dead_code)]
     compilation: External<Arc<CompilationCell>>
 }
 
 impl JsCompilation {
     pub fn from_compilation(compilation: External<Arc<CompilationCell>>) -> Self{
         Self {
-            compilation: compilation
+            compilation
         }
     }
 }
\ No newline at end of file
@@ -1,15 +1,13 @@
+use crate::js_options::JsCompilerOptions;
 use crate::js_plugin::JsPluginAdapter;
+use crate::js_stats::JsStats;
 use camino::Utf8PathBuf;
 use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
-use napi::Env;
+use indexmap::IndexMap;
+use napi::{Either, Env};
 use napi_derive::napi;
 use std::sync::Arc;
-use unpack::compiler::EntryItem;
-use unpack::resolver::ResolveOptions;
-use unpack::{
-    compiler::{Compiler, CompilerOptions},
-    plugin::BoxPlugin,
-};
+use unpack::{compiler::Compiler, plugin::BoxPlugin};
 
 #[napi]
 pub struct JsCompiler {
@@ -22,23 +20,14 @@ impl JsCompiler {
     pub fn new(
         env: Env,
         context: String,
-        entry: String,
+        #[napi(ts_arg_type = "string | Record<string, string>")]
+        entry: Either<String, IndexMap<String, String>>,
         mut plugins: Vec<JsPluginAdapter>,
-    ) -> Self {
-        let options = CompilerOptions {
-            context: Utf8PathBuf::from(context),
-            entry: vec![EntryItem {
-                name: "main".to_string(),
-                import: entry,
-            }],
-            resolve: ResolveOptions {
-                extensions: vec![".js", ".ts", ".mjs", ".jsx"]
-                    .into_iter()
-                    .map(|x| x.to_string())
-                    .collect::<Vec<_>>(),
-                ..Default::default()
-            },
-        };
+        options: Option<JsCompilerOptions>,
+    ) -> napi::Result<Self> {
+        let options = options
+            .unwrap_or_default()
+            .into_compiler_options(&env, Utf8PathBuf::from(context), entry)?;
         // unref napi handles to avoid hang problem
         for plugin in plugins.iter_mut() {
             if let Some(resolve) = &mut plugin.on_resolve {
@@ -57,9 +46,9 @@ impl JsCompiler {
             .map(|x| Arc::new(x) as BoxPlugin)
             .collect();
         let compiler = Compiler::new(Arc::new(options), plugins);
-        Self {
+        Ok(Self {
             inner: Some(compiler),
-        }
+        })
     }
     #[napi]
     pub async unsafe fn build(&mut self, callback: ThreadsafeFunction<(), ErrorStrategy::CalleeHandled>) -> napi::Result<()> {
@@ -75,4 +64,30 @@ impl JsCompiler {
         callback.call(Ok(()), ThreadsafeFunctionCallMode::NonBlocking);
         Ok(())
     }
+    /// stats of the last build
+    #[napi]
+    pub fn get_stats(&self) -> napi::Result<JsStats> {
+        let compiler = self.inner.as_ref().ok_or_else(|| {
+            napi::Error::from_reason("stats are not available while building")
+        })?;
+        Ok(compiler.stats().into())
+    }
+    /// messages of the errors and warnings of the last build, each followed by its causes
+    #[napi]
+    pub fn get_diagnostics(&self) -> napi::Result<Vec<String>> {
+        let compiler = self.inner.as_ref().ok_or_else(|| {
+            napi::Error::from_reason("diagnostics are not available while building")
+        })?;
+        Ok(compiler
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| {
+                diagnostic
+                    .chain()
+                    .map(|error| error.to_string())
+                    .collect::<Vec<_>>()
+                    .join(": ")
+            })
+            .collect())
+    }
 }
@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use napi::bindgen_prelude::{Buffer, Either3, Promise};
+use napi::threadsafe_function::{ErrorStrategy::CalleeHandled, ThreadsafeFunction};
+use napi::{Either, Status};
+use napi_derive::napi;
+use std::{fmt::Debug, future::IntoFuture};
+use unpack::errors::miette::{miette, Report, Result};
+use unpack::loader::{Loader, LoaderContext};
+use rspack_sources::SourceMap;
+
+#[napi(object)]
+pub struct JsLoaderContext {
+    pub resource_path: String,
+    /// e.g. `?inline`, empty if the request has no query
+    pub resource_query: String,
+}
+
+/// code with the map back to the content the loader received
+#[napi(object, object_to_js = false)]
+pub struct JsLoaderOutput {
+    pub code: Either<String, Buffer>,
+    /// source map in json
+    pub map: Option<String>,
+}
+
+pub type JsLoaderResult = Either3<String, Buffer, JsLoaderOutput>;
+
+impl JsLoaderOutput {
+    pub fn into_content(self) -> Result<(Vec<u8>, Option<SourceMap>)> {
+        let content = match self.code {
+            Either::A(code) => code.into_bytes(),
+            Either::B(buffer) => buffer.into(),
+        };
+        let source_map = self
+            .map
+            .map(|map| SourceMap::from_json(&map).map_err(|err| miette!("invalid source map: {err}")))
+            .transpose()?;
+        Ok((content, source_map))
+    }
+}
+
+/// the exception thrown by the loader, or the reason of its result being invalid
+fn loader_error(err: napi::Error) -> Report {
+    if err.status == Status::InvalidArg {
+        return miette!(
+            "the loader should return a string, a Buffer, `{{ code, map }}` or a Promise of them"
+        );
+    }
+    miette!("{}", err.reason)
+}
+
+/// a loader implemented by a js function, which receives the content and returns the new one.
+/// errors are handled by the callee, so that a throwing loader fails the module instead of the process
+pub struct JsLoaderAdapter {
+    pub name: String,
+    pub callback: ThreadsafeFunction<(Buffer, JsLoaderContext), CalleeHandled>,
+}
+impl Debug for JsLoaderAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsLoaderAdapter")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+#[async_trait]
+impl Loader for JsLoaderAdapter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    async fn run(&self, loader_context: &mut LoaderContext) -> Result<()> {
+        let content = std::mem::take(&mut loader_context.content);
+        // exceptions and values which are not a loader result are received as errors
+        let ret: Either<JsLoaderResult, Promise<JsLoaderResult>> = self
+            .callback
+            .call_async(Ok((
+                content.into(),
+                JsLoaderContext {
+                    resource_path: loader_context.resource_path.to_string(),
+                    resource_query: loader_context.resource_query.clone(),
+                },
+            )))
+            .await
+            .map_err(loader_error)?;
+        let result = match ret {
+            Either::A(result) => result,
+            Either::B(promise) => promise.into_future().await.map_err(loader_error)?,
+        };
+        (loader_context.content, loader_context.source_map) = match result {
+            Either3::A(code) => (code.into_bytes(), None),
+            Either3::B(buffer) => (buffer.into(), None),
+            Either3::C(output) => output.into_content()?,
+        };
+        Ok(())
+    }
+}
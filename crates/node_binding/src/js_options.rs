@@ -0,0 +1,880 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use indexmap::IndexMap;
+use napi::bindgen_prelude::Either3;
+use napi::bindgen_prelude::Buffer;
+use napi::threadsafe_function::{ErrorStrategy::CalleeHandled, ThreadsafeFunction};
+use napi::{Either, Env, JsObject, Status};
+use napi_derive::napi;
+use regex::Regex;
+use std::path::PathBuf;
+use std::sync::Arc;
+use unpack::compiler::{
+    CacheGroup, ChunkIds, CompilerOptions, Devtool, EntryItem, HashFunction, JsxOptions, JsxRuntime, ModuleIds,
+    ModuleOptions, ModuleRule, OptimizationOptions, OutputOptions, ParserBackend, RuleCondition,
+    SplitChunksOptions, Target,
+};
+use unpack::loader::BoxLoader;
+use unpack::module::ModuleType;
+
+use crate::js_loader::{JsLoaderAdapter, JsLoaderContext};
+use unpack::resolver::{AliasValue, ResolveOptions, TsconfigOptions, TsconfigReferences};
+
+#[napi(object, object_to_js = false)]
+#[derive(Default)]
+pub struct JsCompilerOptions {
+    pub resolve: Option<JsResolveOptions>,
+    pub jsx: Option<JsJsxOptions>,
+    #[napi(ts_type = "'swc' | 'oxc'")]
+    pub parser_backend: Option<String>,
+    pub output: Option<JsOutputOptions>,
+    pub module: Option<JsModuleOptions>,
+    /// values are code, e.g. `{ "process.env.NODE_ENV": JSON.stringify("production") }`
+    pub define: Option<IndexMap<String, String>>,
+    #[napi(ts_type = "'web' | 'node'")]
+    pub target: Option<String>,
+    #[napi(ts_type = "'source-map' | 'inline-source-map' | 'hidden-source-map' | false")]
+    pub devtool: Option<Either<String, bool>>,
+    pub optimization: Option<JsOptimizationOptions>,
+}
+
+#[napi(object, object_to_js = false)]
+pub struct JsOptimizationOptions {
+    /// leave out the exports which are not imported by any module, defaults to `true`
+    pub used_exports: Option<bool>,
+    /// leave out the modules flagged by `sideEffects: false` if none of their exports is used,
+    /// defaults to `true`
+    pub side_effects: Option<bool>,
+    /// merge esm modules of a chunk into a single scope, defaults to `true`
+    pub concatenate_modules: Option<bool>,
+    /// minify emitted chunks with swc's minifier, defaults to `false`
+    pub minimize: Option<bool>,
+    pub minimizer: Option<JsMinimizerOptions>,
+    /// move modules shared by chunks into new chunks, defaults to `false`
+    pub split_chunks: Option<Either<bool, JsSplitChunksOptions>>,
+    /// defaults to `"natural"`
+    #[napi(ts_type = "'natural' | 'named' | 'deterministic'")]
+    pub module_ids: Option<String>,
+    /// defaults to `"natural"`
+    #[napi(ts_type = "'natural' | 'named' | 'deterministic'")]
+    pub chunk_ids: Option<String>,
+}
+
+#[napi(object, object_to_js = false)]
+pub struct JsSplitChunksOptions {
+    /// defaults to `20000`
+    pub min_size: Option<u32>,
+    /// defaults to `1`
+    pub min_chunks: Option<u32>,
+    /// merged with the built-in `default` and `vendors` groups, `false` disables a group
+    pub cache_groups: Option<IndexMap<String, Either<bool, JsCacheGroup>>>,
+}
+
+#[napi(object, object_to_js = false)]
+pub struct JsCacheGroup {
+    #[napi(ts_type = "string | RegExp | Array<string | RegExp>")]
+    pub test: Option<JsRuleCondition>,
+    pub min_size: Option<u32>,
+    pub min_chunks: Option<u32>,
+    /// defaults to `0`
+    pub priority: Option<i32>,
+    pub name: Option<String>,
+}
+
+#[napi(object, object_to_js = false)]
+pub struct JsMinimizerOptions {
+    /// defaults to `true`
+    pub compress: Option<bool>,
+    /// defaults to `true`
+    pub mangle: Option<bool>,
+}
+
+#[napi(object, object_to_js = false)]
+pub struct JsOutputOptions {
+    pub path: Option<String>,
+    /// `"auto"` infers the public path from the url of current script
+    pub public_path: Option<String>,
+    pub filename: Option<String>,
+    pub css_filename: Option<String>,
+    pub asset_module_filename: Option<String>,
+    /// defaults to `"xxhash64"`
+    #[napi(ts_type = "'xxhash64' | 'md5' | 'sha256'")]
+    pub hash_function: Option<String>,
+    /// defaults to `20`
+    pub hash_digest_length: Option<u32>,
+    /// filename of a json file mapping `[name].js` and `[name].css` of chunks to the emitted files
+    pub manifest: Option<String>,
+    pub environment: Option<JsEnvironmentOptions>,
+}
+
+#[napi(object, object_to_js = false)]
+pub struct JsEnvironmentOptions {
+    /// required by top level await
+    pub async_function: Option<bool>,
+}
+
+#[napi(object, object_to_js = false)]
+pub struct JsModuleOptions {
+    pub parser: Option<JsParserOptions>,
+    pub rules: Option<Vec<JsModuleRule>>,
+}
+
+/// a string matches values starting with it, arrays match if any item matches
+pub type JsRuleCondition = Either3<String, Vec<Either<String, JsObject>>, JsObject>;
+
+#[napi(object, object_to_js = false)]
+pub struct JsModuleRule {
+    #[napi(ts_type = "string | RegExp | Array<string | RegExp>")]
+    pub test: Option<JsRuleCondition>,
+    #[napi(ts_type = "string | RegExp | Array<string | RegExp>")]
+    pub include: Option<JsRuleCondition>,
+    #[napi(ts_type = "string | RegExp | Array<string | RegExp>")]
+    pub exclude: Option<JsRuleCondition>,
+    #[napi(ts_type = "string | RegExp | Array<string | RegExp>")]
+    pub resource_query: Option<JsRuleCondition>,
+    /// e.g. `"asset/resource"`, `"css/module"`
+    #[napi(js_name = "type")]
+    pub module_type: Option<String>,
+    /// overrides the `sideEffects` field of package.json
+    pub side_effects: Option<bool>,
+    /// loaders run from the last to the first, the first argument is always `null` as in node
+    /// style callbacks, a loader which throws or rejects fails the module
+    #[napi(
+        js_name = "use",
+        ts_type = "Array<(err: null, content: Buffer, context: JsLoaderContext) => string | Buffer | JsLoaderOutput | Promise<string | Buffer | JsLoaderOutput>>"
+    )]
+    pub loaders: Option<Vec<ThreadsafeFunction<(Buffer, JsLoaderContext), CalleeHandled>>>,
+}
+
+#[napi(object, object_to_js = false)]
+pub struct JsParserOptions {
+    pub asset: Option<JsAssetParserOptions>,
+}
+
+#[napi(object, object_to_js = false)]
+pub struct JsAssetParserOptions {
+    pub data_url_condition: Option<JsAssetParserDataUrlCondition>,
+}
+
+#[napi(object, object_to_js = false)]
+pub struct JsAssetParserDataUrlCondition {
+    pub max_size: Option<u32>,
+}
+
+#[napi(object, object_to_js = false)]
+pub struct JsJsxOptions {
+    #[napi(ts_type = "'classic' | 'automatic'")]
+    pub runtime: Option<String>,
+    pub pragma: Option<String>,
+    pub pragma_frag: Option<String>,
+    pub import_source: Option<String>,
+    pub development: Option<bool>,
+}
+
+/// alias target, same as webpack: a path, a list of paths or `false` to ignore the module
+pub type JsAliasValue = Either3<String, bool, Vec<String>>;
+
+#[napi(object, object_to_js = false)]
+#[derive(Default)]
+pub struct JsResolveOptions {
+    #[napi(ts_type = "Record<string, string | false | string[]>")]
+    pub alias: Option<IndexMap<String, JsAliasValue>>,
+    pub extensions: Option<Vec<String>>,
+    pub main_fields: Option<Vec<String>>,
+    pub condition_names: Option<Vec<String>>,
+    pub modules: Option<Vec<String>>,
+    pub symlinks: Option<bool>,
+    #[napi(ts_type = "string | JsTsconfigOptions")]
+    pub tsconfig: Option<Either<String, JsTsconfigOptions>>,
+    #[napi(ts_type = "Record<string, string | false | string[]>")]
+    pub fallback: Option<IndexMap<String, JsAliasValue>>,
+    /// a `false` value in these fields produces an empty module, defaults to `["browser"]` for
+    /// `target: "web"`
+    pub alias_fields: Option<Vec<String>>,
+}
+
+#[napi(object, object_to_js = false)]
+pub struct JsTsconfigOptions {
+    pub config_file: String,
+    /// `"auto"` or a list of referenced tsconfig paths
+    #[napi(ts_type = "'auto' | string[]")]
+    pub references: Option<Either<String, Vec<String>>>,
+}
+
+fn invalid_option(message: String) -> napi::Error {
+    napi::Error::new(Status::InvalidArg, message)
+}
+
+impl JsCompilerOptions {
+    pub fn into_compiler_options(
+        self,
+        env: &Env,
+        context: Utf8PathBuf,
+        entry: Either<String, IndexMap<String, String>>,
+    ) -> napi::Result<CompilerOptions> {
+        let target = match self.target.as_deref() {
+            None | Some("web") => Target::Web,
+            Some("node") => Target::Node,
+            Some(target) => {
+                return Err(invalid_option(format!(
+                    "target: expected \"web\" or \"node\", got \"{target}\""
+                )))
+            }
+        };
+        let resolve = match self.resolve {
+            Some(resolve) => resolve.into_resolve_options(&context, target)?,
+            None => JsResolveOptions::default_resolve_options(target),
+        };
+        let jsx = match self.jsx {
+            Some(jsx) => jsx.into_jsx_options()?,
+            None => Default::default(),
+        };
+        let parser_backend = match self.parser_backend.as_deref() {
+            None | Some("swc") => ParserBackend::Swc,
+            Some("oxc") => ParserBackend::Oxc,
+            Some(backend) => {
+                return Err(invalid_option(format!(
+                    "parserBackend: expected \"swc\" or \"oxc\", got \"{backend}\""
+                )))
+            }
+        };
+        let devtool = match self.devtool {
+            None | Some(Either::B(false)) => Devtool::None,
+            Some(Either::A(devtool)) if devtool == "source-map" => Devtool::SourceMap,
+            Some(Either::A(devtool)) if devtool == "inline-source-map" => Devtool::InlineSourceMap,
+            Some(Either::A(devtool)) if devtool == "hidden-source-map" => Devtool::HiddenSourceMap,
+            Some(devtool) => {
+                let devtool = match devtool {
+                    Either::A(devtool) => format!("\"{devtool}\""),
+                    Either::B(devtool) => devtool.to_string(),
+                };
+                return Err(invalid_option(format!(
+                    "devtool: expected \"source-map\", \"inline-source-map\", \"hidden-source-map\" or false, got {devtool}"
+                )));
+            }
+        };
+        let output = match self.output {
+            Some(output) => output.into_output_options()?,
+            None => Default::default(),
+        };
+        let mut module = ModuleOptions::default();
+        let (parser, rules) = match self.module {
+            Some(options) => (options.parser, options.rules),
+            None => (None, None),
+        };
+        if let Some(rules) = rules {
+            module.rules = rules
+                .into_iter()
+                .enumerate()
+                .map(|(index, rule)| rule.into_module_rule(env, index))
+                .collect::<napi::Result<_>>()?;
+        }
+        if let Some(max_size) = parser
+            .and_then(|parser| parser.asset)
+            .and_then(|asset| asset.data_url_condition)
+            .and_then(|condition| condition.max_size)
+        {
+            module.parser.asset.data_url_max_size = max_size as usize;
+        }
+        // a single entry is named `main`
+        let entry = match entry {
+            Either::A(import) => vec![EntryItem {
+                name: "main".to_string(),
+                import,
+            }],
+            Either::B(entries) => entries
+                .into_iter()
+                .map(|(name, import)| EntryItem { name, import })
+                .collect(),
+        };
+        Ok(CompilerOptions {
+            context,
+            entry,
+            resolve,
+            jsx,
+            parser_backend,
+            output,
+            module,
+            define: self.define.unwrap_or_default(),
+            target,
+            devtool,
+            optimization: self
+                .optimization
+                .map(JsOptimizationOptions::into_optimization_options)
+                .transpose()?
+                .unwrap_or_default(),
+        })
+    }
+}
+
+impl JsOptimizationOptions {
+    pub fn into_optimization_options(self) -> napi::Result<OptimizationOptions> {
+        let mut options = OptimizationOptions::default();
+        if let Some(used_exports) = self.used_exports {
+            options.used_exports = used_exports;
+        }
+        if let Some(side_effects) = self.side_effects {
+            options.side_effects = side_effects;
+        }
+        if let Some(concatenate_modules) = self.concatenate_modules {
+            options.concatenate_modules = concatenate_modules;
+        }
+        if let Some(minimize) = self.minimize {
+            options.minimize = minimize;
+        }
+        if let Some(minimizer) = self.minimizer {
+            if let Some(compress) = minimizer.compress {
+                options.minimizer.compress = compress;
+            }
+            if let Some(mangle) = minimizer.mangle {
+                options.minimizer.mangle = mangle;
+            }
+        }
+        options.split_chunks = match self.split_chunks {
+            Some(Either::A(true)) => Some(Default::default()),
+            Some(Either::B(split_chunks)) => Some(split_chunks.into_split_chunks_options()?),
+            Some(Either::A(false)) | None => None,
+        };
+        options.module_ids = match self.module_ids.as_deref() {
+            None | Some("natural") => ModuleIds::Natural,
+            Some("named") => ModuleIds::Named,
+            Some("deterministic") => ModuleIds::Deterministic,
+            Some(module_ids) => {
+                return Err(invalid_option(format!(
+                    "optimization.moduleIds: expected \"natural\", \"named\" or \"deterministic\", got \"{module_ids}\""
+                )))
+            }
+        };
+        options.chunk_ids = match self.chunk_ids.as_deref() {
+            None | Some("natural") => ChunkIds::Natural,
+            Some("named") => ChunkIds::Named,
+            Some("deterministic") => ChunkIds::Deterministic,
+            Some(chunk_ids) => {
+                return Err(invalid_option(format!(
+                    "optimization.chunkIds: expected \"natural\", \"named\" or \"deterministic\", got \"{chunk_ids}\""
+                )))
+            }
+        };
+        Ok(options)
+    }
+}
+
+impl JsSplitChunksOptions {
+    pub fn into_split_chunks_options(self) -> napi::Result<SplitChunksOptions> {
+        let mut options = SplitChunksOptions::default();
+        if let Some(min_size) = self.min_size {
+            options.min_size = min_size as usize;
+        }
+        if let Some(min_chunks) = self.min_chunks {
+            options.min_chunks = min_chunks as usize;
+        }
+        for (key, cache_group) in self.cache_groups.unwrap_or_default() {
+            let cache_group = match cache_group {
+                Either::A(true) => continue,
+                Either::A(false) => {
+                    options.cache_groups.shift_remove(&key);
+                    continue;
+                }
+                Either::B(cache_group) => cache_group,
+            };
+            let test = cache_group
+                .test
+                .map(|test| {
+                    convert_rule_condition(
+                        &format!("optimization.splitChunks.cacheGroups.{key}.test"),
+                        test,
+                    )
+                })
+                .transpose()?;
+            options.cache_groups.insert(
+                key,
+                CacheGroup {
+                    test,
+                    min_size: cache_group.min_size.map(|min_size| min_size as usize),
+                    min_chunks: cache_group.min_chunks.map(|min_chunks| min_chunks as usize),
+                    priority: cache_group.priority.unwrap_or_default(),
+                    name: cache_group.name,
+                },
+            );
+        }
+        Ok(options)
+    }
+}
+
+impl JsModuleRule {
+    pub fn into_module_rule(self, env: &Env, index: usize) -> napi::Result<ModuleRule> {
+        let name = format!("module.rules[{index}]");
+        let condition = |key: &str, condition: Option<JsRuleCondition>| {
+            condition
+                .map(|condition| convert_rule_condition(&format!("{name}.{key}"), condition))
+                .transpose()
+        };
+        let module_type = self
+            .module_type
+            .map(|module_type| {
+                module_type
+                    .parse::<ModuleType>()
+                    .map_err(|err| invalid_option(format!("{name}.type: {err}")))
+            })
+            .transpose()?;
+        let loaders = self
+            .loaders
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(loader_index, mut callback)| {
+                // unref napi handles to avoid hang problem
+                callback.unref(env)?;
+                Ok(Arc::new(JsLoaderAdapter {
+                    name: format!("{name}.use[{loader_index}]"),
+                    callback,
+                }) as BoxLoader)
+            })
+            .collect::<napi::Result<_>>()?;
+        Ok(ModuleRule {
+            test: condition("test", self.test)?,
+            include: condition("include", self.include)?,
+            exclude: condition("exclude", self.exclude)?,
+            resource_query: condition("resourceQuery", self.resource_query)?,
+            module_type,
+            side_effects: self.side_effects,
+            loaders,
+        })
+    }
+}
+
+fn convert_regexp(name: &str, regexp: JsObject) -> napi::Result<RuleCondition> {
+    let (Some(source), Some(flags)) = (
+        regexp.get_named_property::<Option<String>>("source")?,
+        regexp.get_named_property::<Option<String>>("flags")?,
+    ) else {
+        return Err(invalid_option(format!(
+            "{name}: expected a string or RegExp"
+        )));
+    };
+    let pattern = if flags.contains('i') {
+        format!("(?i){source}")
+    } else {
+        source
+    };
+    Regex::new(&pattern)
+        .map(RuleCondition::Regex)
+        .map_err(|err| invalid_option(format!("{name}: unsupported RegExp /{pattern}/, {err}")))
+}
+
+fn convert_rule_condition(name: &str, condition: JsRuleCondition) -> napi::Result<RuleCondition> {
+    match condition {
+        Either3::A(prefix) => Ok(RuleCondition::String(prefix)),
+        Either3::B(conditions) => conditions
+            .into_iter()
+            .map(|condition| match condition {
+                Either::A(prefix) => Ok(RuleCondition::String(prefix)),
+                Either::B(regexp) => convert_regexp(name, regexp),
+            })
+            .collect::<napi::Result<_>>()
+            .map(RuleCondition::Any),
+        Either3::C(regexp) => convert_regexp(name, regexp),
+    }
+}
+
+impl JsOutputOptions {
+    pub fn into_output_options(self) -> napi::Result<OutputOptions> {
+        let mut options = OutputOptions::default();
+        if let Some(path) = self.path {
+            options.path = path.into();
+        }
+        if let Some(public_path) = self.public_path {
+            options.public_path = public_path;
+        }
+        if let Some(filename) = self.filename {
+            options.filename = filename;
+        }
+        if let Some(css_filename) = self.css_filename {
+            options.css_filename = css_filename;
+        }
+        if let Some(asset_module_filename) = self.asset_module_filename {
+            options.asset_module_filename = asset_module_filename;
+        }
+        options.hash_function = match self.hash_function.as_deref() {
+            None | Some("xxhash64") => HashFunction::Xxhash64,
+            Some("md5") => HashFunction::Md5,
+            Some("sha256") => HashFunction::Sha256,
+            Some(hash_function) => {
+                return Err(invalid_option(format!(
+                    "output.hashFunction: expected \"xxhash64\", \"md5\" or \"sha256\", got \"{hash_function}\""
+                )))
+            }
+        };
+        if let Some(hash_digest_length) = self.hash_digest_length {
+            options.hash_digest_length = hash_digest_length as usize;
+        }
+        options.manifest = self.manifest;
+        if let Some(async_function) = self
+            .environment
+            .and_then(|environment| environment.async_function)
+        {
+            options.environment.async_function = async_function;
+        }
+        Ok(options)
+    }
+}
+
+impl JsJsxOptions {
+    pub fn into_jsx_options(self) -> napi::Result<JsxOptions> {
+        let mut options = JsxOptions::default();
+        if let Some(runtime) = self.runtime {
+            options.runtime = match runtime.as_str() {
+                "classic" => JsxRuntime::Classic,
+                "automatic" => JsxRuntime::Automatic,
+                _ => {
+                    return Err(invalid_option(format!(
+                        "jsx.runtime: expected \"classic\" or \"automatic\", got \"{runtime}\""
+                    )))
+                }
+            };
+        }
+        if let Some(pragma) = self.pragma {
+            options.pragma = pragma;
+        }
+        if let Some(pragma_frag) = self.pragma_frag {
+            options.pragma_frag = pragma_frag;
+        }
+        if let Some(import_source) = self.import_source {
+            options.import_source = import_source;
+        }
+        if let Some(development) = self.development {
+            options.development = development;
+        }
+        Ok(options)
+    }
+}
+
+impl JsResolveOptions {
+    /// the `browser` field of package.json is respected when targeting web, same as webpack
+    pub fn default_resolve_options(target: Target) -> ResolveOptions {
+        let alias_fields = match target {
+            Target::Web => vec![vec!["browser".to_string()]],
+            Target::Node => vec![],
+        };
+        ResolveOptions {
+            extensions: vec![".js", ".ts", ".mjs", ".jsx"]
+                .into_iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>(),
+            alias_fields,
+            ..Default::default()
+        }
+    }
+    /// validate the options passed from js side and convert them into [ResolveOptions]
+    pub fn into_resolve_options(
+        self,
+        context: &Utf8Path,
+        target: Target,
+    ) -> napi::Result<ResolveOptions> {
+        let mut options = Self::default_resolve_options(target);
+        if let Some(alias) = self.alias {
+            options.alias = convert_alias("alias", alias)?;
+        }
+        if let Some(fallback) = self.fallback {
+            options.fallback = convert_alias("fallback", fallback)?;
+        }
+        if let Some(extensions) = self.extensions {
+            if let Some(ext) = extensions.iter().find(|ext| !ext.starts_with('.')) {
+                return Err(invalid_option(format!(
+                    "resolve.extensions: extension \"{ext}\" should start with \".\""
+                )));
+            }
+            options.extensions = extensions;
+        }
+        if let Some(main_fields) = self.main_fields {
+            options.main_fields = non_empty_strings("mainFields", main_fields)?;
+        }
+        if let Some(condition_names) = self.condition_names {
+            options.condition_names = non_empty_strings("conditionNames", condition_names)?;
+        }
+        if let Some(modules) = self.modules {
+            options.modules = non_empty_strings("modules", modules)?;
+        }
+        if let Some(alias_fields) = self.alias_fields {
+            options.alias_fields = non_empty_strings("aliasFields", alias_fields)?
+                .into_iter()
+                .map(|field| vec![field])
+                .collect();
+        }
+        if let Some(symlinks) = self.symlinks {
+            options.symlinks = symlinks;
+        }
+        if let Some(tsconfig) = self.tsconfig {
+            options.tsconfig = Some(convert_tsconfig(tsconfig, context)?);
+        }
+        Ok(options)
+    }
+}
+
+fn non_empty_strings(name: &str, values: Vec<String>) -> napi::Result<Vec<String>> {
+    if values.iter().any(|value| value.is_empty()) {
+        return Err(invalid_option(format!(
+            "resolve.{name}: empty string is not allowed"
+        )));
+    }
+    Ok(values)
+}
+
+fn convert_alias(
+    name: &str,
+    alias: IndexMap<String, JsAliasValue>,
+) -> napi::Result<Vec<(String, Vec<AliasValue>)>> {
+    alias
+        .into_iter()
+        .map(|(key, value)| {
+            if key.is_empty() {
+                return Err(invalid_option(format!(
+                    "resolve.{name}: alias key should not be empty"
+                )));
+            }
+            let values = match value {
+                Either3::A(path) => vec![AliasValue::Path(path)],
+                Either3::B(false) => vec![AliasValue::Ignore],
+                Either3::B(true) => {
+                    return Err(invalid_option(format!(
+                        "resolve.{name}[\"{key}\"]: only `false` is allowed as a boolean value"
+                    )))
+                }
+                Either3::C(paths) => {
+                    if paths.is_empty() {
+                        return Err(invalid_option(format!(
+                            "resolve.{name}[\"{key}\"]: alias list should not be empty"
+                        )));
+                    }
+                    paths.into_iter().map(AliasValue::Path).collect()
+                }
+            };
+            Ok((key, values))
+        })
+        .collect()
+}
+
+fn convert_tsconfig(
+    tsconfig: Either<String, JsTsconfigOptions>,
+    context: &Utf8Path,
+) -> napi::Result<TsconfigOptions> {
+    let (config_file, references) = match tsconfig {
+        Either::A(config_file) => (config_file, None),
+        Either::B(options) => (options.config_file, options.references),
+    };
+    if config_file.is_empty() {
+        return Err(invalid_option(
+            "resolve.tsconfig: configFile should not be empty".to_string(),
+        ));
+    }
+    let references = match references {
+        None => TsconfigReferences::Disabled,
+        Some(Either::A(value)) if value == "auto" => TsconfigReferences::Auto,
+        Some(Either::A(value)) => {
+            return Err(invalid_option(format!(
+                "resolve.tsconfig.references: expected \"auto\" or an array of paths, got \"{value}\""
+            )))
+        }
+        Some(Either::B(paths)) => TsconfigReferences::Paths(
+            paths
+                .into_iter()
+                .map(|path| PathBuf::from(context.join(path)))
+                .collect(),
+        ),
+    };
+    Ok(TsconfigOptions {
+        config_file: PathBuf::from(context.join(config_file)),
+        references,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> &'static Utf8Path {
+        Utf8Path::new("/project")
+    }
+
+    fn convert(options: JsResolveOptions) -> napi::Result<ResolveOptions> {
+        options.into_resolve_options(context(), Target::Node)
+    }
+
+    fn error(options: JsResolveOptions) -> String {
+        convert(options)
+            .expect_err("options should be rejected")
+            .reason
+    }
+
+    #[test]
+    fn defaults_of_omitted_fields() {
+        let options = convert(JsResolveOptions::default()).unwrap();
+        let defaults = ResolveOptions::default();
+        assert_eq!(options.extensions, [".js", ".ts", ".mjs", ".jsx"]);
+        assert_eq!(options.main_fields, defaults.main_fields);
+        assert_eq!(options.condition_names, defaults.condition_names);
+        assert_eq!(options.modules, defaults.modules);
+        assert_eq!(options.alias_fields, defaults.alias_fields);
+        assert_eq!(options.symlinks, defaults.symlinks);
+        assert!(options.alias.is_empty());
+        assert!(options.fallback.is_empty());
+        assert!(options.tsconfig.is_none());
+    }
+
+    #[test]
+    fn alias_fields_of_target() {
+        let web = JsResolveOptions::default()
+            .into_resolve_options(context(), Target::Web)
+            .unwrap();
+        assert_eq!(web.alias_fields, [["browser"]]);
+        let node = JsResolveOptions::default()
+            .into_resolve_options(context(), Target::Node)
+            .unwrap();
+        assert!(node.alias_fields.is_empty());
+        // configured fields replace the default
+        let options = JsResolveOptions {
+            alias_fields: Some(vec![]),
+            ..Default::default()
+        }
+        .into_resolve_options(context(), Target::Web)
+        .unwrap();
+        assert!(options.alias_fields.is_empty());
+    }
+
+    #[test]
+    fn extensions_should_start_with_dot() {
+        assert_eq!(
+            error(JsResolveOptions {
+                extensions: Some(vec![".js".to_string(), "ts".to_string()]),
+                ..Default::default()
+            }),
+            "resolve.extensions: extension \"ts\" should start with \".\""
+        );
+    }
+
+    #[test]
+    fn empty_lists_override_defaults() {
+        let options = convert(JsResolveOptions {
+            extensions: Some(vec![]),
+            main_fields: Some(vec![]),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(options.extensions.is_empty());
+        assert!(options.main_fields.is_empty());
+        assert_eq!(
+            error(JsResolveOptions {
+                main_fields: Some(vec!["module".to_string(), String::new()]),
+                ..Default::default()
+            }),
+            "resolve.mainFields: empty string is not allowed"
+        );
+    }
+
+    #[test]
+    fn alias_values() {
+        let options = convert(JsResolveOptions {
+            alias: Some(IndexMap::from_iter([
+                ("a".to_string(), Either3::A("./b".to_string())),
+                ("c".to_string(), Either3::B(false)),
+                (
+                    "d".to_string(),
+                    Either3::C(vec!["./e".to_string(), "./f".to_string()]),
+                ),
+            ])),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            options.alias,
+            [
+                ("a".to_string(), vec![AliasValue::Path("./b".to_string())]),
+                ("c".to_string(), vec![AliasValue::Ignore]),
+                (
+                    "d".to_string(),
+                    vec![
+                        AliasValue::Path("./e".to_string()),
+                        AliasValue::Path("./f".to_string())
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_alias_values() {
+        let alias = |key: &str, value: JsAliasValue| JsResolveOptions {
+            alias: Some(IndexMap::from_iter([(key.to_string(), value)])),
+            ..Default::default()
+        };
+        assert_eq!(
+            error(alias("a", Either3::B(true))),
+            "resolve.alias[\"a\"]: only `false` is allowed as a boolean value"
+        );
+        assert_eq!(
+            error(alias("a", Either3::C(vec![]))),
+            "resolve.alias[\"a\"]: alias list should not be empty"
+        );
+        assert_eq!(
+            error(alias("", Either3::A("./a".to_string()))),
+            "resolve.alias: alias key should not be empty"
+        );
+        assert_eq!(
+            error(JsResolveOptions {
+                fallback: Some(IndexMap::from_iter([("a".to_string(), Either3::B(true))])),
+                ..Default::default()
+            }),
+            "resolve.fallback[\"a\"]: only `false` is allowed as a boolean value"
+        );
+    }
+
+    #[test]
+    fn tsconfig_with_references() {
+        let tsconfig = |references| JsResolveOptions {
+            tsconfig: Some(Either::B(JsTsconfigOptions {
+                config_file: "tsconfig.json".to_string(),
+                references,
+            })),
+            ..Default::default()
+        };
+        let options = convert(tsconfig(Some(Either::B(vec![
+            "packages/a".to_string(),
+            "packages/b/tsconfig.json".to_string(),
+        ]))))
+        .unwrap()
+        .tsconfig
+        .unwrap();
+        assert_eq!(options.config_file, PathBuf::from("/project/tsconfig.json"));
+        assert!(matches!(
+            options.references,
+            TsconfigReferences::Paths(paths) if paths == [
+                PathBuf::from("/project/packages/a"),
+                PathBuf::from("/project/packages/b/tsconfig.json"),
+            ]
+        ));
+        let options = convert(tsconfig(Some(Either::A("auto".to_string()))))
+            .unwrap()
+            .tsconfig
+            .unwrap();
+        assert!(matches!(options.references, TsconfigReferences::Auto));
+        assert_eq!(
+            error(tsconfig(Some(Either::A("all".to_string())))),
+            "resolve.tsconfig.references: expected \"auto\" or an array of paths, got \"all\""
+        );
+        // a path only is the same as no references
+        let options = convert(JsResolveOptions {
+            tsconfig: Some(Either::A("tsconfig.json".to_string())),
+            ..Default::default()
+        })
+        .unwrap()
+        .tsconfig
+        .unwrap();
+        assert!(matches!(options.references, TsconfigReferences::Disabled));
+        assert_eq!(
+            error(JsResolveOptions {
+                tsconfig: Some(Either::A(String::new())),
+                ..Default::default()
+            }),
+            "resolve.tsconfig: configFile should not be empty"
+        );
+    }
+}
@@ -9,9 +9,22 @@ use napi::{
 use napi_derive::napi;
 use std::{fmt::Debug, future::IntoFuture, sync::Arc};
 use unpack::errors::miette::Result;
-use unpack::plugin::{CompilationCell, LoadArgs, Plugin, PluginContext, ResolveArgs};
+use unpack::plugin::{CompilationCell, LoadArgs, LoadResult, Plugin, PluginContext, ResolveArgs};
+use unpack::resolver::ResolveResult;
 
 use crate::js_compilation::JsCompilation;
+use crate::js_loader::JsLoaderOutput;
+
+/// content of the module, or the code with its source map
+pub type JsLoadResult = Either<Buffer, JsLoaderOutput>;
+
+/// resolved path with the flags of the module
+#[napi(object, object_to_js = false)]
+pub struct JsResolveResult {
+    pub path: String,
+    /// `false` if the module is side effect free, it's left out if none of its exports is used
+    pub side_effects: Option<bool>,
+}
 
 
 #[napi(object, object_to_js = false)]
@@ -35,19 +48,19 @@ impl Plugin for JsPluginAdapter {
         let compilation = JsCompilation::from_compilation(External::new(compilation));
         let (send, mut recv) = unbounded_channel();
         let Some(callback) = &self.this_compilation else {
-            return ();
+            return;
         };
         callback.call_with_return_value(
             compilation,
             napi::threadsafe_function::ThreadsafeFunctionCallMode::Blocking,
-            move |ret:()| {
-                send.send(());
+            move |_ret: ()| {
+                let _ = send.send(());
                 Ok(())
             },
         );
         recv.recv().await.unwrap();
     }
-    async fn load(&self, _ctx: Arc<PluginContext>, args: LoadArgs) -> Result<Option<Vec<u8>>> {
+    async fn load(&self, _ctx: Arc<PluginContext>, args: LoadArgs) -> Result<Option<LoadResult>> {
         let (send, mut recv) = unbounded_channel();
         let Some(callback) = &self.on_load else {
             return Ok(None);
@@ -55,7 +68,7 @@ impl Plugin for JsPluginAdapter {
         callback.call_with_return_value(
             args.path.to_string(),
             napi::threadsafe_function::ThreadsafeFunctionCallMode::Blocking,
-            move |ret: Either<Option<Buffer>, Promise<Option<Buffer>>>| {
+            move |ret: Either<Option<JsLoadResult>, Promise<Option<JsLoadResult>>>| {
                 let _ = send.send(ret);
                 Ok(())
             },
@@ -70,9 +83,19 @@ impl Plugin for JsPluginAdapter {
                 // block_on(s.into_future()).unwrap()
             }
         };
-        Ok(result.map(|x| x.into()))
+        let Some(result) = result else {
+            return Ok(None);
+        };
+        let (content, source_map) = match result {
+            Either::A(buffer) => (buffer.into(), None),
+            Either::B(output) => output.into_content()?,
+        };
+        Ok(Some(LoadResult {
+            content,
+            source_map,
+        }))
     }
-    async fn resolve(&self, _ctx: Arc<PluginContext>, args: ResolveArgs) -> Result<Option<String>> {
+    async fn resolve(&self, _ctx: Arc<PluginContext>, args: ResolveArgs) -> Result<Option<ResolveResult>> {
         let (send, mut recv) = unbounded_channel();
         let Some(callback) = &self.on_resolve else {
             return Ok(None);
@@ -80,13 +103,23 @@ impl Plugin for JsPluginAdapter {
         callback.call_with_return_value(
             args.path.to_string(),
             napi::threadsafe_function::ThreadsafeFunctionCallMode::Blocking,
-            move |ret: Option<String>| {
+            move |ret: Option<Either<String, JsResolveResult>>| {
                 let _ = send.send(ret);
                 Ok(())
             },
         );
 
         let result = recv.recv().await.unwrap();
-        Ok(result)
+        Ok(result.map(|result| {
+            let (path, side_effects) = match result {
+                Either::A(path) => (path, None),
+                Either::B(result) => (result.path, result.side_effects),
+            };
+            ResolveResult {
+                path: path.into(),
+                query: String::new(),
+                side_effects,
+            }
+        }))
     }
 }
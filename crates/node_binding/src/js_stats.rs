@@ -0,0 +1,68 @@
+use napi_derive::napi;
+use unpack::stats::{Stats, StatsChunk, StatsModule};
+
+#[napi(object)]
+pub struct JsStats {
+    pub modules: Vec<JsStatsModule>,
+    pub chunks: Vec<JsStatsChunk>,
+    /// all the emitted files
+    pub assets: Vec<String>,
+    /// `[fullhash]` of the compilation
+    pub hash: String,
+}
+
+#[napi(object)]
+pub struct JsStatsChunk {
+    pub id: String,
+    pub name: Option<String>,
+    /// emitted files with the hashes filled in
+    pub files: Vec<String>,
+    /// `[chunkhash]` of the chunk
+    pub hash: String,
+}
+
+#[napi(object)]
+pub struct JsStatsModule {
+    pub identifier: String,
+    /// `null` if the exports are unknown statically, e.g. commonjs
+    pub provided_exports: Option<Vec<String>>,
+    /// `null` if every export is used, e.g. by a namespace import
+    pub used_exports: Option<Vec<String>>,
+    pub unused_exports: Vec<String>,
+    /// why optimizations are not applied, e.g. the module can't be concatenated
+    pub optimization_bailout: Vec<String>,
+}
+
+impl From<&Stats> for JsStats {
+    fn from(stats: &Stats) -> Self {
+        Self {
+            modules: stats.modules.iter().map(JsStatsModule::from).collect(),
+            chunks: stats.chunks.iter().map(JsStatsChunk::from).collect(),
+            assets: stats.assets.clone(),
+            hash: stats.hash.clone(),
+        }
+    }
+}
+
+impl From<&StatsModule> for JsStatsModule {
+    fn from(module: &StatsModule) -> Self {
+        Self {
+            identifier: module.identifier.clone(),
+            provided_exports: module.provided_exports.clone(),
+            used_exports: module.used_exports.clone(),
+            unused_exports: module.unused_exports.clone(),
+            optimization_bailout: module.optimization_bailout.clone(),
+        }
+    }
+}
+
+impl From<&StatsChunk> for JsStatsChunk {
+    fn from(chunk: &StatsChunk) -> Self {
+        Self {
+            id: chunk.id.clone(),
+            name: chunk.name.clone(),
+            files: chunk.files.clone(),
+            hash: chunk.hash.clone(),
+        }
+    }
+}
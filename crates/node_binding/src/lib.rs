@@ -1,4 +1,7 @@
 #![deny(clippy::all)]
 mod js_compilation;
 mod js_compiler;
+mod js_loader;
+mod js_options;
 mod js_plugin;
+mod js_stats;
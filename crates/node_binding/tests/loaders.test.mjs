@@ -0,0 +1,59 @@
+import assert from 'node:assert/strict';
+import { mkdtempSync } from 'node:fs';
+import { createRequire } from 'node:module';
+import { tmpdir } from 'node:os';
+import path from 'node:path';
+import test from 'node:test';
+
+const { JsCompiler } = createRequire(import.meta.url)('../index.js');
+const context = path.join(import.meta.dirname, 'fixtures/loaders');
+
+/** build the fixture with `loader` for `.txt` files, returns the diagnostics */
+async function build(loader) {
+    const compiler = new JsCompiler(context, './index.mjs', [], {
+        output: { path: mkdtempSync(path.join(tmpdir(), 'unpack-loaders-')) },
+        module: {
+            rules: [{ test: /\.txt$/, use: [loader] }],
+        },
+    });
+    await compiler.build(() => {});
+    return compiler.getDiagnostics();
+}
+
+/** the error of the loader is reported as a failure of the module */
+function assertFailure(diagnostics, message) {
+    assert.equal(diagnostics.length, 1, diagnostics.join('\n'));
+    assert.match(diagnostics[0], /^loader "[^"]+" failed to process .*data\.txt: /);
+    assert.ok(diagnostics[0].includes(message), diagnostics[0]);
+}
+
+test('loader result', async () => {
+    const diagnostics = await build((err, content, loaderContext) => {
+        assert.equal(err, null);
+        assert.equal(loaderContext.resourcePath, path.join(context, 'data.txt'));
+        return `export default ${JSON.stringify(content.toString())};`;
+    });
+    assert.deepEqual(diagnostics, []);
+});
+
+test('loader which throws', async () => {
+    const diagnostics = await build(() => {
+        throw new Error('sync failure');
+    });
+    assertFailure(diagnostics, 'Error: sync failure');
+});
+
+test('loader which returns a rejected promise', async () => {
+    const diagnostics = await build(() => Promise.reject(new Error('async failure')));
+    assertFailure(diagnostics, 'Error: async failure');
+});
+
+test('loader which returns undefined', async () => {
+    const diagnostics = await build(() => undefined);
+    assertFailure(diagnostics, 'the loader should return a string');
+});
+
+test('loader which resolves undefined', async () => {
+    const diagnostics = await build(async () => undefined);
+    assertFailure(diagnostics, 'the loader should return a string');
+});
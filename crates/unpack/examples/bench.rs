@@ -2,7 +2,8 @@ use rspack_resolver::ResolveOptions;
 use std::{path::PathBuf, sync::Arc};
 use unpack::compiler::{Compiler, CompilerOptions, EntryItem};
 
-fn main() {
+#[tokio::main]
+async fn main() {
     let current_file = file!();
     dbg!(current_file);
     let context = PathBuf::from(current_file)
@@ -22,7 +23,8 @@ fn main() {
                 .collect::<Vec<_>>(),
             ..Default::default()
         },
+        ..Default::default()
     };
     let mut compiler = Compiler::new(Arc::new(compiler_options), vec![]);
-    compiler.build();
+    compiler.build().await;
 }
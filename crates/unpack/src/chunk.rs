@@ -2,11 +2,13 @@ mod chunk_graph;
 mod chunk_graph_chunk;
 mod chunk_graph_module;
 mod chunk_group;
+mod chunk_hash;
 mod chunk_linker;
 mod chunk_node;
 pub use chunk_graph::*;
 pub use chunk_graph_chunk::*;
 pub use chunk_graph_module::*;
 pub use chunk_group::*;
+pub use chunk_hash::*;
 pub use chunk_linker::*;
 pub use chunk_node::*;
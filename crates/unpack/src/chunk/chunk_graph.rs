@@ -2,11 +2,14 @@ use index_vec::IndexVec;
 use indexmap::IndexSet;
 use rustc_hash::FxHashMap;
 
+use crate::compiler::OutputOptions;
+use crate::ids::PublicId;
 use crate::module::ModuleId;
+use crate::utils::hash::replace_hash;
 
 use super::{
-    chunk_group::ChunkGroup, Chunk, ChunkGraphChunk, ChunkGraphChunkId, ChunkGraphModule,
-    ChunkGraphModuleId, ChunkGroupId, ChunkId,
+    chunk_group::ChunkGroup, Chunk, ChunkFileType, ChunkGraphChunk, ChunkGraphChunkId, ChunkGraphModule,
+    ChunkGraphModuleId, ChunkGroupId, ChunkId, HashPlaceholder,
 };
 
 #[derive(Debug, Default)]
@@ -19,6 +22,9 @@ pub struct ChunkGraph {
     chunk_id_to_chunk_graph_chunk_id: FxHashMap<ChunkId, ChunkGraphChunkId>,
     module_id_to_chunk_graph_module_id: FxHashMap<ModuleId, ChunkGraphModuleId>,
     chunk_groups: IndexVec<ChunkGroupId, ChunkGroup>,
+    /// ids rendered in the output, assigned after the chunk graph is optimized
+    module_ids: FxHashMap<ModuleId, PublicId>,
+    chunk_ids: FxHashMap<ChunkId, PublicId>,
 }
 
 impl ChunkGraph {
@@ -52,6 +58,17 @@ impl ChunkGraph {
     pub fn chunk_by_id(&self, chunk_id: ChunkId) -> &Chunk {
         &self.chunks[chunk_id]
     }
+    pub fn chunk_by_name(&self, name: &str) -> Option<ChunkId> {
+        self.named_chunks.get(name).copied()
+    }
+    /// chunk groups which the chunk is in
+    pub fn get_chunk_groups(&self, chunk_id: ChunkId) -> Vec<ChunkGroupId> {
+        self.chunk_groups
+            .iter_enumerated()
+            .filter(|(_, chunk_group)| chunk_group.chunks().contains(&chunk_id))
+            .map(|(chunk_group_id, _)| chunk_group_id)
+            .collect()
+    }
     pub fn chunk_group_by_id(&self, chunk_group_id: ChunkGroupId) -> &ChunkGroup {
         &self.chunk_groups[chunk_group_id]
     }
@@ -60,10 +77,19 @@ impl ChunkGraph {
     }
     pub fn connect_chunk_and_entry_module(
         &mut self,
-        _chunk_id: ChunkId,
-        _module_id: ModuleId,
-        _entry_point_id: ChunkGroupId,
+        chunk_id: ChunkId,
+        module_id: ModuleId,
+        entry_point_id: ChunkGroupId,
     ) {
+        let chunk_graph_module_id = self.chunk_graph_module_id_by_module_id(module_id);
+        let chunk_graph_module = self.chunk_graph_module_by_id_mut(chunk_graph_module_id);
+        chunk_graph_module.entry_in_chunks.insert(chunk_id);
+
+        let chunk_graph_chunk_id = self.chunk_graph_chunk_id_by_chunk_id(chunk_id);
+        let chunk_graph_chunk = self.chunk_graph_chunk_by_id_mut(chunk_graph_chunk_id);
+        chunk_graph_chunk
+            .entry_modules
+            .insert(module_id, entry_point_id);
     }
     pub fn chunk_graph_chunk_by_id(
         &self,
@@ -96,6 +122,16 @@ impl ChunkGraph {
         let chunk_graph_chunk = self.chunk_graph_chunk_by_id(cgc_id);
         chunk_graph_chunk.modules.contains(&module_id)
     }
+    /// modules which are left out, e.g. unused side effect free modules, are not in any chunk
+    pub fn is_module_in_any_chunk(&self, module_id: ModuleId) -> bool {
+        self.module_id_to_chunk_graph_module_id
+            .get(&module_id)
+            .is_some_and(|&chunk_graph_module_id| {
+                !self.chunk_graph_module_by_id(chunk_graph_module_id)
+                    .chunks
+                    .is_empty()
+            })
+    }
     pub fn chunk_graph_module_id_by_module_id(
         &mut self,
         module_id: ModuleId,
@@ -106,7 +142,10 @@ impl ChunkGraph {
             } else {
                 let chunk_graph_module = ChunkGraphModule::new();
 
-                self.chunk_graph_modules.push(chunk_graph_module)
+                let chunk_graph_module_id = self.chunk_graph_modules.push(chunk_graph_module);
+                self.module_id_to_chunk_graph_module_id
+                    .insert(module_id, chunk_graph_module_id);
+                chunk_graph_module_id
             };
         chunk_graph_module_id
     }
@@ -134,9 +173,93 @@ impl ChunkGraph {
         let chunk_graph_chunk = self.chunk_graph_chunk_by_id_mut(chunk_graph_chunk_id);
         chunk_graph_chunk.modules.insert(module_id);
     }
+    /// remove the module from the chunk, e.g. when it's concatenated into another module
+    pub fn disconnect_chunk_and_module(&mut self, chunk_id: ChunkId, module_id: ModuleId) {
+        let chunk_graph_module_id = self.chunk_graph_module_id_by_module_id(module_id);
+        let chunk_graph_module = self.chunk_graph_module_by_id_mut(chunk_graph_module_id);
+        chunk_graph_module.chunks.shift_remove(&chunk_id);
+
+        let chunk_graph_chunk_id = self.chunk_graph_chunk_id_by_chunk_id(chunk_id);
+        let chunk_graph_chunk = self.chunk_graph_chunk_by_id_mut(chunk_graph_chunk_id);
+        chunk_graph_chunk.modules.shift_remove(&module_id);
+    }
+    /// chunks which contain the module
+    pub fn get_module_chunks(&self, module_id: ModuleId) -> IndexSet<ChunkId> {
+        self.module_id_to_chunk_graph_module_id
+            .get(&module_id)
+            .map(|&chunk_graph_module_id| {
+                self.chunk_graph_module_by_id(chunk_graph_module_id)
+                    .chunks
+                    .clone()
+            })
+            .unwrap_or_default()
+    }
+    pub fn get_chunk_entry_modules(&mut self, chunk_id: ChunkId) -> Vec<ModuleId> {
+        let chunk_graph_chunk_id = self.chunk_graph_chunk_id_by_chunk_id(chunk_id);
+        let chunk_graph_chunk = self.chunk_graph_chunk_by_id(chunk_graph_chunk_id);
+        chunk_graph_chunk.entry_modules.keys().copied().collect()
+    }
+    /// chunks which the module is an entry of
+    pub fn get_module_entry_chunks(&self, module_id: ModuleId) -> Vec<ChunkId> {
+        self.module_id_to_chunk_graph_module_id
+            .get(&module_id)
+            .map(|&chunk_graph_module_id| {
+                self.chunk_graph_module_by_id(chunk_graph_module_id)
+                    .entry_in_chunks
+                    .iter()
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
     pub fn get_chunk_modules(&mut self, chunk_id: ChunkId) -> IndexSet<ModuleId> {
         let chunk_graph_chunk_id = self.chunk_graph_chunk_id_by_chunk_id(chunk_id);
         let chunk_graph_chunk = self.chunk_graph_chunk_by_id_mut(chunk_graph_chunk_id);
         chunk_graph_chunk.modules.clone()
     }
+    pub fn set_module_id(&mut self, module_id: ModuleId, id: PublicId) {
+        self.module_ids.insert(module_id, id);
+    }
+    /// id of the module in the output, e.g. the argument of `__unpack_require__`
+    pub fn get_module_id(&self, module_id: ModuleId) -> &PublicId {
+        self.module_ids
+            .get(&module_id)
+            .expect("should have module id")
+    }
+    pub fn set_chunk_id(&mut self, chunk_id: ChunkId, id: PublicId) {
+        self.chunk_ids.insert(chunk_id, id);
+    }
+    /// id of the chunk in the output, which the runtime tracks loaded chunks by
+    pub fn get_chunk_id(&self, chunk_id: ChunkId) -> &PublicId {
+        self.chunk_ids.get(&chunk_id).expect("should have chunk id")
+    }
+    /// fill `[name]` and `[id]` of the filename of the chunk file, chunks without name are
+    /// named after their ids. hashes are filled with placeholders, see `HashPlaceholder`
+    pub fn get_chunk_filename(
+        &self,
+        output: &OutputOptions,
+        chunk_id: ChunkId,
+        file_type: ChunkFileType,
+    ) -> String {
+        let id = self.get_chunk_id(chunk_id).to_string();
+        let name = self.chunk_by_id(chunk_id).name.as_deref().unwrap_or(&id);
+        let filename_template = match file_type {
+            ChunkFileType::Js => &output.filename,
+            ChunkFileType::Css => &output.css_filename,
+        };
+        let filename = filename_template
+            .replace("[name]", name)
+            .replace("[id]", &id);
+        [
+            ("contenthash", HashPlaceholder::Content(chunk_id, file_type)),
+            ("chunkhash", HashPlaceholder::Chunk(chunk_id)),
+            ("fullhash", HashPlaceholder::Full),
+        ]
+        .into_iter()
+        .fold(filename, |filename, (name, placeholder)| {
+            replace_hash(&filename, name, output.hash_digest_length, |length| {
+                placeholder.render(length)
+            })
+        })
+    }
 }
@@ -3,11 +3,11 @@ use indexmap::{IndexMap, IndexSet};
 
 use crate::module::ModuleId;
 
-use super::ChunkId;
+use super::ChunkGroupId;
 
 #[derive(Debug)]
 pub struct ChunkGraphChunk {
-    pub entry_modules: IndexMap<ModuleId, ChunkId>,
+    pub entry_modules: IndexMap<ModuleId, ChunkGroupId>,
     pub modules: IndexSet<ModuleId>,
     pub runtime_modules: IndexSet<ModuleId>,
 }
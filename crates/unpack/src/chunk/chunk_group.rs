@@ -28,6 +28,24 @@ impl ChunkGroup {
     pub fn connect_chunk(&mut self, chunk_id: ChunkId) {
         self.chunks.push(chunk_id);
     }
+    /// insert the chunk before `before` so that it's loaded earlier, returns false if the
+    /// chunk is in the group already
+    pub fn insert_chunk(&mut self, chunk_id: ChunkId, before: ChunkId) -> bool {
+        if self.chunks.contains(&chunk_id) {
+            return false;
+        }
+        let index = self
+            .chunks
+            .iter()
+            .position(|&id| id == before)
+            .unwrap_or(self.chunks.len());
+        self.chunks.insert(index, chunk_id);
+        true
+    }
+    /// chunks in loading order
+    pub fn chunks(&self) -> &[ChunkId] {
+        &self.chunks
+    }
 }
 
 use index_vec::define_index_type;
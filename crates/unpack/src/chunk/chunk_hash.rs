@@ -0,0 +1,269 @@
+use std::ops::Range;
+
+use indexmap::IndexSet;
+use regex::Regex;
+use rspack_sources::{BoxSource, ReplaceSource, SourceExt};
+use rustc_hash::FxHashMap;
+
+use super::ChunkId;
+use crate::compiler::HashFunction;
+use crate::utils::hash::{output_hash, shorten_hash, OutputHasher};
+
+/// files emitted for a chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChunkFileType {
+    Js,
+    Css,
+}
+
+impl ChunkFileType {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ChunkFileType::Js => "js",
+            ChunkFileType::Css => "css",
+        }
+    }
+}
+
+/// a hash in the filename of a chunk, which is known only after the chunks are rendered. the
+/// code referencing the chunk renders a placeholder instead, which is replaced when the chunks
+/// are hashed, similar with webpack's RealContentHashPlugin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashPlaceholder {
+    /// `[contenthash]`, hash of the file
+    Content(ChunkId, ChunkFileType),
+    /// `[chunkhash]`, hash of all the files of the chunk
+    Chunk(ChunkId),
+    /// `[fullhash]`, hash of all the chunks
+    Full,
+}
+
+impl HashPlaceholder {
+    /// placeholder of the first `length` characters of the hash
+    pub fn render(self, length: usize) -> String {
+        match self {
+            HashPlaceholder::Content(chunk_id, file_type) => format!(
+                "__unpack_contenthash_{}_{}_{length}__",
+                file_type.extension(),
+                chunk_id.index()
+            ),
+            HashPlaceholder::Chunk(chunk_id) => {
+                format!("__unpack_chunkhash_{}_{length}__", chunk_id.index())
+            }
+            HashPlaceholder::Full => format!("__unpack_fullhash_{length}__"),
+        }
+    }
+    fn chunk_id(self) -> Option<ChunkId> {
+        match self {
+            HashPlaceholder::Content(chunk_id, _) | HashPlaceholder::Chunk(chunk_id) => {
+                Some(chunk_id)
+            }
+            HashPlaceholder::Full => None,
+        }
+    }
+}
+
+/// placeholders in the code with their ranges and lengths
+fn find_hash_placeholders(code: &str) -> Vec<(Range<usize>, HashPlaceholder, usize)> {
+    let regex =
+        Regex::new(r"__unpack_(?:contenthash_(js|css)_(\d+)|chunkhash_(\d+)|fullhash)_(\d+)__")
+            .expect("should be valid regex");
+    regex
+        .captures_iter(code)
+        .filter_map(|captures| {
+            let range = captures.get(0)?.range();
+            let chunk_id = |index| {
+                captures
+                    .get(index)
+                    .and_then(|id| id.as_str().parse::<usize>().ok())
+                    .map(ChunkId::from_usize)
+            };
+            let placeholder = match captures.get(1).map(|file_type| file_type.as_str()) {
+                Some("js") => HashPlaceholder::Content(chunk_id(2)?, ChunkFileType::Js),
+                Some(_) => HashPlaceholder::Content(chunk_id(2)?, ChunkFileType::Css),
+                None => match chunk_id(3) {
+                    Some(chunk_id) => HashPlaceholder::Chunk(chunk_id),
+                    None => HashPlaceholder::Full,
+                },
+            };
+            let length = captures.get(4)?.as_str().parse().ok()?;
+            Some((range, placeholder, length))
+        })
+        .collect()
+}
+
+/// a rendered file of a chunk, hashes in its filename and code are placeholders
+#[derive(Debug, Clone)]
+pub struct ChunkFile {
+    pub chunk_id: ChunkId,
+    pub file_type: ChunkFileType,
+    pub filename: String,
+    pub source: BoxSource,
+}
+
+#[derive(Debug, Default)]
+pub struct ChunkHashes {
+    pub content_hashes: FxHashMap<(ChunkId, ChunkFileType), String>,
+    pub chunk_hashes: FxHashMap<ChunkId, String>,
+    pub full_hash: String,
+}
+
+impl ChunkHashes {
+    fn get(&self, placeholder: HashPlaceholder, length: usize) -> Option<&str> {
+        let hash = match placeholder {
+            HashPlaceholder::Content(chunk_id, file_type) => {
+                self.content_hashes.get(&(chunk_id, file_type))?
+            }
+            HashPlaceholder::Chunk(chunk_id) => self.chunk_hashes.get(&chunk_id)?,
+            HashPlaceholder::Full => &self.full_hash,
+        };
+        Some(shorten_hash(hash, length))
+    }
+    /// the code to compute the content hash of a file of `chunk_id` from, placeholders of the
+    /// chunk itself are replaced with a fixed token, and the ones of chunks which are not hashed
+    /// yet because of cycles are replaced with the hashes of their code with placeholders
+    fn code_to_hash(
+        &self,
+        chunk_id: ChunkId,
+        code: &str,
+        placeholders: &[(Range<usize>, HashPlaceholder, usize)],
+        raw_hashes: &FxHashMap<ChunkId, String>,
+    ) -> String {
+        let mut result = String::with_capacity(code.len());
+        let mut last = 0;
+        for (range, placeholder, length) in placeholders {
+            let hash = match placeholder.chunk_id() {
+                Some(id) if id == chunk_id => Some("__unpack_self__"),
+                id => self
+                    .get(*placeholder, *length)
+                    .or_else(|| raw_hashes.get(&id?).map(String::as_str)),
+            };
+            let Some(hash) = hash else {
+                continue;
+            };
+            result.push_str(&code[last..range.start]);
+            result.push_str(hash);
+            last = range.end;
+        }
+        result.push_str(&code[last..]);
+        result
+    }
+    /// replace the placeholders of known hashes, others are kept
+    fn replace(
+        &self,
+        code: &str,
+        placeholders: &[(Range<usize>, HashPlaceholder, usize)],
+    ) -> String {
+        let mut result = String::with_capacity(code.len());
+        let mut last = 0;
+        for (range, placeholder, length) in placeholders {
+            if let Some(hash) = self.get(*placeholder, *length) {
+                result.push_str(&code[last..range.start]);
+                result.push_str(hash);
+                last = range.end;
+            }
+        }
+        result.push_str(&code[last..]);
+        result
+    }
+}
+
+/// hash the files of chunks and replace the placeholders in their filenames and code. the full
+/// hash is the hash of all the files with placeholders, a chunk is hashed after the chunks it
+/// references so that its hash changes with their filenames, chunks referencing each other are
+/// hashed with the hashes of the code of the others, which still changes with their content
+pub fn hash_chunk_files(
+    files: Vec<ChunkFile>,
+    hash_function: HashFunction,
+) -> (Vec<ChunkFile>, ChunkHashes) {
+    let codes = files
+        .iter()
+        .map(|file| file.source.source().into_owned())
+        .collect::<Vec<_>>();
+    let placeholders = codes
+        .iter()
+        .map(|code| find_hash_placeholders(code))
+        .collect::<Vec<_>>();
+    let mut full_hasher = OutputHasher::new(hash_function);
+    for (file, code) in files.iter().zip(&codes) {
+        full_hasher.update(file.filename.as_bytes());
+        full_hasher.update(code.as_bytes());
+    }
+    let mut hashes = ChunkHashes {
+        full_hash: full_hasher.digest(),
+        ..Default::default()
+    };
+    let mut raw_hashers = FxHashMap::default();
+    for (file, code) in files.iter().zip(&codes) {
+        raw_hashers
+            .entry(file.chunk_id)
+            .or_insert_with(|| OutputHasher::new(hash_function))
+            .update(code.as_bytes());
+    }
+    let raw_hashes = raw_hashers
+        .into_iter()
+        .map(|(chunk_id, hasher)| (chunk_id, hasher.digest()))
+        .collect::<FxHashMap<_, _>>();
+
+    let mut pending = files
+        .iter()
+        .map(|file| file.chunk_id)
+        .collect::<IndexSet<_>>();
+    while !pending.is_empty() {
+        let referenced_chunks_hashed = |chunk_id: ChunkId| {
+            files
+                .iter()
+                .zip(&placeholders)
+                .filter(|(file, _)| file.chunk_id == chunk_id)
+                .flat_map(|(_, placeholders)| placeholders)
+                .filter_map(|(_, placeholder, _)| placeholder.chunk_id())
+                .all(|id| id == chunk_id || hashes.chunk_hashes.contains_key(&id))
+        };
+        let index = pending
+            .iter()
+            .position(|&chunk_id| referenced_chunks_hashed(chunk_id))
+            .unwrap_or(0);
+        let chunk_id = pending
+            .shift_remove_index(index)
+            .expect("should have pending chunk");
+        let mut chunk_hasher = OutputHasher::new(hash_function);
+        for (index, file) in files.iter().enumerate() {
+            if file.chunk_id != chunk_id {
+                continue;
+            }
+            let code =
+                hashes.code_to_hash(chunk_id, &codes[index], &placeholders[index], &raw_hashes);
+            let content_hash = output_hash(hash_function, code.as_bytes());
+            chunk_hasher.update(content_hash.as_bytes());
+            hashes
+                .content_hashes
+                .insert((chunk_id, file.file_type), content_hash);
+        }
+        hashes.chunk_hashes.insert(chunk_id, chunk_hasher.digest());
+    }
+
+    let files = files
+        .into_iter()
+        .zip(placeholders)
+        .map(|(file, placeholders)| {
+            let filename = hashes.replace(&file.filename, &find_hash_placeholders(&file.filename));
+            let source = if placeholders.is_empty() {
+                file.source
+            } else {
+                let mut source = ReplaceSource::new(file.source);
+                for (range, placeholder, length) in placeholders {
+                    if let Some(hash) = hashes.get(placeholder, length) {
+                        source.replace(range.start as u32, range.end as u32, hash, None);
+                    }
+                }
+                source.boxed()
+            };
+            ChunkFile {
+                filename,
+                source,
+                ..file
+            }
+        })
+        .collect();
+    (files, hashes)
+}
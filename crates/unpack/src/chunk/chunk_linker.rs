@@ -8,6 +8,7 @@ use crate::{
     dependency::BlockId,
     errors::Diagnostics,
     module::{EntryData, ModuleGraph, ModuleId},
+    optimize::ConcatenatedModule,
 };
 #[derive(Debug)]
 enum QueueAction {
@@ -59,6 +60,11 @@ impl ChunkLinker {
                 .get_entry_point_chunk()
                 .expect("should get entry_chunk");
             for module_id in module_ids {
+                state.chunk_graph.connect_chunk_and_entry_module(
+                    entry_point_chunk_id,
+                    module_id,
+                    chunk_group_id,
+                );
                 state
                     .queue
                     .push_back(QueueAction::AddAndEnterModule(AddAndEnterModule {
@@ -137,22 +143,62 @@ impl ChunkLinker {
         let module_id = action.module_id;
         let connection_ids = state.module_graph.get_outgoing_connections(module_id);
         for connection_id in connection_ids {
+            // side effect free modules are left out if none of their exports is used
+            if !state.module_graph.is_connection_active(connection_id) {
+                continue;
+            }
             let connection = state.module_graph.connection_by_id(connection_id);
             let resolved_module_id = connection.resolved_module_id;
+            let is_entry = state
+                .module_graph
+                .dependency_by_id(connection.dependency_id)
+                .as_module_dependency()
+                .is_some_and(|dependency| dependency.is_entry());
+            let chunk_id = if is_entry {
+                self.add_entry_point(state, resolved_module_id)
+            } else {
+                action.chunk_id
+            };
             state
                 .queue
                 .push_back(QueueAction::AddAndEnterModule(AddAndEnterModule {
                     module_id: resolved_module_id,
-                    chunk_id: action.chunk_id,
+                    chunk_id,
                 }));
         }
     }
+    /// the entry chunk of the chunk group started by the module, e.g. a worker,
+    /// the chunk is named after the path of the module
+    fn add_entry_point(&self, state: &mut LinkerState, module_id: ModuleId) -> ChunkId {
+        let identifier = state.module_graph.module_by_id(module_id).identifier();
+        let name = chunk_name_of_module(identifier, self.options.context.as_str());
+        if let Some(&chunk_group_id) = state.entry_points.get(&name) {
+            return state
+                .chunk_graph
+                .chunk_group_by_id(chunk_group_id)
+                .get_entry_point_chunk()
+                .expect("should get entry_chunk");
+        }
+        let chunk_id = state.chunk_graph.create_chunk(Some(name.clone()));
+        let chunk_group_id = state
+            .chunk_graph
+            .create_chunk_group(chunk_id, Some(name.clone()));
+        state
+            .chunk_graph
+            .chunk_group_by_id_mut(chunk_group_id)
+            .set_entry_point_chunk(chunk_id);
+        state
+            .chunk_graph
+            .connect_chunk_and_entry_module(chunk_id, module_id, chunk_group_id);
+        state.entry_points.insert(name, chunk_group_id);
+        chunk_id
+    }
     pub fn prepare_input_entrypoints_and_modules(
         &self,
         state: &mut LinkerState,
     ) -> IndexMap<ChunkGroupId, Vec<ModuleId>> {
         let mut entrypoint_module_map = IndexMap::default();
-        for (name, _entry_data) in &self.entries {
+        for (name, entry_data) in &self.entries {
             let chunk_id = state.chunk_graph.create_chunk(Some(name.clone()));
             let chunk_group_id = state
                 .chunk_graph
@@ -160,23 +206,41 @@ impl ChunkLinker {
             let chunk_group = state.chunk_graph.chunk_group_by_id_mut(chunk_group_id);
             chunk_group.set_entry_point_chunk(chunk_id);
             state.entry_points.insert(name.clone(), chunk_group_id);
-            // let module_ids = entry_data
-            //     .dependencies
-            //     .iter()
-            //     .map(|dep_id| state.module_graph.module_id_by_dependency_id(*dep_id))
-            //     .collect::<Vec<_>>();
-            let module_ids = vec![];
+            // entries failed to resolve are reported by the scanner already
+            let module_ids = entry_data
+                .dependencies
+                .iter()
+                .filter_map(|dep_id| state.module_graph.get_module_id_by_dependency_id(*dep_id))
+                .collect::<Vec<_>>();
             entrypoint_module_map.insert(chunk_group_id, module_ids);
         }
         entrypoint_module_map
     }
 }
 
+/// `src/worker.js` to `src_worker_js`
+fn chunk_name_of_module(identifier: &str, context: &str) -> String {
+    let path = identifier
+        .strip_prefix(context)
+        .and_then(|path| path.strip_prefix('/'))
+        .unwrap_or(identifier);
+    let mut name = String::with_capacity(path.len());
+    for c in path.chars() {
+        let c = if c.is_ascii_alphanumeric() { c } else { '_' };
+        if !(c == '_' && name.ends_with('_')) {
+            name.push(c);
+        }
+    }
+    name.trim_matches('_').to_string()
+}
+
 pub struct LinkerState {
     pub chunk_graph: ChunkGraph,
     pub module_graph: ModuleGraph,
     pub entry_points: IndexMap<String, ChunkGroupId>,
     pub diagnostics: Diagnostics,
+    /// esm modules merged into a single scope, see `optimization.concatenate_modules`
+    pub concatenated_modules: Vec<ConcatenatedModule>,
     queue: VecDeque<QueueAction>,
 }
 
@@ -187,7 +251,8 @@ impl LinkerState {
             entry_points: Default::default(),
             module_graph,
             queue: Default::default(),
-            diagnostics
+            diagnostics,
+            concatenated_modules: Default::default(),
         }
     }
 }
@@ -1,15 +1,17 @@
+use base64::Engine;
+use camino::Utf8Path;
 use indexmap::IndexSet;
 use miette::Result;
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
-use rspack_sources::{BoxSource, ConcatSource, SourceExt};
-use rustc_hash::FxHashMap;
+use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use rspack_sources::{BoxSource, ConcatSource, MapOptions, RawSource, SourceExt};
+use rustc_hash::{FxHashMap, FxHashSet};
 use tokio::sync::mpsc::unbounded_channel;
 
 use crate::{
-    chunk::{ChunkGraph, ChunkId, ChunkLinker, LinkerState}, compiler::CompilerOptions, errors::Diagnostics, module::{
-        CodeGenerationContext, CodeGenerationResult, ModuleGraph, ModuleId, ModuleScanner,
-        ScannerState,
-    }, plugin::PluginDriver, task::Task
+    chunk::{hash_chunk_files, ChunkFile, ChunkFileType, ChunkGraph, ChunkId, ChunkLinker, LinkerState}, compiler::{CompilerOptions, Devtool}, errors::{Diagnostics, TopLevelAwaitUnsupportedError}, ids::{assign_chunk_ids, assign_module_ids}, module::{
+        CodeGenerationContext, CodeGenerationResult, ExportsType, ModuleGraph, ModuleId,
+        ModuleScanner, ScannerState,
+    }, optimize::{concatenate_modules, minify, split_chunks}, plugin::PluginDriver, runtime::{render_runtime, RuntimeGlobals}, stats::Stats, task::Task, utils::identifier::quoted
 };
 use std::sync::Arc;
 #[derive(Debug, Default)]
@@ -18,6 +20,9 @@ struct CodeGenerationResults {
 }
 pub struct CodeGenerationState {
     chunk_graph: ChunkGraph,
+    module_graph: ModuleGraph,
+    /// modules concatenated into the root of their group to the root, they are not in any chunk
+    inner_modules: FxHashMap<ModuleId, ModuleId>,
     code_generation_results: CodeGenerationResults,
     pub diagnostics: Diagnostics
 }
@@ -31,7 +36,8 @@ pub struct Compilation {
     pub options: Arc<CompilerOptions>,
     module_graph: ModuleGraph,
     pub diagnostics: Diagnostics,
-    pub plugin_driver: Arc<PluginDriver>
+    pub plugin_driver: Arc<PluginDriver>,
+    pub stats: Stats,
 }
 
 impl Compilation {
@@ -41,6 +47,7 @@ impl Compilation {
             module_graph: Default::default(),
             diagnostics: Default::default(),
             plugin_driver,
+            stats: Default::default(),
         }
     }
     /// similar with webpack's make phase, which will make module graph
@@ -56,83 +63,503 @@ impl Compilation {
     /// similar with webpack's seal phase
     /// this will make chunk(consists of connected modules)
     pub fn link(&mut self, scanner_state: ScannerState) -> LinkerState {
-        let mut linker_state = LinkerState::new(scanner_state.module_graph, scanner_state.diagnostics);
+        let mut module_graph = scanner_state.module_graph;
+        let mut diagnostics = scanner_state.diagnostics;
+        module_graph.sort_outgoing_connections();
+        module_graph.set_async_modules();
+        module_graph.set_exports_info(&self.options.optimization);
+        if !self.options.output.environment.async_function {
+            for module in &module_graph.modules {
+                if module.build_meta().has_top_level_await {
+                    diagnostics.push(
+                        TopLevelAwaitUnsupportedError {
+                            module: module.identifier().to_string(),
+                        }
+                        .into(),
+                    );
+                }
+            }
+        }
+        let mut linker_state = LinkerState::new(module_graph, diagnostics);
         let linker = ChunkLinker::new(self.options.clone(), scanner_state.entries);
         linker.build_chunk_graph(&mut linker_state);
+        if let Some(split_chunks_options) = &self.options.optimization.split_chunks {
+            split_chunks(
+                split_chunks_options,
+                &linker_state.module_graph,
+                &mut linker_state.chunk_graph,
+                &mut linker_state.diagnostics,
+            );
+        }
+        if self.options.optimization.concatenate_modules {
+            linker_state.concatenated_modules = concatenate_modules(
+                &mut linker_state.module_graph,
+                &mut linker_state.chunk_graph,
+            );
+        }
+        assign_module_ids(
+            self.options.optimization.module_ids,
+            &self.options.context,
+            &mut linker_state.module_graph,
+            &mut linker_state.chunk_graph,
+        );
+        assign_chunk_ids(
+            self.options.optimization.chunk_ids,
+            &mut linker_state.chunk_graph,
+        );
+        self.stats = Stats::new(&linker_state.module_graph);
         linker_state
     }
     /// code generation
     pub fn code_generation(&self, linker_state: LinkerState) -> CodeGenerationState {
         let mut code_generation_results = CodeGenerationResults::default();
+        // concatenated modules are generated along with the root of their group
+        let concatenated_roots = linker_state
+            .concatenated_modules
+            .iter()
+            .map(|concatenated_module| (concatenated_module.root, concatenated_module))
+            .collect::<FxHashMap<_, _>>();
+        let inner_modules = linker_state
+            .concatenated_modules
+            .iter()
+            .flat_map(|concatenated_module| {
+                concatenated_module
+                    .inner_modules()
+                    .map(|module_id| (module_id, concatenated_module.root))
+            })
+            .collect::<FxHashMap<_, _>>();
         let results = linker_state
             .module_graph
             .modules
             .indices()
+            .filter(|module_id| !inner_modules.contains_key(module_id))
             .collect::<Vec<_>>()
             .into_par_iter()
-            .map(|module_id| {
-                let module = linker_state.module_graph.module_by_id(module_id);
-                let codegen_result = module.code_generation(CodeGenerationContext {
-                    module_graph: &linker_state.module_graph,
-                });
-                (module_id, codegen_result)
+            .map(|module_id| match concatenated_roots.get(&module_id) {
+                Some(concatenated_module) => concatenated_module.code_generation(
+                    &self.options,
+                    &linker_state.module_graph,
+                    &linker_state.chunk_graph,
+                ),
+                None => {
+                    let module = linker_state.module_graph.module_by_id(module_id);
+                    module
+                        .code_generation(CodeGenerationContext {
+                            options: &self.options,
+                            module_graph: &linker_state.module_graph,
+                            chunk_graph: &linker_state.chunk_graph,
+                            module_id,
+                            concatenated_module: None,
+                        })
+                        .map(|result| vec![(module_id, result)])
+                }
             })
             .collect::<Vec<_>>();
-        for (id, result) in results {
+        for result in results {
             // FIXME: fixed codegeneration diagnostics later
             code_generation_results
                 .module_id_to_generation_result
-                .insert(id, result.expect("codegeneration failed"));
+                .extend(result.expect("codegeneration failed"));
         }
         CodeGenerationState {
             chunk_graph: linker_state.chunk_graph,
+            module_graph: linker_state.module_graph,
+            inner_modules,
             code_generation_results,
             diagnostics: linker_state.diagnostics
         }
     }
     // chunk asset
     pub fn create_chunk_asset(
-        &self,
+        &mut self,
         code_generation_state: &mut CodeGenerationState,
     ) -> ChunkAssetState {
-        // let manifest = vec![];
         let mut assets = FxHashMap::default();
+        let mut chunk_files = vec![];
         for chunk_id in code_generation_state.chunk_graph.chunks.indices() {
-            let chunk_name = code_generation_state
-                .chunk_graph
-                .chunk_by_id(chunk_id)
-                .name
-                .to_owned()
-                .expect("should have name");
-            let chunk_modules = code_generation_state
-                .chunk_graph
-                .get_chunk_modules(chunk_id);
-            let chunk_source =
-                self.render_chunk_modules(code_generation_state, chunk_id, chunk_modules);
-            assets.insert(chunk_name, chunk_source);
+            let chunk_source = self.render_chunk(code_generation_state, chunk_id);
+            chunk_files.push(ChunkFile {
+                chunk_id,
+                file_type: ChunkFileType::Js,
+                filename: code_generation_state.chunk_graph.get_chunk_filename(
+                    &self.options.output,
+                    chunk_id,
+                    ChunkFileType::Js,
+                ),
+                source: chunk_source,
+            });
+            if let Some(css_source) = self.render_chunk_css(code_generation_state, chunk_id) {
+                chunk_files.push(ChunkFile {
+                    chunk_id,
+                    file_type: ChunkFileType::Css,
+                    filename: code_generation_state.chunk_graph.get_chunk_filename(
+                        &self.options.output,
+                        chunk_id,
+                        ChunkFileType::Css,
+                    ),
+                    source: css_source,
+                });
+            }
+            // files emitted by modules, e.g. `asset/resource`
+            for module_id in code_generation_state.chunk_graph.get_chunk_modules(chunk_id) {
+                let result =
+                    &code_generation_state.code_generation_results.module_id_to_generation_result
+                        [&module_id];
+                for asset in &result.assets {
+                    assets.insert(asset.filename.clone(), asset.source.clone());
+                }
+            }
         }
+        // chunks are minified in parallel, a chunk which fails to minify is emitted as is
+        let (chunk_files, diagnostics): (Vec<_>, Vec<_>) = chunk_files
+            .into_par_iter()
+            .map(|file| {
+                if !self.options.optimization.minimize || file.file_type != ChunkFileType::Js {
+                    return (file, None);
+                }
+                match minify(
+                    file.source.clone(),
+                    &file.filename,
+                    &self.options.optimization.minimizer,
+                    self.options.devtool != Devtool::None,
+                ) {
+                    Ok(source) => (ChunkFile { source, ..file }, None),
+                    Err(err) => {
+                        let diagnostic =
+                            err.wrap_err(format!("failed to minify {}", file.filename));
+                        (file, Some(diagnostic))
+                    }
+                }
+            })
+            .unzip();
+        code_generation_state
+            .diagnostics
+            .extend(diagnostics.into_iter().flatten());
+        // hashes are computed from the minified code, source maps need the final filenames
+        let (chunk_files, hashes) =
+            hash_chunk_files(chunk_files, self.options.output.hash_function);
+        let chunk_assets = chunk_files
+            .par_iter()
+            .map(|file| match file.file_type {
+                ChunkFileType::Js => self.render_source_map(&file.filename, file.source.clone()),
+                ChunkFileType::Css => (file.source.clone(), None),
+            })
+            .collect::<Vec<_>>();
+        for (file, (source, source_map)) in chunk_files.iter().zip(chunk_assets) {
+            if let Some((map_filename, map_source)) = source_map {
+                assets.insert(map_filename, map_source);
+            }
+            assets.insert(file.filename.clone(), source);
+        }
+        if let Some(manifest) = &self.options.output.manifest {
+            let manifest_source =
+                Self::render_manifest(&code_generation_state.chunk_graph, &chunk_files);
+            assets.insert(manifest.clone(), manifest_source);
+        }
+        self.stats
+            .set_chunks(&code_generation_state.chunk_graph, &chunk_files, &hashes);
+        self.stats.set_assets(assets.keys());
         ChunkAssetState { assets }
     }
+    /// json object mapping `[name].js` and `[name].css` of chunks to the emitted files, chunks
+    /// without name are named after their ids
+    fn render_manifest(chunk_graph: &ChunkGraph, chunk_files: &[ChunkFile]) -> BoxSource {
+        let entries = chunk_files
+            .iter()
+            .map(|file| {
+                let id = chunk_graph.get_chunk_id(file.chunk_id).to_string();
+                let name = chunk_graph
+                    .chunk_by_id(file.chunk_id)
+                    .name
+                    .as_deref()
+                    .unwrap_or(&id);
+                format!(
+                    "  {}: {}",
+                    quoted(&format!("{name}.{}", file.file_type.extension())),
+                    quoted(&file.filename)
+                )
+            })
+            .collect::<Vec<_>>();
+        RawSource::from(format!("{{\n{}\n}}\n", entries.join(",\n"))).boxed()
+    }
+    /// add the source map of the chunk according to `devtool`, returns the chunk with the
+    /// `sourceMappingURL` comment and the `.map` asset if it's emitted as a file
+    fn render_source_map(
+        &self,
+        filename: &str,
+        source: BoxSource,
+    ) -> (BoxSource, Option<(String, BoxSource)>) {
+        if self.options.devtool == Devtool::None {
+            return (source, None);
+        }
+        let Some(mut map) = source.map(&MapOptions::new(true)) else {
+            return (source, None);
+        };
+        let basename = Utf8Path::new(filename).file_name().unwrap_or(filename);
+        map.set_file(Some(basename));
+        let sources = map
+            .sources()
+            .iter()
+            .map(|source| self.source_map_source_name(source))
+            .collect::<Vec<_>>();
+        map.set_sources(sources);
+        let json = map.to_json().expect("source map should be serializable");
+        let (comment, asset) = match self.options.devtool {
+            Devtool::SourceMap => (
+                Some(format!("//# sourceMappingURL={basename}.map\n")),
+                Some((format!("{filename}.map"), RawSource::from(json).boxed())),
+            ),
+            Devtool::InlineSourceMap => {
+                let data = base64::engine::general_purpose::STANDARD.encode(json);
+                (
+                    Some(format!(
+                        "//# sourceMappingURL=data:application/json;charset=utf-8;base64,{data}\n"
+                    )),
+                    None,
+                )
+            }
+            Devtool::HiddenSourceMap => (
+                None,
+                Some((format!("{filename}.map"), RawSource::from(json).boxed())),
+            ),
+            Devtool::None => unreachable!(),
+        };
+        let source = match comment {
+            Some(comment) => ConcatSource::new([source, RawSource::from(comment).boxed()]).boxed(),
+            None => source,
+        };
+        (source, asset)
+    }
+    /// name of a module in source maps, relative to the context like webpack's `webpack:///./src/a.js`
+    fn source_map_source_name(&self, resource_path: &str) -> String {
+        match Utf8Path::new(resource_path).strip_prefix(&self.options.context) {
+            Ok(relative) => format!("unpack:///./{relative}"),
+            Err(_) => format!("unpack:///{resource_path}"),
+        }
+    }
+    /// render the chunk as a script which bootstraps its entry modules, chunks split from
+    /// entry chunks register their modules to the runtime of the entry chunks instead
+    pub fn render_chunk(&self, state: &mut CodeGenerationState, chunk_id: ChunkId) -> BoxSource {
+        let chunk_modules = state.chunk_graph.get_chunk_modules(chunk_id);
+        let entry_modules = state.chunk_graph.get_chunk_entry_modules(chunk_id);
+        if entry_modules.is_empty() {
+            return ConcatSource::new([
+                RawSource::from(format!(
+                    "(globalThis.__unpack_chunks__ = globalThis.__unpack_chunks__ || []).push([[{}], ",
+                    state.chunk_graph.get_chunk_id(chunk_id).to_js()
+                ))
+                .boxed(),
+                self.render_chunk_modules(state, chunk_id, chunk_modules),
+                RawSource::from("]);\n").boxed(),
+            ])
+            .boxed();
+        }
+        // chunks split from this chunk, which are loaded before evaluating the entry modules
+        let dependent_chunks = state
+            .chunk_graph
+            .get_chunk_groups(chunk_id)
+            .into_iter()
+            .map(|chunk_group_id| state.chunk_graph.chunk_group_by_id(chunk_group_id))
+            .filter(|chunk_group| chunk_group.get_entry_point_chunk() == Some(chunk_id))
+            .flat_map(|chunk_group| chunk_group.chunks().to_vec())
+            .filter(|&id| id != chunk_id)
+            .collect::<IndexSet<_>>();
+        // the runtime evaluates the modules of dependent chunks as well
+        let mut runtime_requirements = RuntimeGlobals::REQUIRE;
+        for &id in dependent_chunks.iter().chain([&chunk_id]) {
+            for module_id in state.chunk_graph.get_chunk_modules(id) {
+                if state.module_graph.is_only_referenced_by_css(module_id) {
+                    continue;
+                }
+                runtime_requirements |= state.code_generation_results.module_id_to_generation_result
+                    [&module_id]
+                    .runtime_requirements;
+            }
+        }
+        if !dependent_chunks.is_empty() {
+            runtime_requirements |= RuntimeGlobals::CHUNK_LOADING;
+        }
+        let mut source = ConcatSource::default();
+        source.add(RawSource::from("(() => {\nvar __unpack_modules__ = ("));
+        source.add(self.render_chunk_modules(state, chunk_id, chunk_modules));
+        source.add(RawSource::from(");\n"));
+        source.add(render_runtime(runtime_requirements, &self.options));
+        let startup = entry_modules
+            .iter()
+            .map(|module_id| {
+                format!(
+                    "var __unpack_exports__ = __unpack_require__({});\n",
+                    state.chunk_graph.get_module_id(*module_id).to_js()
+                )
+            })
+            .collect::<String>();
+        if dependent_chunks.is_empty() {
+            source.add(RawSource::from(startup));
+        } else {
+            let (chunk_ids, filenames): (Vec<_>, Vec<_>) = dependent_chunks
+                .iter()
+                .map(|&id| {
+                    let filename = state.chunk_graph.get_chunk_filename(
+                        &self.options.output,
+                        id,
+                        ChunkFileType::Js,
+                    );
+                    (
+                        state.chunk_graph.get_chunk_id(id).to_js(),
+                        quoted(&filename),
+                    )
+                })
+                .unzip();
+            source.add(RawSource::from(format!(
+                "__unpack_require__.O([{}], [{}], function () {{\n{startup}}});\n",
+                chunk_ids.join(", "),
+                filenames.join(", ")
+            )));
+        }
+        source.add(RawSource::from("})();\n"));
+        source.boxed()
+    }
+    /// concatenate the css of modules in the chunk, `None` if the chunk has no css
+    pub fn render_chunk_css(
+        &self,
+        state: &mut CodeGenerationState,
+        chunk_id: ChunkId,
+    ) -> Option<BoxSource> {
+        let css_order = Self::css_order(state, chunk_id);
+        let results = &state.code_generation_results.module_id_to_generation_result;
+        let mut source = ConcatSource::default();
+        let mut has_css = false;
+        for (module_id, conditions) in css_order {
+            if let Some(css) = &results[&module_id].css {
+                for condition in &conditions {
+                    source.add(RawSource::from(format!("{condition} {{\n")));
+                }
+                source.add(css.clone());
+                source.add(RawSource::from("\n"));
+                for _ in &conditions {
+                    source.add(RawSource::from("}\n"));
+                }
+                has_css = true;
+            }
+        }
+        has_css.then(|| source.boxed())
+    }
+    /// modules of the chunk in post order of the module graph, so that the css of a module
+    /// comes after the css it imports, the same order as evaluating javascript. the traversal
+    /// starts from the entry modules of the chunk groups, as split chunks have no entry modules.
+    /// each module comes with the conditions of the `@import` chain reaching it, a module
+    /// imported with different conditions is emitted once for each of them
+    fn css_order(
+        state: &mut CodeGenerationState,
+        chunk_id: ChunkId,
+    ) -> Vec<(ModuleId, Vec<String>)> {
+        let chunk_modules = state.chunk_graph.get_chunk_modules(chunk_id);
+        let chunk_groups = state.chunk_graph.get_chunk_groups(chunk_id);
+        let mut group_modules = FxHashSet::default();
+        let mut entry_modules = vec![];
+        for chunk_group_id in chunk_groups {
+            let chunk_group = state.chunk_graph.chunk_group_by_id(chunk_group_id);
+            let entry_point_chunk_id = chunk_group.get_entry_point_chunk();
+            for id in chunk_group.chunks().to_vec() {
+                group_modules.extend(state.chunk_graph.get_chunk_modules(id));
+            }
+            if let Some(entry_point_chunk_id) = entry_point_chunk_id {
+                entry_modules.extend(
+                    state
+                        .chunk_graph
+                        .get_chunk_entry_modules(entry_point_chunk_id),
+                );
+            }
+        }
+        let mut visited = FxHashSet::default();
+        let mut order = vec![];
+        // (module, conditions, whether its dependencies are visited)
+        let mut stack = entry_modules
+            .into_iter()
+            .rev()
+            .map(|module_id| (module_id, vec![], false))
+            .collect::<Vec<_>>();
+        while let Some((module_id, conditions, leaving)) = stack.pop() {
+            // the css imported by concatenated modules is in the chunk of their root
+            let root_module_id = state
+                .inner_modules
+                .get(&module_id)
+                .copied()
+                .unwrap_or(module_id);
+            if leaving {
+                if chunk_modules.contains(&root_module_id) {
+                    order.push((module_id, conditions));
+                }
+                continue;
+            }
+            if !group_modules.contains(&root_module_id)
+                || !visited.insert((module_id, conditions.clone()))
+            {
+                continue;
+            }
+            stack.push((module_id, conditions.clone(), true));
+            let connection_ids = state.module_graph.get_outgoing_connections(module_id);
+            for connection_id in connection_ids.into_iter().rev() {
+                let connection = state.module_graph.connection_by_id(connection_id);
+                let dependency = state
+                    .module_graph
+                    .dependency_by_id(connection.dependency_id);
+                let mut conditions = conditions.clone();
+                if let Some(dependency) = dependency.as_module_dependency() {
+                    conditions.extend(dependency.css_conditions().iter().cloned());
+                }
+                stack.push((connection.resolved_module_id, conditions, false));
+            }
+        }
+        order
+    }
+    /// the object of module factories keyed by module id, sorted by module id
     pub fn render_chunk_modules(
         &self,
         state: &mut CodeGenerationState,
         chunk_id: ChunkId,
-        chunk_modules: IndexSet<ModuleId>,
+        mut chunk_modules: IndexSet<ModuleId>,
     ) -> BoxSource {
-        let module_sources = chunk_modules
-            .iter()
-            .map(|module_id| self.render_module(state, chunk_id, *module_id));
-        let concat_source = ConcatSource::new(module_sources);
+        chunk_modules
+            .sort_by_cached_key(|&module_id| state.chunk_graph.get_module_id(module_id).clone());
+        let mut concat_source = ConcatSource::default();
+        concat_source.add(RawSource::from("{\n"));
+        for module_id in chunk_modules {
+            if state.module_graph.is_only_referenced_by_css(module_id) {
+                continue;
+            }
+            concat_source.add(self.render_module(state, chunk_id, module_id));
+        }
+        concat_source.add(RawSource::from("}"));
         concat_source.boxed()
     }
+    /// wrap the generated code of module into a function which is called by `__unpack_require__`
     pub fn render_module(
         &self,
         state: &mut CodeGenerationState,
         _chunk_id: ChunkId,
         module_id: ModuleId,
     ) -> BoxSource {
-        state.code_generation_results.module_id_to_generation_result[&module_id]
+        let module = state.module_graph.module_by_id(module_id);
+        let (arguments, prologue) = match module.build_meta().exports_type {
+            ExportsType::Namespace => (
+                "__unpack_module__, __unpack_exports__, __unpack_require__",
+                "\"use strict\";\n",
+            ),
+            _ => ("module, exports, __unpack_require__", ""),
+        };
+        let source = state.code_generation_results.module_id_to_generation_result[&module_id]
             .source
-            .clone()
+            .clone();
+        ConcatSource::new([
+            RawSource::from(format!(
+                "{}: (function ({arguments}) {{\n{prologue}",
+                state.chunk_graph.get_module_id(module_id).to_js()
+            ))
+            .boxed(),
+            source,
+            RawSource::from("\n}),\n").boxed(),
+        ])
+        .boxed()
     }
 }
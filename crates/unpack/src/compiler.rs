@@ -4,14 +4,20 @@ use std::sync::Arc;
 
 pub use options::CompilerOptions;
 pub use options::EntryItem;
-use swc_core::common::plugin;
-use std::cell::UnsafeCell;
+pub use options::{
+    AssetParserOptions, CacheGroup, ChunkIds, Devtool, EnvironmentOptions, HashFunction, JsxOptions, JsxRuntime, MinimizerOptions, ModuleIds, ModuleOptions, ModuleRule, OptimizationOptions, OutputOptions,
+    ParserBackend, ParserOptions, RuleCondition, SplitChunksOptions, Target,
+};
+use miette::{IntoDiagnostic, Result};
+
 use crate::compilation::ChunkAssetState;
 use crate::compilation::Compilation;
+use crate::errors::Diagnostics;
 use crate::plugin::BoxPlugin;
 use crate::plugin::CompilationCell;
 use crate::plugin::PluginContext;
 use crate::plugin::PluginDriver;
+use crate::stats::Stats;
 
 pub struct Compiler {
     #[allow(dead_code)]
@@ -31,7 +37,7 @@ impl Compiler {
         });
         
         let compilation = Arc::new(CompilationCell::new(Compilation::new(options.clone(), plugin_driver.clone())));
-        Self { options, plugins , compilation: compilation,plugin_driver: plugin_driver.clone()}
+        Self { options, plugins , compilation,plugin_driver: plugin_driver.clone()}
     }
     pub async fn build(&mut self) {
         self.plugin_driver.run_compilation_hook(self.compilation.clone()).await;
@@ -39,10 +45,12 @@ impl Compiler {
         let scanner_state = compilation.scan().await;
         let linker_state = compilation.link(scanner_state);
         let mut code_generation_state = compilation.code_generation(linker_state);
-        compilation.diagnostics.extend(mem::take(&mut code_generation_state.diagnostics));
         let asset_state = compilation.create_chunk_asset(&mut code_generation_state);
+        compilation.diagnostics.extend(mem::take(&mut code_generation_state.diagnostics));
         
-        self.emit_assets(asset_state);
+        if let Err(err) = self.emit_assets(asset_state) {
+            compilation.diagnostics.push(err);
+        }
         let compilation: &Compilation = unsafe{&*self.compilation.get()};
         if !compilation.diagnostics.is_empty() {
             for diag in &compilation.diagnostics {
@@ -51,9 +59,26 @@ impl Compiler {
         }
         println!("Compilation finished");
     }
-    pub fn emit_assets(&self, asset_state: ChunkAssetState) {
-        for (_name, _source) in asset_state.assets {
-            // std::fs::write(name, source.buffer().as_ref());
+    /// stats of the last build
+    pub fn stats(&self) -> &Stats {
+        let compilation: &Compilation = unsafe { &*self.compilation.get() };
+        &compilation.stats
+    }
+    /// errors and warnings of the last build
+    pub fn diagnostics(&self) -> &Diagnostics {
+        let compilation: &Compilation = unsafe { &*self.compilation.get() };
+        &compilation.diagnostics
+    }
+    /// write assets into the output path
+    pub fn emit_assets(&self, asset_state: ChunkAssetState) -> Result<()> {
+        let output_path = self.options.context.join(&self.options.output.path);
+        for (name, source) in asset_state.assets {
+            let path = output_path.join(name);
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).into_diagnostic()?;
+            }
+            std::fs::write(&path, source.buffer()).into_diagnostic()?;
         }
+        Ok(())
     }
 }
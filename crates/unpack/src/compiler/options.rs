@@ -1,14 +1,373 @@
 use camino::Utf8PathBuf;
+use indexmap::IndexMap;
+use regex::Regex;
 use rspack_resolver::ResolveOptions;
+
+use crate::loader::BoxLoader;
+use crate::module::ModuleType;
 #[derive(Clone, Debug)]
 pub struct EntryItem {
     pub name: String,
     pub import: String,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct CompilerOptions {
     pub context: Utf8PathBuf,
     pub entry: Vec<EntryItem>,
     pub resolve: ResolveOptions,
+    pub jsx: JsxOptions,
+    pub parser_backend: ParserBackend,
+    pub output: OutputOptions,
+    pub module: ModuleOptions,
+    /// compile time constants, e.g. `process.env.NODE_ENV` to `"production"`,
+    /// values are code instead of strings. branches made dead by them are removed with the
+    /// `new URL()` and workers in them, `require()` is not bundled and only removed as code
+    pub define: IndexMap<String, String>,
+    pub target: Target,
+    pub devtool: Devtool,
+    pub optimization: OptimizationOptions,
+}
+
+#[derive(Clone, Debug)]
+pub struct OptimizationOptions {
+    /// leave out the exports which are not imported by any module, so that minifiers can
+    /// remove their code
+    pub used_exports: bool,
+    /// leave out the modules flagged as side effect free if none of their exports is used,
+    /// and import re-exported bindings from the modules which define them
+    pub side_effects: bool,
+    /// merge esm modules of a chunk into a single scope instead of wrapping each of them in
+    /// a function, known as scope hoisting
+    pub concatenate_modules: bool,
+    /// minify emitted chunks with `minimizer`
+    pub minimize: bool,
+    pub minimizer: MinimizerOptions,
+    /// move modules shared by chunks into new chunks, `None` disables it
+    pub split_chunks: Option<SplitChunksOptions>,
+    /// how module ids in the output are assigned
+    pub module_ids: ModuleIds,
+    /// how chunk ids in the output are assigned
+    pub chunk_ids: ChunkIds,
+}
+
+impl Default for OptimizationOptions {
+    fn default() -> Self {
+        Self {
+            used_exports: true,
+            side_effects: true,
+            concatenate_modules: true,
+            minimize: false,
+            minimizer: Default::default(),
+            split_chunks: None,
+            module_ids: Default::default(),
+            chunk_ids: Default::default(),
+        }
+    }
+}
+
+/// options of swc's minifier, license comments are always preserved
+#[derive(Clone, Debug)]
+pub struct MinimizerOptions {
+    /// drop dead code and simplify expressions
+    pub compress: bool,
+    /// shorten the names of local variables
+    pub mangle: bool,
+}
+
+impl Default for MinimizerOptions {
+    fn default() -> Self {
+        Self {
+            compress: true,
+            mangle: true,
+        }
+    }
+}
+
+/// same as webpack's `optimization.splitChunks` with `chunks: "all"`
+#[derive(Clone, Debug)]
+pub struct SplitChunksOptions {
+    /// chunks smaller than this in bytes are not created
+    pub min_size: usize,
+    /// modules in fewer chunks than this are not split
+    pub min_chunks: usize,
+    /// modules matching multiple groups go to the one with the highest priority
+    pub cache_groups: IndexMap<String, CacheGroup>,
+}
+
+impl Default for SplitChunksOptions {
+    fn default() -> Self {
+        Self {
+            min_size: 20000,
+            min_chunks: 1,
+            cache_groups: IndexMap::from_iter([
+                (
+                    "default".to_string(),
+                    CacheGroup {
+                        min_chunks: Some(2),
+                        priority: -20,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "vendors".to_string(),
+                    CacheGroup {
+                        test: Some(RuleCondition::Regex(
+                            Regex::new(r"[\\/]node_modules[\\/]")
+                                .expect("should be valid regex"),
+                        )),
+                        priority: -10,
+                        ..Default::default()
+                    },
+                ),
+            ]),
+        }
+    }
+}
+
+/// same as webpack's `optimization.moduleIds`, ids don't depend on the order modules are built
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ModuleIds {
+    /// numbers in the order modules are imported from the entries
+    #[default]
+    Natural,
+    /// paths relative to context, e.g. `./src/a.js`
+    Named,
+    /// short numeric hashes of the paths, which are kept when other modules change
+    Deterministic,
+}
+
+/// same as webpack's `optimization.chunkIds`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChunkIds {
+    /// numbers in the order chunks are created
+    #[default]
+    Natural,
+    /// names of chunks
+    Named,
+    /// short numeric hashes of the names, which are kept when other chunks change
+    Deterministic,
+}
+
+/// rule to select modules into new chunks
+#[derive(Clone, Debug, Default)]
+pub struct CacheGroup {
+    /// matched against the resource path, all modules are selected if not set
+    pub test: Option<RuleCondition>,
+    /// overrides `min_size` of split chunks
+    pub min_size: Option<usize>,
+    /// overrides `min_chunks` of split chunks
+    pub min_chunks: Option<usize>,
+    pub priority: i32,
+    /// name of the chunk which all selected modules go to, otherwise modules are grouped by
+    /// the chunks they are in and the chunks are named after the group and those chunks
+    pub name: Option<String>,
+}
+
+/// how source maps of chunks are emitted, same as webpack's `devtool`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Devtool {
+    #[default]
+    None,
+    /// `[file].map` next to the chunk, referenced by a `sourceMappingURL` comment
+    SourceMap,
+    /// a base64 data url in the `sourceMappingURL` comment
+    InlineSourceMap,
+    /// `[file].map` next to the chunk without the comment, e.g. for error reporting services
+    HiddenSourceMap,
+}
+
+/// environment the output runs in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Target {
+    #[default]
+    Web,
+    Node,
+}
+
+#[derive(Clone, Debug)]
+pub struct OutputOptions {
+    /// directory of emitted files, relative to context
+    pub path: Utf8PathBuf,
+    /// prefix of the urls of emitted files at runtime, `"auto"` infers it from the url of current script
+    pub public_path: String,
+    /// filename of chunks, `[name]` is replaced with chunk name and `[id]` with chunk id,
+    /// `[contenthash]`, `[chunkhash]` and `[fullhash]` with the hashes of the file, the chunk
+    /// and the compilation, hashes can be shortened like `[contenthash:8]`
+    pub filename: String,
+    /// filename of the css files of chunks, supports the placeholders of `filename`
+    pub css_filename: String,
+    /// filename of `asset/resource` modules, supports `[name]`, `[ext]` and `[hash]` or
+    /// `[contenthash]`
+    pub asset_module_filename: String,
+    pub hash_function: HashFunction,
+    /// length of the hashes in filenames unless specified by the placeholder
+    pub hash_digest_length: usize,
+    /// filename of a json file mapping `[name].js` and `[name].css` of chunks to the emitted
+    /// files, not emitted if `None`
+    pub manifest: Option<String>,
+    pub environment: EnvironmentOptions,
+}
+
+/// algorithm of the hashes in filenames, same as webpack's `output.hashFunction`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HashFunction {
+    #[default]
+    Xxhash64,
+    Md5,
+    Sha256,
+}
+
+/// syntax supported by the environment the output runs in, same as webpack's `output.environment`
+#[derive(Clone, Debug)]
+pub struct EnvironmentOptions {
+    /// `async () => {}`, modules with top level await are wrapped in it
+    pub async_function: bool,
+}
+
+impl Default for EnvironmentOptions {
+    fn default() -> Self {
+        Self {
+            async_function: true,
+        }
+    }
+}
+
+impl Default for OutputOptions {
+    fn default() -> Self {
+        Self {
+            path: "dist".into(),
+            public_path: "auto".to_string(),
+            filename: "[name].js".to_string(),
+            css_filename: "[name].css".to_string(),
+            asset_module_filename: "[hash][ext]".to_string(),
+            hash_function: Default::default(),
+            hash_digest_length: 20,
+            manifest: None,
+            environment: Default::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ModuleOptions {
+    pub parser: ParserOptions,
+    pub rules: Vec<ModuleRule>,
+}
+
+/// how the modules matching the conditions are processed, all the matching rules are applied
+#[derive(Clone, Debug, Default)]
+pub struct ModuleRule {
+    /// matched against the resource path
+    pub test: Option<RuleCondition>,
+    /// matched against the resource path
+    pub include: Option<RuleCondition>,
+    /// matched against the resource path
+    pub exclude: Option<RuleCondition>,
+    /// matched against the query of the resource, e.g. `?inline`
+    pub resource_query: Option<RuleCondition>,
+    /// overrides the module type inferred from the extension
+    pub module_type: Option<ModuleType>,
+    /// overrides the `sideEffects` field of package.json for the matching modules
+    pub side_effects: Option<bool>,
+    /// loaders run from the last to the first, same as webpack's `use`
+    pub loaders: Vec<BoxLoader>,
+}
+
+impl ModuleRule {
+    pub fn matches(&self, resource_path: &str, resource_query: &str) -> bool {
+        self.test
+            .as_ref()
+            .is_none_or(|condition| condition.matches(resource_path))
+            && self
+                .include
+                .as_ref()
+                .is_none_or(|condition| condition.matches(resource_path))
+            && !self
+                .exclude
+                .as_ref()
+                .is_some_and(|condition| condition.matches(resource_path))
+            && self
+                .resource_query
+                .as_ref()
+                .is_none_or(|condition| condition.matches(resource_query))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum RuleCondition {
+    /// matches values starting with the string, e.g. an absolute directory
+    String(String),
+    Regex(Regex),
+    /// matches if any of the conditions matches
+    Any(Vec<RuleCondition>),
+}
+
+impl RuleCondition {
+    pub fn matches(&self, value: &str) -> bool {
+        match self {
+            RuleCondition::String(prefix) => value.starts_with(prefix.as_str()),
+            RuleCondition::Regex(regex) => regex.is_match(value),
+            RuleCondition::Any(conditions) => {
+                conditions.iter().any(|condition| condition.matches(value))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ParserOptions {
+    pub asset: AssetParserOptions,
+}
+
+#[derive(Clone, Debug)]
+pub struct AssetParserOptions {
+    /// `asset` modules not larger than this are inlined as data url, otherwise emitted as files
+    pub data_url_max_size: usize,
+}
+
+impl Default for AssetParserOptions {
+    fn default() -> Self {
+        Self {
+            data_url_max_size: 8096,
+        }
+    }
+}
+
+/// parser used to scan dependencies of javascript modules
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParserBackend {
+    #[default]
+    Swc,
+    Oxc,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JsxRuntime {
+    /// `React.createElement(...)` with a custom pragma
+    #[default]
+    Classic,
+    /// `jsx(...)` imported from `${import_source}/jsx-runtime`
+    Automatic,
+}
+
+#[derive(Clone, Debug)]
+pub struct JsxOptions {
+    pub runtime: JsxRuntime,
+    pub pragma: String,
+    pub pragma_frag: String,
+    pub import_source: String,
+    pub development: bool,
+}
+
+impl Default for JsxOptions {
+    fn default() -> Self {
+        Self {
+            runtime: JsxRuntime::Classic,
+            pragma: "React.createElement".to_string(),
+            pragma_frag: "React.Fragment".to_string(),
+            import_source: "react".to_string(),
+            development: false,
+        }
+    }
 }
@@ -1,23 +1,41 @@
 mod const_dependency;
+mod css_import_dependency;
+mod css_url_dependency;
 mod dependency_block;
 mod dependency_id;
 mod dependency_template;
 mod entry_dependency;
+mod harmony_compatibility_dependency;
+mod harmony_export_expression_dependency;
+mod harmony_export_imported_specifier_dependency;
+mod harmony_export_specifier_dependency;
 mod harmony_import_side_effect_dependency;
+mod harmony_import_specifier_dependency;
 mod module_dependency;
 mod swc_span_ext;
+mod url_dependency;
+mod worker_dependency;
 use std::fmt::Debug;
 
 use camino::Utf8Path;
 pub use const_dependency::*;
+pub use css_import_dependency::*;
+pub use css_url_dependency::*;
 pub use dependency_block::*;
 pub use dependency_id::*;
 pub use dependency_template::*;
 use dyn_clone::{clone_trait_object, DynClone};
 pub use entry_dependency::*;
+pub use harmony_compatibility_dependency::*;
+pub use harmony_export_expression_dependency::*;
+pub use harmony_export_imported_specifier_dependency::*;
+pub use harmony_export_specifier_dependency::*;
 pub use harmony_import_side_effect_dependency::*;
+pub use harmony_import_specifier_dependency::*;
 pub use module_dependency::*;
 pub use swc_span_ext::*;
+pub use url_dependency::*;
+pub use worker_dependency::*;
 pub trait Dependency:
     AsModuleDependency + AsDependencyTemplate + Debug + DynClone + Send + Sync
 {
@@ -1,4 +1,4 @@
-use super::DependencyTemplate;
+use super::{DependencyTemplate, TemplateContext};
 
 #[derive(Debug, Clone)]
 pub struct ConstDependency {
@@ -20,7 +20,7 @@ impl DependencyTemplate for ConstDependency {
     fn apply(
         &self,
         source: &mut rspack_sources::ReplaceSource<rspack_sources::BoxSource>,
-        _code_generation_context: &crate::module::CodeGenerationContext,
+        _template_context: &mut TemplateContext,
     ) {
         source.replace(self.start, self.end, self.content.as_ref(), None);
     }
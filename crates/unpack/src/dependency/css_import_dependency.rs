@@ -0,0 +1,34 @@
+use swc_core::atoms::Atom;
+
+use super::{Dependency, DependencyTemplate, ModuleDependency, TemplateContext};
+
+/// `@import "./a.css"` in css, the rule is removed because the imported css
+/// is emitted before the importer in the css file of chunk
+#[derive(Debug, Clone)]
+pub struct CssImportDependency {
+    pub request: Atom,
+    /// preludes of the at-rules wrapping the imported css from the outermost, e.g.
+    /// `@layer base` and `@media print` of `@import "./a.css" layer(base) print`
+    pub conditions: Vec<String>,
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Dependency for CssImportDependency {}
+impl ModuleDependency for CssImportDependency {
+    fn request(&self) -> &str {
+        &self.request
+    }
+    fn css_conditions(&self) -> &[String] {
+        &self.conditions
+    }
+}
+impl DependencyTemplate for CssImportDependency {
+    fn apply(
+        &self,
+        source: &mut rspack_sources::ReplaceSource<rspack_sources::BoxSource>,
+        _template_context: &mut TemplateContext,
+    ) {
+        source.replace(self.start, self.end, "", None);
+    }
+}
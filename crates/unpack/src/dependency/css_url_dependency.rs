@@ -0,0 +1,64 @@
+use swc_core::atoms::Atom;
+
+use super::{Dependency, DependencyTemplate, ModuleDependency, TemplateContext};
+use crate::module::{AssetUrl, ModuleType};
+use crate::utils::identifier::quoted;
+
+/// `url("./a.png")` in css, replaced with the url of the referenced asset module. the asset is
+/// the same module as `new URL("./a.png", import.meta.url)` of javascript
+#[derive(Debug, Clone)]
+pub struct CssUrlDependency {
+    pub request: Atom,
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Dependency for CssUrlDependency {}
+impl ModuleDependency for CssUrlDependency {
+    fn request(&self) -> &str {
+        &self.request
+    }
+    fn module_type(&self) -> Option<ModuleType> {
+        Some(ModuleType::AssetResource)
+    }
+}
+impl DependencyTemplate for CssUrlDependency {
+    fn apply(
+        &self,
+        source: &mut rspack_sources::ReplaceSource<rspack_sources::BoxSource>,
+        template_context: &mut TemplateContext,
+    ) {
+        let code_generation_context = template_context.code_generation_context;
+        let module_graph = code_generation_context.module_graph;
+        let Some(asset_url) = template_context
+            .dependency_id
+            .and_then(|dependency_id| module_graph.get_module_id_by_dependency_id(dependency_id))
+            .and_then(|module_id| {
+                module_graph
+                    .module_by_id(module_id)
+                    .build_meta()
+                    .asset_url
+                    .as_ref()
+            })
+        else {
+            // not an asset module, keep the url as is
+            return;
+        };
+        let url = match asset_url {
+            AssetUrl::DataUrl(data_url) => data_url.clone(),
+            // the css file is emitted into the output path, same as assets
+            AssetUrl::Filename(filename) => {
+                match code_generation_context.options.output.public_path.as_str() {
+                    "auto" => filename.clone(),
+                    public_path => format!("{public_path}{filename}"),
+                }
+            }
+        };
+        source.replace(
+            self.start,
+            self.end,
+            &format!("url({})", quoted(&url)),
+            None,
+        );
+    }
+}
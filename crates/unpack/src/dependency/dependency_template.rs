@@ -3,13 +3,25 @@ use std::fmt::Debug;
 use dyn_clone::DynClone;
 use rspack_sources::{BoxSource, ReplaceSource};
 
-use crate::module::CodeGenerationContext;
+use crate::module::{CodeGenerationContext, InitFragment};
+use crate::runtime::RuntimeGlobals;
+
+use super::DependencyId;
+
+/// state shared by all the dependency templates of a module during code generation
+pub struct TemplateContext<'a, 'b> {
+    pub code_generation_context: &'a CodeGenerationContext<'b>,
+    /// `None` for presentational dependencies which are not added to module graph
+    pub dependency_id: Option<DependencyId>,
+    pub init_fragments: &'a mut Vec<InitFragment>,
+    pub runtime_requirements: &'a mut RuntimeGlobals,
+}
 
 pub trait DependencyTemplate: Debug + DynClone + Send + Sync {
     fn apply(
         &self,
         _source: &mut ReplaceSource<BoxSource>,
-        _code_generation_context: &CodeGenerationContext,
+        _template_context: &mut TemplateContext,
     ) {
     }
 }
@@ -1,7 +1,10 @@
 use camino::Utf8PathBuf;
 use derive_new::new;
 
-use super::{module_dependency::ModuleDependency, AsDependencyTemplate, Dependency};
+use super::{
+    module_dependency::ModuleDependency, AsDependencyTemplate, Dependency, ReferencedExports,
+};
+use crate::module::ExportsInfo;
 
 // dependency for entry
 #[derive(new, Debug, Clone)]
@@ -20,5 +23,9 @@ impl ModuleDependency for EntryDependency {
     fn request(&self) -> &str {
         &self.request
     }
+    /// exports of entries are not exposed
+    fn referenced_exports(&self, _exports_info: &ExportsInfo) -> ReferencedExports {
+        ReferencedExports::Names(vec![])
+    }
 }
 impl AsDependencyTemplate for EntryDependency {}
@@ -0,0 +1,33 @@
+use super::{DependencyTemplate, TemplateContext};
+use crate::module::{InitFragment, InitFragmentStage};
+use crate::runtime::RuntimeGlobals;
+
+/// marks the exports of an esm module with `__esModule`
+#[derive(Debug, Clone)]
+pub struct HarmonyCompatibilityDependency;
+
+impl DependencyTemplate for HarmonyCompatibilityDependency {
+    fn apply(
+        &self,
+        _source: &mut rspack_sources::ReplaceSource<rspack_sources::BoxSource>,
+        template_context: &mut TemplateContext,
+    ) {
+        // the root of a concatenated module marks its exports instead
+        if template_context
+            .code_generation_context
+            .concatenated_module
+            .is_some()
+        {
+            return;
+        }
+        template_context
+            .runtime_requirements
+            .insert(RuntimeGlobals::MAKE_NAMESPACE_OBJECT);
+        template_context.init_fragments.push(InitFragment::new(
+            "__unpack_require__.r(__unpack_exports__);\n".to_string(),
+            InitFragmentStage::HarmonyExports,
+            0,
+            "harmony compatibility".to_string(),
+        ));
+    }
+}
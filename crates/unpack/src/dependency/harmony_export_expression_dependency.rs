@@ -0,0 +1,55 @@
+use super::{DependencyTemplate, TemplateContext};
+use crate::utils::identifier::quoted;
+
+/// name of the binding which holds the value of `export default expression`
+pub const DEFAULT_EXPORT: &str = "__unpack_default_export__";
+
+/// an anonymous function or class declaration of `export default`
+#[derive(Debug, Clone, Copy)]
+pub struct AnonymousDeclaration {
+    /// where the name is inserted, e.g. after `function*`
+    pub name_position: u32,
+    pub end: u32,
+}
+
+/// `export default expression`, also anonymous functions and classes, `start` and `end` are the
+/// range of `export default` which is replaced by a declaration of the default export binding
+#[derive(Debug, Clone)]
+pub struct HarmonyExportExpressionDependency {
+    pub start: u32,
+    pub end: u32,
+    /// anonymous declarations are named after the binding instead, so they are still hoisted
+    pub declaration: Option<AnonymousDeclaration>,
+}
+
+impl DependencyTemplate for HarmonyExportExpressionDependency {
+    fn apply(
+        &self,
+        source: &mut rspack_sources::ReplaceSource<rspack_sources::BoxSource>,
+        template_context: &mut TemplateContext,
+    ) {
+        let code_generation_context = template_context.code_generation_context;
+        // the binding may be renamed when the module is concatenated with others
+        let name = match code_generation_context.concatenated_module {
+            Some(concatenated_module) => {
+                concatenated_module.binding_name(code_generation_context.module_id, DEFAULT_EXPORT)
+            }
+            None => DEFAULT_EXPORT,
+        };
+        let Some(declaration) = self.declaration else {
+            source.replace(self.start, self.end, &format!("const {name} = "), None);
+            return;
+        };
+        source.replace(self.start, self.end, "", None);
+        source.insert(declaration.name_position, &format!(" {name}"), None);
+        // anonymous default exports are named `default`, unless a class defines a static `name`
+        source.insert(
+            declaration.end,
+            &format!(
+                "\nif ({name}.name === {}) Object.defineProperty({name}, \"name\", {{ value: \"default\", configurable: true }});",
+                quoted(name)
+            ),
+            None,
+        );
+    }
+}
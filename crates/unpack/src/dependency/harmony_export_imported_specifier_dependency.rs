@@ -0,0 +1,163 @@
+use swc_core::atoms::Atom;
+
+use super::{
+    harmony_export_getter, harmony_import_expression, harmony_import_var_name,
+    unused_harmony_export, Dependency, DependencyTemplate, ModuleDependency, ReferencedExports,
+    TemplateContext,
+};
+use crate::module::{ExportsInfo, InitFragment, InitFragmentStage, ModuleGraph, ModuleId};
+use crate::runtime::RuntimeGlobals;
+use crate::utils::identifier::property_access;
+
+/// re-export of an imported module, e.g. `export { a as b } from './a'`, `export * from './a'`,
+/// or `export { a }` where `a` is an imported binding
+#[derive(Debug, Clone)]
+pub struct HarmonyExportImportedSpecifierDependency {
+    pub request: Atom,
+    pub source_order: u32,
+    /// exported name, `None` for `export * from './a'`
+    pub name: Option<Atom>,
+    /// imported export name, `None` for namespace
+    pub id: Option<Atom>,
+}
+
+impl Dependency for HarmonyExportImportedSpecifierDependency {}
+impl ModuleDependency for HarmonyExportImportedSpecifierDependency {
+    fn request(&self) -> &str {
+        &self.request
+    }
+    fn is_harmony_import(&self) -> bool {
+        true
+    }
+    fn source_order(&self) -> Option<u32> {
+        Some(self.source_order)
+    }
+    fn is_export_star(&self) -> bool {
+        self.name.is_none()
+    }
+    fn imported_id(&self) -> Option<&Atom> {
+        self.name.as_ref().and(self.id.as_ref())
+    }
+    fn reexported_name(&self) -> Option<&Atom> {
+        self.name.as_ref()
+    }
+    fn referenced_exports(&self, exports_info: &ExportsInfo) -> ReferencedExports {
+        match (&self.name, &self.id) {
+            (Some(name), _) if !exports_info.is_used(name) => ReferencedExports::Names(vec![]),
+            (Some(_), Some(id)) => ReferencedExports::Names(vec![id.clone()]),
+            // `export * as ns from './a'`
+            (Some(_), None) => ReferencedExports::ExportsObject,
+            (None, _) if exports_info.exports_object_used => ReferencedExports::ExportsObject,
+            // the used exports of the module which may come from `export *`
+            (None, _) => ReferencedExports::Names(
+                exports_info
+                    .used
+                    .iter()
+                    .filter(|name| exports_info.is_star_export(name))
+                    .cloned()
+                    .collect(),
+            ),
+        }
+    }
+}
+impl DependencyTemplate for HarmonyExportImportedSpecifierDependency {
+    fn apply(
+        &self,
+        _source: &mut rspack_sources::ReplaceSource<rspack_sources::BoxSource>,
+        template_context: &mut TemplateContext,
+    ) {
+        let code_generation_context = template_context.code_generation_context;
+        let module_graph = code_generation_context.module_graph;
+        // the root of a concatenated module defines the getters of the exports instead
+        if code_generation_context.concatenated_module.is_some() {
+            return;
+        }
+        let fragment = match &self.name {
+            Some(name) if !module_graph.is_export_used(code_generation_context.module_id, name) => {
+                unused_harmony_export(name)
+            }
+            Some(name) => {
+                let expression = harmony_import_expression(
+                    module_graph,
+                    template_context,
+                    &self.request,
+                    self.source_order,
+                    self.id.as_deref(),
+                );
+                harmony_export_getter(template_context, name, &expression)
+            }
+            None => {
+                let var_name = harmony_import_var_name(&self.request, self.source_order);
+                let module_id = template_context.dependency_id.and_then(|dependency_id| {
+                    module_graph.get_module_id_by_dependency_id(dependency_id)
+                });
+                // left out as a side effect free module whose exports are unused
+                if module_id.is_some_and(|module_id| {
+                    !code_generation_context
+                        .chunk_graph
+                        .is_module_in_any_chunk(module_id)
+                }) {
+                    return;
+                }
+                // the used exports are known when both sides are esm, each of them gets a getter
+                // instead of copying all the exports at runtime
+                if let Some(names) = module_id.and_then(|module_id| {
+                    star_reexported_names(module_graph, code_generation_context.module_id, module_id)
+                }) {
+                    for name in names {
+                        let fragment = harmony_export_getter(
+                            template_context,
+                            &name,
+                            &format!("{var_name}{}", property_access(&name)),
+                        );
+                        template_context.init_fragments.push(fragment);
+                    }
+                    return;
+                }
+                template_context
+                    .runtime_requirements
+                    .insert(RuntimeGlobals::EXPORT_STAR);
+                let is_async = module_id.is_some_and(|module_id| module_graph.is_async(module_id));
+                // rendered right after the import of the re-exported module,
+                // or after waiting for it if it's async
+                InitFragment::new(
+                    format!("__unpack_require__.es({var_name}, __unpack_exports__);\n"),
+                    if is_async {
+                        InitFragmentStage::AsyncDependencies
+                    } else {
+                        InitFragmentStage::HarmonyImports
+                    },
+                    self.source_order * 2 + 1,
+                    format!("harmony reexport star {var_name}"),
+                )
+            }
+        };
+        template_context.init_fragments.push(fragment);
+    }
+}
+
+/// used exports of `module_id` which come from `export * from` the module `target`,
+/// `None` if they are unknown statically
+fn star_reexported_names(
+    module_graph: &ModuleGraph,
+    module_id: ModuleId,
+    target: ModuleId,
+) -> Option<Vec<Atom>> {
+    let exports_info = module_graph.exports_info(module_id)?;
+    let target_exports_info = module_graph.exports_info(target)?;
+    if exports_info.exports_object_used || target_exports_info.other_exports_provided {
+        return None;
+    }
+    Some(
+        exports_info
+            .used
+            .iter()
+            .filter(|name| {
+                name.as_str() != "default"
+                    && exports_info.is_star_export(name)
+                    && target_exports_info.provided.contains_key(*name)
+            })
+            .cloned()
+            .collect(),
+    )
+}
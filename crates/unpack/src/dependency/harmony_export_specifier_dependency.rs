@@ -0,0 +1,72 @@
+use swc_core::atoms::Atom;
+
+use super::{DependencyTemplate, TemplateContext};
+use crate::module::{InitFragment, InitFragmentStage};
+use crate::runtime::RuntimeGlobals;
+use crate::utils::identifier::quoted;
+
+/// export of a local binding, e.g. `export const a = 1` or `export { a as b }`
+#[derive(Debug, Clone)]
+pub struct HarmonyExportSpecifierDependency {
+    /// exported name
+    pub name: Atom,
+    /// local binding
+    pub local: Atom,
+}
+
+/// `__unpack_require__.d(__unpack_exports__, { "name": () => (value) });`
+pub fn harmony_export_getter(
+    template_context: &mut TemplateContext,
+    name: &str,
+    value: &str,
+) -> InitFragment {
+    template_context
+        .runtime_requirements
+        .insert(RuntimeGlobals::DEFINE_PROPERTY_GETTERS);
+    InitFragment::new(
+        format!(
+            "__unpack_require__.d(__unpack_exports__, {{ {}: () => ({value}) }});\n",
+            quoted(name)
+        ),
+        InitFragmentStage::HarmonyExports,
+        1,
+        format!("harmony export {name}"),
+    )
+}
+
+/// the getter of an export which is not used by any module is left out,
+/// so that minifiers can remove the unreferenced declaration
+pub fn unused_harmony_export(name: &str) -> InitFragment {
+    InitFragment::new(
+        format!(
+            "/* unused harmony export {} */\n",
+            name.replace("*/", "* /")
+        ),
+        InitFragmentStage::HarmonyExports,
+        1,
+        format!("harmony export {name}"),
+    )
+}
+
+impl DependencyTemplate for HarmonyExportSpecifierDependency {
+    fn apply(
+        &self,
+        _source: &mut rspack_sources::ReplaceSource<rspack_sources::BoxSource>,
+        template_context: &mut TemplateContext,
+    ) {
+        let code_generation_context = template_context.code_generation_context;
+        // the root of a concatenated module defines the getters of the exports instead
+        if code_generation_context.concatenated_module.is_some() {
+            return;
+        }
+        let fragment = if code_generation_context
+            .module_graph
+            .is_export_used(code_generation_context.module_id, &self.name)
+        {
+            harmony_export_getter(template_context, &self.name, &self.local)
+        } else {
+            unused_harmony_export(&self.name)
+        };
+        template_context.init_fragments.push(fragment);
+    }
+}
@@ -1,10 +1,80 @@
 use swc_core::atoms::Atom;
 
-use super::{AsDependencyTemplate, Dependency, ModuleDependency};
+use super::{
+    Dependency, DependencyTemplate, ModuleDependency, ReferencedExports, TemplateContext,
+};
+use crate::ids::PublicId;
+use crate::module::{ExportsInfo, InitFragment, InitFragmentStage, ModuleId};
+use crate::runtime::RuntimeGlobals;
+use crate::utils::identifier::{quoted, to_identifier};
 
+/// `import './a'`, also added for every `import ... from './a'` and `export ... from './a'`,
+/// it declares the variable which holds the exports of the imported module
 #[derive(Debug, Clone)]
 pub struct HarmonyImportSideEffectDependency {
     pub request: Atom,
+    /// order of the request in the module, starts from 0
+    pub source_order: u32,
+}
+
+/// name of the variable which holds the exports of an imported module
+pub fn harmony_import_var_name(request: &str, source_order: u32) -> String {
+    format!(
+        "{}__unpack_imported_module_{source_order}__",
+        to_identifier(request)
+    )
+}
+
+/// name of the variable which holds the exports of the module a re-exported binding is
+/// redirected to, see `ModuleGraph::redirected_ids`
+pub fn harmony_redirected_import_var_name(request: &str, module_id: &PublicId) -> String {
+    format!(
+        "{}__unpack_reexported_module_{}__",
+        to_identifier(request),
+        to_identifier(&module_id.to_string())
+    )
+}
+
+/// declare the variable which holds the exports of the imported module `module_id`,
+/// and wait for the module if it's async
+pub fn harmony_import_fragments(
+    template_context: &mut TemplateContext,
+    var_name: &str,
+    request: &str,
+    module_id: Option<ModuleId>,
+    source_order: u32,
+) {
+    let module_graph = template_context.code_generation_context.module_graph;
+    if module_id.is_some_and(|module_id| module_graph.is_async(module_id)) {
+        template_context.init_fragments.push(InitFragment::new(
+            format!("{var_name} = await __unpack_async_dependency__({var_name});\n"),
+            InitFragmentStage::AsyncDependencies,
+            source_order * 2,
+            format!("harmony async dependency {var_name}"),
+        ));
+    }
+    let content = match module_id {
+        Some(module_id) => {
+            template_context
+                .runtime_requirements
+                .insert(RuntimeGlobals::REQUIRE);
+            let chunk_graph = template_context.code_generation_context.chunk_graph;
+            format!(
+                "var {var_name} = __unpack_require__({});\n",
+                chunk_graph.get_module_id(module_id).to_js()
+            )
+        }
+        None => format!(
+            "var {var_name} = (() => {{ throw new Error({}); }})();\n",
+            quoted(&format!("Cannot find module '{request}'"))
+        ),
+    };
+    template_context.init_fragments.push(InitFragment::new(
+        content,
+        InitFragmentStage::HarmonyImports,
+        source_order * 2,
+        format!("harmony import {var_name}"),
+    ));
 }
 
 impl Dependency for HarmonyImportSideEffectDependency {}
@@ -12,5 +82,47 @@ impl ModuleDependency for HarmonyImportSideEffectDependency {
     fn request(&self) -> &str {
         &self.request
     }
+    fn is_harmony_import(&self) -> bool {
+        true
+    }
+    fn source_order(&self) -> Option<u32> {
+        Some(self.source_order)
+    }
+    fn referenced_exports(&self, _exports_info: &ExportsInfo) -> ReferencedExports {
+        ReferencedExports::Names(vec![])
+    }
+}
+impl DependencyTemplate for HarmonyImportSideEffectDependency {
+    fn apply(
+        &self,
+        _source: &mut rspack_sources::ReplaceSource<rspack_sources::BoxSource>,
+        template_context: &mut TemplateContext,
+    ) {
+        let var_name = harmony_import_var_name(&self.request, self.source_order);
+        let code_generation_context = template_context.code_generation_context;
+        // the root of a concatenated module imports the modules which are not concatenated
+        if code_generation_context.concatenated_module.is_some() {
+            return;
+        }
+        let module_id = template_context.dependency_id.and_then(|dependency_id| {
+            code_generation_context
+                .module_graph
+                .get_module_id_by_dependency_id(dependency_id)
+        });
+        // left out as a side effect free module whose exports are unused
+        if module_id.is_some_and(|module_id| {
+            !code_generation_context
+                .chunk_graph
+                .is_module_in_any_chunk(module_id)
+        }) {
+            return;
+        }
+        harmony_import_fragments(
+            template_context,
+            &var_name,
+            &self.request,
+            module_id,
+            self.source_order,
+        );
+    }
 }
-impl AsDependencyTemplate for HarmonyImportSideEffectDependency {}
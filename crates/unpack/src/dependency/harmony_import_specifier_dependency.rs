@@ -0,0 +1,186 @@
+use swc_core::atoms::Atom;
+
+use super::{
+    harmony_import_fragments, harmony_import_var_name, harmony_redirected_import_var_name,
+    Dependency, DependencyTemplate, ModuleDependency, ReferencedExports, TemplateContext,
+};
+use crate::module::{ExportsInfo, ExportsType, InitFragment, InitFragmentStage, ModuleGraph};
+use crate::runtime::RuntimeGlobals;
+use crate::utils::identifier::{is_identifier, property_access};
+
+/// how an imported binding is referenced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportReferenceKind {
+    Normal,
+    /// `foo()`, called without `this` of the imported module
+    Call,
+    /// `{ foo }`
+    Shorthand,
+}
+
+/// reference to an imported binding, e.g. `foo` in `import { foo } from './a'; foo()`
+#[derive(Debug, Clone)]
+pub struct HarmonyImportSpecifierDependency {
+    pub request: Atom,
+    pub source_order: u32,
+    /// local name of the binding
+    pub name: Atom,
+    /// imported export name, `None` for namespace import
+    pub id: Option<Atom>,
+    pub kind: ImportReferenceKind,
+    pub start: u32,
+    pub end: u32,
+}
+
+/// expression to access the export `id` (`None` for namespace) of an imported module,
+/// the module defining the export is imported instead if the dependency is redirected
+pub fn harmony_import_expression(
+    module_graph: &ModuleGraph,
+    template_context: &mut TemplateContext,
+    request: &str,
+    source_order: u32,
+    id: Option<&str>,
+) -> String {
+    let module_id = template_context
+        .dependency_id
+        .and_then(|dependency_id| module_graph.get_module_id_by_dependency_id(dependency_id));
+    let redirected_id = template_context
+        .dependency_id
+        .and_then(|dependency_id| module_graph.redirected_ids.get(&dependency_id));
+    let (var_name, id) = match (redirected_id, module_id) {
+        (Some(redirected_id), Some(module_id)) => {
+            let chunk_graph = template_context.code_generation_context.chunk_graph;
+            let var_name =
+                harmony_redirected_import_var_name(request, chunk_graph.get_module_id(module_id));
+            harmony_import_fragments(
+                template_context,
+                &var_name,
+                request,
+                Some(module_id),
+                source_order,
+            );
+            (var_name, redirected_id.as_deref())
+        }
+        _ => (harmony_import_var_name(request, source_order), id),
+    };
+    let exports_type = module_id
+        .map(|module_id| {
+            module_graph
+                .module_by_id(module_id)
+                .build_meta()
+                .exports_type
+        })
+        .unwrap_or_default();
+    let namespace_cache = (id.is_none() && exports_type != ExportsType::Namespace)
+        .then(|| harmony_namespace_cache_name(&var_name));
+    if let Some(namespace_cache) = &namespace_cache {
+        template_context.init_fragments.push(InitFragment::new(
+            format!("var {namespace_cache};\n"),
+            InitFragmentStage::HarmonyImports,
+            source_order * 2,
+            format!("harmony namespace cache {namespace_cache}"),
+        ));
+    }
+    harmony_import_access(
+        &var_name,
+        exports_type,
+        id,
+        namespace_cache.as_deref(),
+        template_context.runtime_requirements,
+    )
+}
+
+/// name of the variable which caches the namespace object of a module which is not esm
+pub fn harmony_namespace_cache_name(var_name: &str) -> String {
+    format!("{var_name}_namespace_cache")
+}
+
+/// expression to access the export `id` (`None` for namespace) of the exports held by `var_name`.
+/// the namespace object of a module which is not esm is created on first access and kept in
+/// `namespace_cache` if the variable is declared
+pub fn harmony_import_access(
+    var_name: &str,
+    exports_type: ExportsType,
+    id: Option<&str>,
+    namespace_cache: Option<&str>,
+    runtime_requirements: &mut RuntimeGlobals,
+) -> String {
+    match (id, exports_type) {
+        (None, ExportsType::Namespace) | (Some("default"), ExportsType::DefaultWithNamed) => {
+            var_name.to_string()
+        }
+        // `default` is the exports object, `__esModule` modules are their own namespace
+        (None, _) => {
+            runtime_requirements.insert(RuntimeGlobals::CREATE_FAKE_NAMESPACE_OBJECT);
+            let mode = u8::from(exports_type == ExportsType::Dynamic);
+            let namespace = format!("__unpack_require__.t({var_name}, {mode})");
+            match namespace_cache {
+                Some(cache) => format!("({cache} || ({cache} = {namespace}))"),
+                None => namespace,
+            }
+        }
+        (Some("default"), ExportsType::Dynamic) => {
+            format!("({var_name} && {var_name}.__esModule ? {var_name}[\"default\"] : {var_name})")
+        }
+        (Some(id), _) => format!("{var_name}{}", property_access(id)),
+    }
+}
+
+impl Dependency for HarmonyImportSpecifierDependency {}
+impl ModuleDependency for HarmonyImportSpecifierDependency {
+    fn request(&self) -> &str {
+        &self.request
+    }
+    fn is_harmony_import(&self) -> bool {
+        true
+    }
+    fn source_order(&self) -> Option<u32> {
+        Some(self.source_order)
+    }
+    fn imported_id(&self) -> Option<&Atom> {
+        self.id.as_ref()
+    }
+    fn referenced_exports(&self, _exports_info: &ExportsInfo) -> ReferencedExports {
+        match &self.id {
+            Some(id) => ReferencedExports::Names(vec![id.clone()]),
+            None => ReferencedExports::ExportsObject,
+        }
+    }
+}
+impl DependencyTemplate for HarmonyImportSpecifierDependency {
+    fn apply(
+        &self,
+        source: &mut rspack_sources::ReplaceSource<rspack_sources::BoxSource>,
+        template_context: &mut TemplateContext,
+    ) {
+        let code_generation_context = template_context.code_generation_context;
+        let expression = match (
+            code_generation_context.concatenated_module,
+            template_context.dependency_id,
+        ) {
+            // bindings of concatenated modules are referenced directly
+            (Some(concatenated_module), Some(dependency_id)) => concatenated_module
+                .import_expression(
+                    code_generation_context.module_graph,
+                    code_generation_context.chunk_graph,
+                    dependency_id,
+                    self.id.as_deref(),
+                    template_context.runtime_requirements,
+                ),
+            _ => harmony_import_expression(
+                code_generation_context.module_graph,
+                template_context,
+                &self.request,
+                self.source_order,
+                self.id.as_deref(),
+            ),
+        };
+        let content = match self.kind {
+            ImportReferenceKind::Normal => expression,
+            ImportReferenceKind::Call if is_identifier(&expression) => expression,
+            ImportReferenceKind::Call => format!("(0, {expression})"),
+            ImportReferenceKind::Shorthand => format!("{}: {expression}", self.name),
+        };
+        source.replace(self.start, self.end, &content, None);
+    }
+}
@@ -2,10 +2,63 @@ use std::fmt::Debug;
 
 use dyn_clone::{clone_trait_object, DynClone};
 
+use swc_core::atoms::Atom;
+
 use super::Dependency;
+use crate::module::{ExportsInfo, ModuleType};
+
+/// exports of the referenced module used by a dependency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReferencedExports {
+    /// the exports object is used as a whole, e.g. namespace import
+    ExportsObject,
+    /// only the listed exports, empty if the module is only evaluated, e.g. `import './a'`
+    Names(Vec<Atom>),
+}
 
 pub trait ModuleDependency: Dependency + Debug + DynClone {
     fn request(&self) -> &str;
+    /// the type of the referenced module regardless of its extension, rules can still override it
+    fn module_type(&self) -> Option<ModuleType> {
+        None
+    }
+    /// whether the referenced module is the entry of a new chunk group with its own runtime,
+    /// e.g. workers
+    fn is_entry(&self) -> bool {
+        false
+    }
+    /// whether it's an esm import, the importer waits for the imported module if it's async
+    fn is_harmony_import(&self) -> bool {
+        false
+    }
+    /// order of the request in the module for esm imports, modules are evaluated in this order
+    fn source_order(&self) -> Option<u32> {
+        None
+    }
+    /// `export * from './a'`, the module provides all the exports of the referenced module
+    /// except `default`
+    fn is_export_star(&self) -> bool {
+        false
+    }
+    /// the export imported by name, e.g. `a` of `import { a } from './a'` and `export { a } from './a'`,
+    /// the dependency is redirected to the module defining it if the referenced module is side effect free
+    fn imported_id(&self) -> Option<&Atom> {
+        None
+    }
+    /// the name of a re-export, e.g. `b` of `export { a as b } from './a'`
+    fn reexported_name(&self) -> Option<&Atom> {
+        None
+    }
+    /// exports of the referenced module used by the dependency, `exports_info` is the one of
+    /// the module which has the dependency, similar with webpack's getReferencedExports
+    fn referenced_exports(&self, _exports_info: &ExportsInfo) -> ReferencedExports {
+        ReferencedExports::ExportsObject
+    }
+    /// preludes of the at-rules wrapping the css of the referenced module, only for `@import`
+    /// with `layer()`, `supports()` or media queries
+    fn css_conditions(&self) -> &[String] {
+        &[]
+    }
 }
 clone_trait_object!(ModuleDependency);
 
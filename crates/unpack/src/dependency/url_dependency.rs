@@ -0,0 +1,54 @@
+use swc_core::atoms::Atom;
+
+use super::{Dependency, DependencyTemplate, ModuleDependency, TemplateContext};
+use crate::module::ModuleType;
+use crate::runtime::RuntimeGlobals;
+use crate::utils::identifier::quoted;
+
+/// `new URL("./a.png", import.meta.url)`, the referenced file is always an asset,
+/// the expression is replaced with the url of the asset at runtime
+#[derive(Debug, Clone)]
+pub struct UrlDependency {
+    pub request: Atom,
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Dependency for UrlDependency {}
+impl ModuleDependency for UrlDependency {
+    fn request(&self) -> &str {
+        &self.request
+    }
+    fn module_type(&self) -> Option<ModuleType> {
+        Some(ModuleType::AssetResource)
+    }
+}
+impl DependencyTemplate for UrlDependency {
+    fn apply(
+        &self,
+        source: &mut rspack_sources::ReplaceSource<rspack_sources::BoxSource>,
+        template_context: &mut TemplateContext,
+    ) {
+        let module_graph = template_context.code_generation_context.module_graph;
+        let chunk_graph = template_context.code_generation_context.chunk_graph;
+        let url = match template_context
+            .dependency_id
+            .and_then(|dependency_id| module_graph.get_module_id_by_dependency_id(dependency_id))
+        {
+            Some(module_id) => {
+                template_context
+                    .runtime_requirements
+                    .insert(RuntimeGlobals::REQUIRE | RuntimeGlobals::BASE_URI);
+                format!(
+                    "new URL(__unpack_require__({}), __unpack_require__.b)",
+                    chunk_graph.get_module_id(module_id).to_js()
+                )
+            }
+            None => format!(
+                "(() => {{ throw new Error({}); }})()",
+                quoted(&format!("Cannot find module '{}'", self.request))
+            ),
+        };
+        source.replace(self.start, self.end, &url, None);
+    }
+}
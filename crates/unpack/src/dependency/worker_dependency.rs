@@ -0,0 +1,74 @@
+use swc_core::atoms::Atom;
+
+use super::{
+    Dependency, DependencyTemplate, ModuleDependency, ReferencedExports, TemplateContext,
+};
+use crate::chunk::ChunkFileType;
+use crate::module::ExportsInfo;
+use crate::runtime::RuntimeGlobals;
+use crate::utils::identifier::quoted;
+
+/// `new URL("./worker.js", import.meta.url)` passed to `new Worker()` or `new SharedWorker()`,
+/// the worker module is the entry of its own chunk, the url is replaced with the url of the chunk
+#[derive(Debug, Clone)]
+pub struct WorkerDependency {
+    pub request: Atom,
+    /// range of the `new URL()` expression
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Dependency for WorkerDependency {}
+impl ModuleDependency for WorkerDependency {
+    fn request(&self) -> &str {
+        &self.request
+    }
+    fn is_entry(&self) -> bool {
+        true
+    }
+    /// exports of entries are not exposed
+    fn referenced_exports(&self, _exports_info: &ExportsInfo) -> ReferencedExports {
+        ReferencedExports::Names(vec![])
+    }
+}
+impl DependencyTemplate for WorkerDependency {
+    fn apply(
+        &self,
+        source: &mut rspack_sources::ReplaceSource<rspack_sources::BoxSource>,
+        template_context: &mut TemplateContext,
+    ) {
+        let code_generation_context = template_context.code_generation_context;
+        let module_graph = code_generation_context.module_graph;
+        let chunk_graph = code_generation_context.chunk_graph;
+        let chunk_id = template_context
+            .dependency_id
+            .and_then(|dependency_id| module_graph.get_module_id_by_dependency_id(dependency_id))
+            .and_then(|module_id| {
+                chunk_graph
+                    .get_module_entry_chunks(module_id)
+                    .into_iter()
+                    .next()
+            });
+        let url = match chunk_id {
+            Some(chunk_id) => {
+                template_context
+                    .runtime_requirements
+                    .insert(RuntimeGlobals::PUBLIC_PATH | RuntimeGlobals::BASE_URI);
+                let filename = chunk_graph.get_chunk_filename(
+                    &code_generation_context.options.output,
+                    chunk_id,
+                    ChunkFileType::Js,
+                );
+                format!(
+                    "new URL(__unpack_require__.p + {}, __unpack_require__.b)",
+                    quoted(&filename)
+                )
+            }
+            None => format!(
+                "(() => {{ throw new Error({}); }})()",
+                quoted(&format!("Cannot find module '{}'", self.request))
+            ),
+        };
+        source.replace(self.start, self.end, &url, None);
+    }
+}
@@ -12,3 +12,71 @@ pub struct ResolveError {
 }
 
 pub type Diagnostics = Vec<Report>;
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("json parse error: {message}")]
+#[diagnostic()]
+pub struct JsonParseError {
+    #[source_code]
+    pub source_code: miette::NamedSource<String>,
+    #[label("{message}")]
+    pub span: miette::SourceSpan,
+    pub message: String,
+}
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("css parse error: {message}")]
+#[diagnostic()]
+pub struct CssParseError {
+    #[source_code]
+    pub source_code: miette::NamedSource<String>,
+    #[label("{message}")]
+    pub span: miette::SourceSpan,
+    pub message: String,
+}
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("js parse error: {message}")]
+#[diagnostic()]
+pub struct JsParseError {
+    #[source_code]
+    pub source_code: miette::NamedSource<String>,
+    /// every error reported by the parser, including the recoverable ones
+    #[label(collection)]
+    pub labels: Vec<miette::LabeledSpan>,
+    pub message: String,
+}
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("top level await in {module} requires async functions, which are disabled by output.environment.asyncFunction")]
+#[diagnostic()]
+pub struct TopLevelAwaitUnsupportedError {
+    pub module: String,
+}
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("the name `{name}` of split chunks cache group `{cache_group}` conflicts with an existing chunk, its modules are not split")]
+#[diagnostic(severity(Warning))]
+pub struct SplitChunksNameConflictWarning {
+    pub name: String,
+    pub cache_group: String,
+}
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("unknown property `import.meta.{property}`, it is replaced with `undefined`")]
+#[diagnostic(severity(Warning))]
+pub struct UnknownImportMetaPropertyWarning {
+    #[source_code]
+    pub source_code: miette::NamedSource<String>,
+    #[label("unknown property")]
+    pub span: miette::SourceSpan,
+    pub property: String,
+}
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to load the source map of {module}: {message}")]
+#[diagnostic(severity(Warning))]
+pub struct InputSourceMapWarning {
+    pub module: String,
+    pub message: String,
+}
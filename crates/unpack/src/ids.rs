@@ -0,0 +1,6 @@
+mod chunk_ids;
+mod module_ids;
+mod public_id;
+pub use chunk_ids::*;
+pub use module_ids::*;
+pub use public_id::*;
@@ -0,0 +1,33 @@
+use super::{assign_deterministic_ids, PublicId};
+use crate::chunk::ChunkGraph;
+use crate::compiler::ChunkIds;
+
+/// assign public ids to all the chunks, see `ChunkIds`. chunks are created in a deterministic
+/// order, as the chunk graph is built from the sorted connections of the module graph
+pub fn assign_chunk_ids(algorithm: ChunkIds, chunk_graph: &mut ChunkGraph) {
+    let names = chunk_graph
+        .chunks
+        .iter_enumerated()
+        .map(|(chunk_id, chunk)| {
+            let name = chunk
+                .name
+                .clone()
+                .unwrap_or_else(|| chunk_id.index().to_string());
+            (chunk_id, name)
+        })
+        .collect::<Vec<_>>();
+    let ids = match algorithm {
+        ChunkIds::Natural => names
+            .into_iter()
+            .map(|(chunk_id, _)| (chunk_id, PublicId::Number(chunk_id.index() as u32)))
+            .collect(),
+        ChunkIds::Named => names
+            .into_iter()
+            .map(|(chunk_id, name)| (chunk_id, PublicId::String(name)))
+            .collect(),
+        ChunkIds::Deterministic => assign_deterministic_ids(names),
+    };
+    for (chunk_id, id) in ids {
+        chunk_graph.set_chunk_id(chunk_id, id);
+    }
+}
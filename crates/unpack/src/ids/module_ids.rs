@@ -0,0 +1,71 @@
+use camino::Utf8Path;
+use rustc_hash::FxHashSet;
+
+use super::{assign_deterministic_ids, PublicId};
+use crate::chunk::ChunkGraph;
+use crate::compiler::ModuleIds;
+use crate::module::{ModuleGraph, ModuleId};
+use crate::utils::identifier::contextify;
+
+/// assign public ids to all the modules, see `ModuleIds`. modules not in any chunk get ids
+/// as well, they are never rendered but may be referenced by left out code
+pub fn assign_module_ids(
+    algorithm: ModuleIds,
+    context: &Utf8Path,
+    module_graph: &mut ModuleGraph,
+    chunk_graph: &mut ChunkGraph,
+) {
+    let names = module_graph
+        .modules
+        .iter_enumerated()
+        .map(|(module_id, module)| (module_id, contextify(module.identifier(), context)))
+        .collect::<Vec<_>>();
+    let ids = match algorithm {
+        ModuleIds::Natural => natural_module_order(module_graph, chunk_graph, names)
+            .into_iter()
+            .enumerate()
+            .map(|(index, module_id)| (module_id, PublicId::Number(index as u32)))
+            .collect(),
+        ModuleIds::Named => names
+            .into_iter()
+            .map(|(module_id, name)| (module_id, PublicId::String(name)))
+            .collect(),
+        ModuleIds::Deterministic => assign_deterministic_ids(names),
+    };
+    for (module_id, id) in ids {
+        chunk_graph.set_module_id(module_id, id);
+    }
+}
+
+/// modules in pre order of the module graph from the entry modules of the chunks, followed by
+/// the unreachable ones sorted by name
+fn natural_module_order(
+    module_graph: &mut ModuleGraph,
+    chunk_graph: &mut ChunkGraph,
+    mut names: Vec<(ModuleId, String)>,
+) -> Vec<ModuleId> {
+    let mut visited = FxHashSet::default();
+    let mut order = vec![];
+    for chunk_id in chunk_graph.chunks.indices() {
+        let mut stack = chunk_graph.get_chunk_entry_modules(chunk_id);
+        stack.reverse();
+        while let Some(module_id) = stack.pop() {
+            if !visited.insert(module_id) {
+                continue;
+            }
+            order.push(module_id);
+            let connection_ids = module_graph.get_outgoing_connections(module_id);
+            for connection_id in connection_ids.into_iter().rev() {
+                stack.push(
+                    module_graph
+                        .connection_by_id(connection_id)
+                        .resolved_module_id,
+                );
+            }
+        }
+    }
+    names.retain(|(module_id, _)| !visited.contains(module_id));
+    names.sort_by(|(_, a), (_, b)| a.cmp(b));
+    order.extend(names.into_iter().map(|(module_id, _)| module_id));
+    order
+}
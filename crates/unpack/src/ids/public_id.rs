@@ -0,0 +1,62 @@
+use std::fmt;
+
+use rustc_hash::FxHashSet;
+
+use crate::utils::{hash::numeric_hash, identifier::quoted};
+
+/// id of a module or a chunk in the output, independent of its index in the graphs
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PublicId {
+    Number(u32),
+    String(String),
+}
+
+impl PublicId {
+    /// javascript literal of the id, e.g. the key of a module factory
+    pub fn to_js(&self) -> String {
+        match self {
+            PublicId::Number(id) => id.to_string(),
+            PublicId::String(id) => quoted(id),
+        }
+    }
+}
+
+impl fmt::Display for PublicId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PublicId::Number(id) => write!(f, "{id}"),
+            PublicId::String(id) => write!(f, "{id}"),
+        }
+    }
+}
+
+/// numeric hashes of the names, with at least 3 digits and more digits if the ids would take
+/// more than 80% of the range, the same as webpack's deterministic ids. a colliding name is
+/// rehashed with a suffix, names are processed in order so the result doesn't depend on the
+/// order of `items`
+pub fn assign_deterministic_ids<T>(mut items: Vec<(T, String)>) -> Vec<(T, PublicId)> {
+    let mut range = 1000u64;
+    while items.len() as u64 * 10 > range * 8 {
+        range *= 10;
+    }
+    items.sort_by(|(_, a), (_, b)| a.cmp(b));
+    let mut used_ids = FxHashSet::default();
+    items
+        .into_iter()
+        .map(|(item, name)| {
+            let mut salt = 0;
+            let id = loop {
+                let key = match salt {
+                    0 => name.clone(),
+                    _ => format!("{name}{salt}"),
+                };
+                let id = numeric_hash(key.as_bytes()) % range;
+                if used_ids.insert(id) {
+                    break id;
+                }
+                salt += 1;
+            };
+            (item, PublicId::Number(id as u32))
+        })
+        .collect()
+}
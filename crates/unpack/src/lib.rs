@@ -5,10 +5,15 @@ pub mod compilation;
 pub mod compiler;
 pub mod dependency;
 pub mod errors;
+pub mod ids;
+pub mod loader;
 pub mod module;
 pub mod normal_module_factory;
+pub mod optimize;
 pub mod plugin;
 pub mod resolver;
 pub mod resolver_factory;
+pub mod runtime;
+pub mod stats;
 pub mod task;
 pub mod utils;
@@ -0,0 +1,57 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use camino::Utf8PathBuf;
+use miette::{Result, WrapErr};
+use rspack_sources::SourceMap;
+
+use crate::compiler::CompilerOptions;
+use crate::utils::source_map::compose_source_maps;
+
+/// the resource being loaded, `content` is replaced by each loader in turn
+#[derive(Debug)]
+pub struct LoaderContext {
+    pub resource_path: Utf8PathBuf,
+    /// e.g. `?inline`, empty if the request has no query
+    pub resource_query: String,
+    pub content: Vec<u8>,
+    /// a loader sets the map of its new content back to the content it received,
+    /// which is composed with the maps of the previous loaders
+    pub source_map: Option<SourceMap>,
+    pub options: Arc<CompilerOptions>,
+}
+
+/// transforms the content of a module before it is parsed, similar with webpack's loader
+#[async_trait]
+pub trait Loader: Debug + Send + Sync {
+    fn name(&self) -> &str;
+    async fn run(&self, loader_context: &mut LoaderContext) -> Result<()>;
+}
+
+pub type BoxLoader = Arc<dyn Loader>;
+
+/// run loaders from the last to the first, same as webpack
+pub async fn run_loaders(loaders: &[BoxLoader], loader_context: &mut LoaderContext) -> Result<()> {
+    for loader in loaders.iter().rev() {
+        let input_map = loader_context.source_map.take();
+        loader.run(loader_context).await.wrap_err_with(|| {
+            format!(
+                "loader \"{}\" failed to process {}{}",
+                loader.name(),
+                loader_context.resource_path,
+                loader_context.resource_query
+            )
+        })?;
+        // a loader without a map loses the mappings, same as webpack
+        loader_context.source_map = match (loader_context.source_map.take(), input_map) {
+            (Some(map), Some(input_map)) => Some(compose_source_maps(
+                &String::from_utf8_lossy(&loader_context.content),
+                map,
+                input_map,
+            )),
+            (map, _) => map,
+        };
+    }
+    Ok(())
+}
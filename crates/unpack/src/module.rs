@@ -1,15 +1,33 @@
 mod ast;
 mod ast2;
+mod build_meta;
 mod connection;
+mod const_value;
+mod define;
+mod dependency_collector;
+mod exports_info;
+mod import_meta;
+mod init_fragment;
 mod module_graph;
 mod module_graph_module;
 mod module_node;
 mod module_scanner;
+mod module_type;
 mod normal_module;
+mod parser_and_generator;
+mod raw_module;
+mod transform;
 
+pub(crate) use ast::parse_program;
+pub use build_meta::*;
 pub use connection::*;
+pub use exports_info::*;
+pub use init_fragment::*;
 pub use module_graph::*;
 pub use module_graph_module::*;
 pub use module_node::*;
 pub use module_scanner::*;
+pub use module_type::*;
 pub use normal_module::*;
+pub use parser_and_generator::*;
+pub use raw_module::*;
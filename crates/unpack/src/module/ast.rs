@@ -1,16 +1,24 @@
-use std::sync::Arc;
-
-use crate::dependency::{
-    BoxDependency, BoxDependencyTemplate, ConstDependency, HarmonyImportSideEffectDependency,
-    SpanExt,
+use crate::compiler::CompilerOptions;
+use crate::dependency::{ImportReferenceKind, SpanExt};
+use crate::errors::miette::Result;
+use crate::errors::JsParseError;
+use camino::Utf8Path;
+use miette::{LabeledSpan, NamedSource};
+use swc_core::atoms::Atom;
+use swc_core::common::comments::Comments;
+use swc_core::common::{
+    sync::Lrc, FileName, Mark, SourceFile, SourceMap, Span, Spanned, SyntaxContext, GLOBALS,
 };
-use crate::errors::miette::{miette, Result};
-use miette::LabeledSpan;
-use swc_core::common::{FileName, SourceMap, Spanned};
-use swc_core::ecma::ast::Program;
-use swc_core::ecma::parser::{EsSyntax, Parser, StringInput, Syntax};
-use swc_core::ecma::utils::swc_ecma_ast;
+use swc_core::ecma::ast::*;
+use swc_core::ecma::parser::{EsSyntax, Parser, StringInput, Syntax, TsSyntax};
+use swc_core::ecma::transforms::base::resolver;
+use swc_core::ecma::utils::find_pat_ids;
+use swc_core::ecma::visit::{Visit, VisitMutWith, VisitWith};
 
+use super::const_value::ConstValue;
+use super::define::Define;
+use super::dependency_collector::DependencyCollector;
+use super::import_meta::ImportMeta;
 use super::ParseResult;
 
 #[derive(Debug)]
@@ -18,69 +26,703 @@ use super::ParseResult;
 pub struct AST {
     pub program: Program,
 }
-pub fn parse(content: String) -> Result<ParseResult> {
-    let cm = SourceMap::default();
-    let fm = cm.new_source_file(Arc::new(FileName::Custom("input.js".into())), content);
-
-    let lexer = swc_core::ecma::parser::lexer::Lexer::new(
+pub fn parse(
+    content: String,
+    resource_path: &Utf8Path,
+    options: &CompilerOptions,
+) -> Result<ParseResult> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        Lrc::new(FileName::Real(resource_path.as_std_path().to_path_buf())),
+        content,
+    );
+    // transformed modules (e.g. typescript) are always plain javascript at this point
+    let mut program = parse_program(
+        &fm,
         Syntax::Es(EsSyntax {
             jsx: true,
             ..Default::default()
         }),
-        Default::default(),
-        StringInput::from(&*fm),
         None,
-    );
+    )?;
+    GLOBALS.set(&Default::default(), || {
+        let unresolved_mark = Mark::new();
+        let top_level_mark = Mark::new();
+        program.visit_mut_with(&mut resolver(unresolved_mark, top_level_mark, false));
+        let mut scanner = DependencyScanner {
+            collector: DependencyCollector::default(),
+            top_level_ctxt: SyntaxContext::empty().apply_mark(top_level_mark),
+            unresolved_ctxt: SyntaxContext::empty().apply_mark(unresolved_mark),
+            source: &fm.src,
+            define: Define::new(&options.define),
+            import_meta: ImportMeta::new(resource_path, &fm.src, options),
+            function_depth: 0,
+        };
+        match &program {
+            Program::Module(module) => scanner.scan(module),
+            Program::Script(script) => script.visit_with(&mut scanner),
+        }
+        // only esm modules can be concatenated, which renames their top level bindings
+        if scanner.collector.is_harmony() {
+            program.visit_with(&mut TopLevelScanner {
+                collector: &mut scanner.collector,
+                top_level_ctxt: scanner.top_level_ctxt,
+                unresolved_ctxt: scanner.unresolved_ctxt,
+            });
+        }
+        Ok(ParseResult {
+            warnings: scanner.import_meta.warnings,
+            ..scanner.collector.finish()
+        })
+    })
+}
 
-    let mut parser = Parser::new_from(lexer);
+fn atom_of_export_name(name: &ModuleExportName) -> Atom {
+    match name {
+        ModuleExportName::Ident(ident) => ident.sym.clone(),
+        ModuleExportName::Str(str) => str.value.clone(),
+    }
+}
+
+struct DependencyScanner<'a> {
+    collector: DependencyCollector,
+    top_level_ctxt: SyntaxContext,
+    unresolved_ctxt: SyntaxContext,
+    source: &'a str,
+    define: Define<'a>,
+    import_meta: ImportMeta<'a>,
+    /// depth of functions, `await` is only top level at 0
+    function_depth: u32,
+}
+
+/// whether the expression is `import.meta` or a member expression on it
+fn is_import_meta(expr: &Expr) -> bool {
+    match expr {
+        Expr::MetaProp(meta) => meta.kind == MetaPropKind::ImportMeta,
+        Expr::Member(member) => is_import_meta(&member.obj),
+        _ => false,
+    }
+}
 
-    let program = match parser.parse_program() {
-        Ok(prog) => prog,
-        Err(err) => {
-            let mut errors = parser.take_errors();
-            if errors.is_empty() {
-                errors.push(err);
-            }
-            let labels = errors
-                .into_iter()
-                .map(|error| {
-                    let message = error.kind().msg().to_string();
-                    let span = error.span();
-                    let start = span.lo.0.saturating_sub(1) as usize;
-                    let end = span.hi.0.saturating_sub(1) as usize;
-                    let len = end - start;
-                    LabeledSpan::new(Some(message), start, len)
-                })
-                .collect::<Vec<_>>();
-            return Err(miette!(labels = labels, "parse error"));
-        }
-    };
-    // Analyze the AST for all import dependencies
-    let mut presentational_dependencies: Vec<BoxDependencyTemplate> = vec![];
-    let mut module_dependencies: Vec<BoxDependency> = vec![];
-    match &program {
-        swc_ecma_ast::Program::Module(module) => {
-            for item in &module.body {
-                if let swc_ecma_ast::ModuleItem::ModuleDecl(swc_ecma_ast::ModuleDecl::Import(
-                    import,
-                )) = item
-                {
-                    let request = import.src.value.clone();
-                    module_dependencies
-                        .push(Box::new(HarmonyImportSideEffectDependency { request }));
-                    presentational_dependencies.push(Box::new(ConstDependency::new(
-                        import.span.real_lo(),
-                        import.span.real_hi(),
-                        "".into(),
-                    )));
+/// finds `var` and function declarations, which are hoisted out of blocks
+#[derive(Default)]
+struct HoistedDeclarations {
+    found: bool,
+}
+
+impl Visit for HoistedDeclarations {
+    fn visit_var_decl(&mut self, var: &VarDecl) {
+        if var.kind == VarDeclKind::Var {
+            self.found = true;
+        }
+    }
+    fn visit_fn_decl(&mut self, _fn_decl: &FnDecl) {
+        self.found = true;
+    }
+    // declarations in functions and classes are not hoisted out of them
+    fn visit_function(&mut self, _function: &Function) {}
+    fn visit_arrow_expr(&mut self, _arrow: &ArrowExpr) {}
+    fn visit_class(&mut self, _class: &Class) {}
+}
+
+fn has_hoisted_declarations(stmt: &Stmt) -> bool {
+    let mut hoisted = HoistedDeclarations::default();
+    stmt.visit_with(&mut hoisted);
+    hoisted.found
+}
+
+impl DependencyScanner<'_> {
+    fn scan(&mut self, module: &Module) {
+        // imports are hoisted, collect their bindings before visiting any reference
+        for item in &module.body {
+            if let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item {
+                self.add_import_bindings(import);
+            }
+        }
+        for item in &module.body {
+            match item {
+                ModuleItem::ModuleDecl(decl) => self.scan_module_decl(decl),
+                ModuleItem::Stmt(stmt) => stmt.visit_with(self),
+            }
+        }
+    }
+    fn add_import_bindings(&mut self, import: &ImportDecl) {
+        for specifier in &import.specifiers {
+            let (local, imported) = match specifier {
+                ImportSpecifier::Named(named) => (
+                    &named.local,
+                    Some(
+                        named
+                            .imported
+                            .as_ref()
+                            .map(atom_of_export_name)
+                            .unwrap_or_else(|| named.local.sym.clone()),
+                    ),
+                ),
+                ImportSpecifier::Default(default) => (&default.local, Some("default".into())),
+                ImportSpecifier::Namespace(namespace) => (&namespace.local, None),
+            };
+            self.collector
+                .add_import_binding(&import.src.value, local.sym.clone(), imported);
+        }
+    }
+    fn scan_module_decl(&mut self, decl: &ModuleDecl) {
+        let span = decl.span();
+        match decl {
+            ModuleDecl::Import(import) => {
+                self.collector.import_declaration(
+                    &import.src.value,
+                    span.real_lo(),
+                    span.real_hi(),
+                );
+            }
+            ModuleDecl::ExportDecl(export) => {
+                let names = match &export.decl {
+                    Decl::Var(var) => find_pat_ids::<_, Id>(&var.decls)
+                        .into_iter()
+                        .map(|id| id.0)
+                        .collect(),
+                    Decl::Fn(function) => vec![function.ident.sym.clone()],
+                    Decl::Class(class) => vec![class.ident.sym.clone()],
+                    _ => vec![],
+                };
+                self.collector.export_declaration(
+                    span.real_lo(),
+                    export.decl.span().real_lo(),
+                    names,
+                );
+                export.decl.visit_with(self);
+            }
+            ModuleDecl::ExportDefaultDecl(export) => {
+                let ident = match &export.decl {
+                    DefaultDecl::Fn(function) => function.ident.as_ref(),
+                    DefaultDecl::Class(class) => class.ident.as_ref(),
+                    DefaultDecl::TsInterfaceDecl(_) => None,
+                };
+                let decl_span = export.decl.span();
+                let decl_start = decl_span.real_lo();
+                match ident {
+                    Some(ident) => self.collector.export_default_declaration(
+                        span.real_lo(),
+                        decl_start,
+                        ident.sym.clone(),
+                    ),
+                    None => self.collector.export_default_anonymous_declaration(
+                        span.real_lo(),
+                        decl_start,
+                        &self.source[decl_start as usize..decl_span.real_hi() as usize],
+                    ),
+                }
+                export.decl.visit_with(self);
+            }
+            ModuleDecl::ExportDefaultExpr(export) => {
+                self.collector
+                    .export_default_expression(span.real_lo(), export.expr.span().real_lo());
+                export.expr.visit_with(self);
+            }
+            ModuleDecl::ExportNamed(export) => {
+                let specifiers = export
+                    .specifiers
+                    .iter()
+                    .filter_map(|specifier| match specifier {
+                        ExportSpecifier::Named(named) => {
+                            let orig = atom_of_export_name(&named.orig);
+                            let exported = named
+                                .exported
+                                .as_ref()
+                                .map(atom_of_export_name)
+                                .unwrap_or_else(|| orig.clone());
+                            Some((Some(orig), exported))
+                        }
+                        ExportSpecifier::Namespace(namespace) => {
+                            Some((None, atom_of_export_name(&namespace.name)))
+                        }
+                        // `export v from 'mod'` is only a proposal
+                        ExportSpecifier::Default(_) => None,
+                    });
+                match &export.src {
+                    Some(src) => self.collector.export_from(
+                        &src.value,
+                        span.real_lo(),
+                        span.real_hi(),
+                        specifiers.collect(),
+                    ),
+                    None => self.collector.export_specifiers(
+                        span.real_lo(),
+                        span.real_hi(),
+                        specifiers
+                            .filter_map(|(local, exported)| Some((local?, exported)))
+                            .collect(),
+                    ),
                 }
             }
+            ModuleDecl::ExportAll(export) => {
+                self.collector
+                    .export_all(&export.src.value, span.real_lo(), span.real_hi());
+            }
+            _ => {}
         }
-        swc_ecma_ast::Program::Script(_) => {}
-    };
-    //mem::forget(program);
-    Ok(ParseResult {
-        module_dependencies,
-        presentational_dependencies,
-    })
+    }
+    fn is_import_binding(&self, ident: &Ident) -> bool {
+        ident.ctxt == self.top_level_ctxt && self.collector.is_import_binding(&ident.sym)
+    }
+    fn add_reference(&mut self, ident: &Ident, span: Span, kind: ImportReferenceKind) {
+        self.collector
+            .import_reference(&ident.sym, span.real_lo(), span.real_hi(), kind);
+    }
+    /// name of a global identifier, `import.meta` or a member expression on them,
+    /// e.g. `process.env.NODE_ENV`
+    fn global_name(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Ident(ident) if ident.ctxt == self.unresolved_ctxt => Some(ident.sym.to_string()),
+            Expr::MetaProp(meta) if meta.kind == MetaPropKind::ImportMeta => {
+                Some("import.meta".to_string())
+            }
+            Expr::Member(member) => {
+                let prop = match &member.prop {
+                    MemberProp::Ident(ident) => &ident.sym,
+                    MemberProp::Computed(ComputedPropName { expr, .. }) => match &**expr {
+                        Expr::Lit(Lit::Str(str)) => &str.value,
+                        _ => return None,
+                    },
+                    MemberProp::PrivateName(_) => return None,
+                };
+                Some(format!("{}.{prop}", self.global_name(&member.obj)?))
+            }
+            _ => None,
+        }
+    }
+    /// the request of `new URL("./a.png", import.meta.url)`
+    fn new_url_request<'b>(&self, new: &'b NewExpr) -> Option<&'b Atom> {
+        match &*new.callee {
+            Expr::Ident(ident) if ident.sym == "URL" && ident.ctxt == self.unresolved_ctxt => {}
+            _ => return None,
+        }
+        let [request, base] = new.args.as_deref()? else {
+            return None;
+        };
+        if request.spread.is_some()
+            || base.spread.is_some()
+            || self.global_name(&base.expr)? != "import.meta.url"
+        {
+            return None;
+        }
+        match &*request.expr {
+            Expr::Lit(Lit::Str(request)) => Some(&request.value),
+            _ => None,
+        }
+    }
+    /// the `new URL()` and its request of `new Worker(new URL("./worker.js", import.meta.url))`
+    fn new_worker_url<'b>(&self, new: &'b NewExpr) -> Option<(&'b NewExpr, &'b Atom)> {
+        match &*new.callee {
+            Expr::Ident(ident)
+                if (ident.sym == "Worker" || ident.sym == "SharedWorker")
+                    && ident.ctxt == self.unresolved_ctxt => {}
+            _ => return None,
+        }
+        let argument = new.args.as_ref()?.first()?;
+        match &*argument.expr {
+            Expr::New(url) if argument.spread.is_none() => Some((url, self.new_url_request(url)?)),
+            _ => None,
+        }
+    }
+    /// replace the expression if it is defined or is `import.meta`, returns whether it is replaced
+    fn replace_define(&mut self, expr: &Expr) -> bool {
+        let (arg, is_typeof) = match expr {
+            Expr::Unary(UnaryExpr {
+                op: UnaryOp::TypeOf,
+                arg,
+                ..
+            }) => (&**arg, true),
+            _ => (expr, false),
+        };
+        if self.define.is_empty() && !is_import_meta(arg) {
+            return false;
+        }
+        let Some(name) = self.global_name(arg) else {
+            return false;
+        };
+        let span = expr.span();
+        let replacement = if is_typeof {
+            self.define
+                .type_of(&name)
+                .or_else(|| self.import_meta.type_of(&name))
+        } else {
+            self.define.expression(&name).or_else(|| {
+                self.import_meta
+                    .expression(&name, span.real_lo(), span.real_hi())
+            })
+        };
+        let Some(replacement) = replacement else {
+            return false;
+        };
+        self.collector
+            .replace(span.real_lo(), span.real_hi(), &replacement);
+        true
+    }
+    /// the value of the expression if it only consists of literals and `define`s
+    fn evaluate(&self, expr: &Expr) -> Option<ConstValue> {
+        match expr {
+            Expr::Paren(paren) => self.evaluate(&paren.expr),
+            Expr::Lit(Lit::Str(str)) => Some(ConstValue::String(str.value.to_string())),
+            Expr::Lit(Lit::Bool(bool)) => Some(ConstValue::Bool(bool.value)),
+            Expr::Lit(Lit::Num(num)) => Some(ConstValue::Number(num.value)),
+            Expr::Lit(Lit::Null(_)) => Some(ConstValue::Null),
+            Expr::Unary(UnaryExpr {
+                op: UnaryOp::Bang,
+                arg,
+                ..
+            }) => Some(ConstValue::Bool(!self.evaluate(arg)?.is_truthy())),
+            Expr::Unary(UnaryExpr {
+                op: UnaryOp::TypeOf,
+                arg,
+                ..
+            }) => match self.global_name(arg) {
+                Some(name) => self.define.type_of_value(&name),
+                None => Some(ConstValue::String(self.evaluate(arg)?.type_of().into())),
+            },
+            Expr::Bin(BinExpr {
+                op, left, right, ..
+            }) => {
+                let left = self.evaluate(left)?;
+                match op {
+                    BinaryOp::LogicalAnd | BinaryOp::LogicalOr | BinaryOp::NullishCoalescing => {
+                        if left.short_circuits(op.as_str()) {
+                            Some(left)
+                        } else {
+                            self.evaluate(right)
+                        }
+                    }
+                    BinaryOp::EqEqEq => {
+                        Some(ConstValue::Bool(left.strict_equals(&self.evaluate(right)?)))
+                    }
+                    BinaryOp::NotEqEq => Some(ConstValue::Bool(
+                        !left.strict_equals(&self.evaluate(right)?),
+                    )),
+                    BinaryOp::EqEq => {
+                        Some(ConstValue::Bool(left.loose_equals(&self.evaluate(right)?)?))
+                    }
+                    BinaryOp::NotEq => Some(ConstValue::Bool(
+                        !left.loose_equals(&self.evaluate(right)?)?,
+                    )),
+                    _ => None,
+                }
+            }
+            _ => self.define.value(&self.global_name(expr)?),
+        }
+    }
+    /// replace the test of a constant condition with `true` or `false`, returns its truthiness
+    fn replace_condition(&mut self, test: &Expr) -> Option<bool> {
+        let is_truthy = self.evaluate(test)?.is_truthy();
+        let span = test.span();
+        self.collector
+            .replace(span.real_lo(), span.real_hi(), &is_truthy.to_string());
+        Some(is_truthy)
+    }
+    /// a dead branch of `if` is removed with its dependencies, unless it has hoisted
+    /// declarations which are visible outside of it
+    fn visit_branch(&mut self, branch: &Stmt, is_live: bool) {
+        if is_live || has_hoisted_declarations(branch) {
+            branch.visit_with(self);
+        } else {
+            let span = branch.span();
+            self.collector
+                .dead_code(span.real_lo(), span.real_hi(), "{}");
+        }
+    }
+    /// a dead branch of `?:`, `&&`, `||` or `??` is removed with its dependencies
+    fn visit_expr_branch(&mut self, expr: &Expr, is_live: bool) {
+        if is_live {
+            expr.visit_with(self);
+        } else {
+            let span = expr.span();
+            self.collector
+                .dead_code(span.real_lo(), span.real_hi(), "0");
+        }
+    }
+}
+
+impl Visit for DependencyScanner<'_> {
+    fn visit_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Ident(ident) if self.is_import_binding(ident) => {
+                self.add_reference(ident, ident.span, ImportReferenceKind::Normal)
+            }
+            _ if self.replace_define(expr) => {}
+            _ => expr.visit_children_with(self),
+        }
+    }
+    // constant conditions, e.g. `if (process.env.NODE_ENV !== "production")` with `define`,
+    // similar with webpack's ConstPlugin
+    fn visit_if_stmt(&mut self, if_stmt: &IfStmt) {
+        let Some(is_truthy) = self.replace_condition(&if_stmt.test) else {
+            return if_stmt.visit_children_with(self);
+        };
+        self.visit_branch(&if_stmt.cons, is_truthy);
+        if let Some(alt) = &if_stmt.alt {
+            self.visit_branch(alt, !is_truthy);
+        }
+    }
+    fn visit_cond_expr(&mut self, cond: &CondExpr) {
+        let Some(is_truthy) = self.replace_condition(&cond.test) else {
+            return cond.visit_children_with(self);
+        };
+        self.visit_expr_branch(&cond.cons, is_truthy);
+        self.visit_expr_branch(&cond.alt, !is_truthy);
+    }
+    fn visit_bin_expr(&mut self, bin: &BinExpr) {
+        let short_circuits = matches!(
+            bin.op,
+            BinaryOp::LogicalAnd | BinaryOp::LogicalOr | BinaryOp::NullishCoalescing
+        ) && self
+            .evaluate(&bin.left)
+            .is_some_and(|left| left.short_circuits(bin.op.as_str()));
+        if !short_circuits {
+            return bin.visit_children_with(self);
+        }
+        bin.left.visit_with(self);
+        self.visit_expr_branch(&bin.right, false);
+    }
+    fn visit_function(&mut self, function: &Function) {
+        self.function_depth += 1;
+        function.visit_children_with(self);
+        self.function_depth -= 1;
+    }
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        self.function_depth += 1;
+        arrow.visit_children_with(self);
+        self.function_depth -= 1;
+    }
+    fn visit_await_expr(&mut self, await_expr: &AwaitExpr) {
+        if self.function_depth == 0 {
+            self.collector.top_level_await();
+        }
+        await_expr.visit_children_with(self);
+    }
+    fn visit_for_of_stmt(&mut self, for_of: &ForOfStmt) {
+        if for_of.is_await && self.function_depth == 0 {
+            self.collector.top_level_await();
+        }
+        for_of.visit_children_with(self);
+    }
+    fn visit_new_expr(&mut self, new: &NewExpr) {
+        if let Some((url, request)) = self.new_worker_url(new) {
+            if self
+                .collector
+                .new_worker(request, url.span.real_lo(), url.span.real_hi())
+            {
+                new.args.iter().flatten().skip(1).for_each(|arg| arg.visit_with(self));
+                return;
+            }
+        }
+        if let Some(request) = self.new_url_request(new) {
+            let span = new.span;
+            if self
+                .collector
+                .new_url(request, span.real_lo(), span.real_hi())
+            {
+                return;
+            }
+        }
+        new.visit_children_with(self);
+    }
+    // the argument is an assignment target, which is never replaced by `define`
+    fn visit_update_expr(&mut self, update: &UpdateExpr) {
+        match &*update.arg {
+            Expr::Ident(ident) if self.is_import_binding(ident) => {
+                self.add_reference(ident, ident.span, ImportReferenceKind::Normal)
+            }
+            arg => arg.visit_children_with(self),
+        }
+    }
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            if let Expr::Ident(ident) = &**callee {
+                if self.is_import_binding(ident) {
+                    self.add_reference(ident, ident.span, ImportReferenceKind::Call);
+                    call.args.visit_with(self);
+                    return;
+                }
+            }
+        }
+        call.visit_children_with(self);
+    }
+    fn visit_tagged_tpl(&mut self, tagged_tpl: &TaggedTpl) {
+        match &*tagged_tpl.tag {
+            Expr::Ident(ident) if self.is_import_binding(ident) => {
+                self.add_reference(ident, ident.span, ImportReferenceKind::Call);
+                tagged_tpl.tpl.visit_with(self);
+            }
+            _ => tagged_tpl.visit_children_with(self),
+        }
+    }
+    fn visit_prop(&mut self, prop: &Prop) {
+        match prop {
+            Prop::Shorthand(ident) if self.is_import_binding(ident) => {
+                self.add_reference(ident, ident.span, ImportReferenceKind::Shorthand)
+            }
+            Prop::Shorthand(ident) if ident.ctxt == self.unresolved_ctxt => {
+                if let Some(replacement) = self.define.expression(&ident.sym) {
+                    self.collector.replace(
+                        ident.span.real_lo(),
+                        ident.span.real_hi(),
+                        &format!("{}: {replacement}", ident.sym),
+                    );
+                }
+            }
+            _ => prop.visit_children_with(self),
+        }
+    }
+    // assignment targets, e.g. `a = 1` and `[a] = []`, nested declarations never have the
+    // syntax context of top level bindings
+    fn visit_binding_ident(&mut self, ident: &BindingIdent) {
+        if self.is_import_binding(&ident.id) {
+            self.add_reference(&ident.id, ident.id.span, ImportReferenceKind::Normal);
+        }
+    }
+    fn visit_assign_pat_prop(&mut self, prop: &AssignPatProp) {
+        if self.is_import_binding(&prop.key.id) {
+            self.add_reference(
+                &prop.key.id,
+                prop.key.id.span,
+                ImportReferenceKind::Shorthand,
+            );
+        }
+        prop.value.visit_with(self);
+    }
+}
+
+/// collects the references to top level bindings and all the used names of an esm module
+struct TopLevelScanner<'a> {
+    collector: &'a mut DependencyCollector,
+    top_level_ctxt: SyntaxContext,
+    unresolved_ctxt: SyntaxContext,
+}
+
+impl TopLevelScanner<'_> {
+    fn add_ident(&mut self, ident: &Ident, shorthand: bool) {
+        // references to imports are always replaced, so their names are not used
+        if ident.ctxt == self.top_level_ctxt && self.collector.is_import_binding(&ident.sym) {
+            return;
+        }
+        self.collector.used_name(&ident.sym);
+        if ident.ctxt == self.top_level_ctxt {
+            self.collector.top_level_reference(
+                &ident.sym,
+                ident.span.real_lo(),
+                ident.span.real_hi(),
+                shorthand,
+            );
+        }
+    }
+}
+
+impl Visit for TopLevelScanner<'_> {
+    // bindings of imports are replaced by the import references
+    fn visit_import_decl(&mut self, _import: &ImportDecl) {}
+    // `export { a }` and `export ... from` are removed
+    fn visit_named_export(&mut self, _export: &NamedExport) {}
+    fn visit_export_all(&mut self, _export: &ExportAll) {}
+    // dead branches are removed
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        let span = stmt.span();
+        if !self.collector.is_dead_code(span.real_lo(), span.real_hi()) {
+            stmt.visit_children_with(self);
+        }
+    }
+    fn visit_expr(&mut self, expr: &Expr) {
+        let span = expr.span();
+        if !self.collector.is_dead_code(span.real_lo(), span.real_hi()) {
+            expr.visit_children_with(self);
+        }
+    }
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.add_ident(ident, false);
+    }
+    fn visit_prop(&mut self, prop: &Prop) {
+        match prop {
+            Prop::Shorthand(ident) => self.add_ident(ident, true),
+            _ => prop.visit_children_with(self),
+        }
+    }
+    fn visit_assign_pat_prop(&mut self, prop: &AssignPatProp) {
+        self.add_ident(&prop.key.id, true);
+        prop.value.visit_with(self);
+    }
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            if matches!(&**callee, Expr::Ident(ident)
+                if ident.sym == "eval" && ident.ctxt == self.unresolved_ctxt)
+            {
+                self.collector.module_concatenation_bailout("eval()");
+            }
+        }
+        call.visit_children_with(self);
+    }
+    // labels and jsx element names are not bindings
+    fn visit_labeled_stmt(&mut self, labeled: &LabeledStmt) {
+        labeled.body.visit_with(self);
+    }
+    fn visit_break_stmt(&mut self, _break_stmt: &BreakStmt) {}
+    fn visit_continue_stmt(&mut self, _continue_stmt: &ContinueStmt) {}
+    fn visit_jsx_element_name(&mut self, _name: &JSXElementName) {}
+}
+
+/// pick the syntax of a module by its extension
+pub fn syntax_by_path(resource_path: &Utf8Path) -> Syntax {
+    match resource_path.extension() {
+        Some("ts" | "mts" | "cts") => Syntax::Typescript(TsSyntax {
+            tsx: false,
+            ..Default::default()
+        }),
+        Some("tsx") => Syntax::Typescript(TsSyntax {
+            tsx: true,
+            ..Default::default()
+        }),
+        _ => Syntax::Es(EsSyntax {
+            jsx: true,
+            ..Default::default()
+        }),
+    }
+}
+
+pub(crate) fn parse_program(
+    fm: &SourceFile,
+    syntax: Syntax,
+    comments: Option<&dyn Comments>,
+) -> Result<Program> {
+    let lexer = swc_core::ecma::parser::lexer::Lexer::new(
+        syntax,
+        Default::default(),
+        StringInput::from(fm),
+        comments,
+    );
+
+    let mut parser = Parser::new_from(lexer);
+
+    let result = parser.parse_program();
+    // recoverable errors don't stop the parser, but the program is still invalid
+    let mut errors = parser.take_errors();
+    match result {
+        Ok(program) if errors.is_empty() => return Ok(program),
+        Ok(_) => {}
+        Err(err) => errors.push(err),
+    }
+    errors.sort_by_key(|error| error.span().lo);
+    errors.dedup_by(|a, b| a.span() == b.span() && a.kind().msg() == b.kind().msg());
+    let labels = errors
+        .iter()
+        .map(|error| {
+            let span = error.span();
+            let start = (span.lo - fm.start_pos).0 as usize;
+            let len = (span.hi - span.lo).0 as usize;
+            LabeledSpan::new(Some(error.kind().msg().to_string()), start, len)
+        })
+        .collect::<Vec<_>>();
+    Err(JsParseError {
+        source_code: NamedSource::new(fm.name.to_string(), fm.src.to_string()),
+        message: errors[0].kind().msg().to_string(),
+        labels,
+    }
+    .into())
 }
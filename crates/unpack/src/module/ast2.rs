@@ -1,36 +1,780 @@
+use camino::Utf8Path;
+use miette::{LabeledSpan, NamedSource};
 use oxc_allocator::Allocator;
 use oxc_ast::ast::*;
+use oxc_ast::visit::walk;
+use oxc_ast::Visit;
 use oxc_parser::Parser;
-use oxc_span::SourceType;
+use oxc_semantic::{ScopeFlags, ScopeId, SemanticBuilder, SymbolId, SymbolTable};
+use oxc_span::{GetSpan, SourceType, Span};
+use rustc_hash::FxHashSet;
+use swc_core::atoms::Atom as SwcAtom;
 
+use super::const_value::ConstValue;
+use super::define::Define;
+use super::dependency_collector::DependencyCollector;
+use super::import_meta::ImportMeta;
 use super::ParseResult;
-use crate::dependency::{
-    BoxDependency, BoxDependencyTemplate, ConstDependency, HarmonyImportSideEffectDependency,
-};
+use crate::compiler::CompilerOptions;
+use crate::dependency::ImportReferenceKind;
+use crate::errors::JsParseError;
 use miette::Result;
 
-pub fn parse2(source: String) -> Result<ParseResult> {
+/// same as [super::ast::parse] but powered by oxc, both should produce identical [ParseResult]
+pub fn parse2(
+    source: String,
+    resource_path: &Utf8Path,
+    options: &CompilerOptions,
+) -> Result<ParseResult> {
     let allocator = Allocator::default();
-    let source_type = SourceType::from_path("a.jsx").unwrap();
-
-    let ret = Parser::new(&allocator, source.as_ref(), source_type).parse();
-    let mut presentational_dependencies: Vec<BoxDependencyTemplate> = vec![];
-    let mut module_dependencies: Vec<BoxDependency> = vec![];
-    for stmt in ret.program.body {
-        if let Statement::ImportDeclaration(import) = stmt {
-            let request = import.source.value.to_string();
-            module_dependencies.push(Box::new(HarmonyImportSideEffectDependency {
-                request: request.into(),
-            }));
-            presentational_dependencies.push(Box::new(ConstDependency {
-                start: import.span.start,
-                end: import.span.end,
-                content: "".into(),
-            }))
+    // transformed modules (e.g. typescript) are always plain javascript at this point
+    let source_type = SourceType::unambiguous().with_jsx(true);
+
+    let mut ret = Parser::new(&allocator, source.as_ref(), source_type).parse();
+    if ret.panicked || !ret.errors.is_empty() {
+        // top level await without any import or export fails as script, retry as esm
+        let module = Parser::new(&allocator, source.as_ref(), source_type.with_module(true)).parse();
+        if !module.panicked && module.errors.is_empty() {
+            ret = module;
+        }
+    }
+    if ret.panicked || !ret.errors.is_empty() {
+        let mut errors = ret.errors;
+        errors.sort_by_key(|error| {
+            error
+                .labels
+                .iter()
+                .flatten()
+                .map(|label| label.offset())
+                .min()
+        });
+        let mut labels = errors
+            .iter()
+            .flat_map(|error| {
+                let message = error.message.to_string();
+                error
+                    .labels
+                    .iter()
+                    .flatten()
+                    .map(move |label| {
+                        LabeledSpan::new(Some(message.clone()), label.offset(), label.len())
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        labels.sort_by(|a, b| {
+            (a.offset(), a.len(), a.label()).cmp(&(b.offset(), b.len(), b.label()))
+        });
+        labels.dedup_by(|a, b| a.inner() == b.inner() && a.label() == b.label());
+        let message = errors.first().map_or_else(
+            || "unexpected end of input".to_string(),
+            |error| error.message.to_string(),
+        );
+        return Err(JsParseError {
+            source_code: NamedSource::new(resource_path.as_str(), source.clone()),
+            labels,
+            message,
+        }
+        .into());
+    }
+    let program = ret.program;
+    let define = Define::new(&options.define);
+    // references are only resolved for esm, which has import bindings and may be concatenated,
+    // or when there is any definition or `new URL()`, the semantic builder also fills the
+    // symbol ids of bindings
+    let (symbols, scopes) = (program.source_type.is_module()
+        || !define.is_empty()
+        || source.contains("URL"))
+    .then(|| {
+        let semantic = SemanticBuilder::new(&source).build(&program).semantic;
+        semantic.into_symbol_table_and_scope_tree()
+    })
+    .unzip();
+    let mut scanner = DependencyScanner {
+        collector: DependencyCollector::default(),
+        import_symbols: Default::default(),
+        symbols,
+        source: &source,
+        define,
+        import_meta: ImportMeta::new(resource_path, &source, options),
+        function_depth: 0,
+    };
+    scanner.add_import_bindings(&program);
+    scanner.scan(&program);
+    // only esm modules can be concatenated, which renames their top level bindings
+    if let (true, Some(symbols), Some(scopes)) =
+        (scanner.collector.is_harmony(), &scanner.symbols, &scopes)
+    {
+        TopLevelScanner {
+            collector: &mut scanner.collector,
+            import_symbols: &scanner.import_symbols,
+            symbols,
+            root_scope_id: scopes.root_scope_id(),
         }
+        .visit_program(&program);
     }
     Ok(ParseResult {
-        presentational_dependencies,
-        module_dependencies,
+        warnings: scanner.import_meta.warnings,
+        ..scanner.collector.finish()
     })
 }
+
+fn swc_atom(name: &str) -> SwcAtom {
+    SwcAtom::from(name)
+}
+
+fn binding_names(pattern: &BindingPattern, names: &mut Vec<SwcAtom>) {
+    match &pattern.kind {
+        BindingPatternKind::BindingIdentifier(ident) => names.push(swc_atom(&ident.name)),
+        BindingPatternKind::ObjectPattern(object) => {
+            for property in &object.properties {
+                binding_names(&property.value, names);
+            }
+            if let Some(rest) = &object.rest {
+                binding_names(&rest.argument, names);
+            }
+        }
+        BindingPatternKind::ArrayPattern(array) => {
+            for element in array.elements.iter().flatten() {
+                binding_names(element, names);
+            }
+            if let Some(rest) = &array.rest {
+                binding_names(&rest.argument, names);
+            }
+        }
+        BindingPatternKind::AssignmentPattern(assignment) => binding_names(&assignment.left, names),
+    }
+}
+
+struct DependencyScanner<'a> {
+    collector: DependencyCollector,
+    import_symbols: FxHashSet<SymbolId>,
+    symbols: Option<SymbolTable>,
+    source: &'a str,
+    define: Define<'a>,
+    import_meta: ImportMeta<'a>,
+    /// depth of functions, `await` is only top level at 0
+    function_depth: u32,
+}
+
+fn is_import_meta_property(meta: &MetaProperty) -> bool {
+    meta.meta.name == "import" && meta.property.name == "meta"
+}
+
+/// whether the expression is `import.meta` or a member expression on it
+fn is_import_meta(expr: &Expression) -> bool {
+    match expr {
+        Expression::MetaProperty(meta) => is_import_meta_property(meta),
+        Expression::StaticMemberExpression(member) => is_import_meta(&member.object),
+        Expression::ComputedMemberExpression(member) => is_import_meta(&member.object),
+        _ => false,
+    }
+}
+
+/// finds `var` and function declarations, which are hoisted out of blocks
+#[derive(Default)]
+struct HoistedDeclarations {
+    found: bool,
+}
+
+impl<'a> Visit<'a> for HoistedDeclarations {
+    fn visit_variable_declaration(&mut self, variable: &VariableDeclaration<'a>) {
+        if variable.kind.is_var() {
+            self.found = true;
+        }
+    }
+    // declarations in functions and classes are not hoisted out of them
+    fn visit_function(&mut self, function: &Function<'a>, _flags: ScopeFlags) {
+        if function.is_declaration() {
+            self.found = true;
+        }
+    }
+    fn visit_arrow_function_expression(&mut self, _arrow: &ArrowFunctionExpression<'a>) {}
+    fn visit_class(&mut self, _class: &Class<'a>) {}
+}
+
+fn has_hoisted_declarations(stmt: &Statement) -> bool {
+    let mut hoisted = HoistedDeclarations::default();
+    hoisted.visit_statement(stmt);
+    hoisted.found
+}
+
+impl DependencyScanner<'_> {
+    // imports are hoisted, collect their bindings before visiting any reference
+    fn add_import_bindings(&mut self, program: &Program) {
+        for stmt in &program.body {
+            let Statement::ImportDeclaration(import) = stmt else {
+                continue;
+            };
+            let request = swc_atom(&import.source.value);
+            for specifier in import.specifiers.iter().flatten() {
+                let (local, imported) = match specifier {
+                    ImportDeclarationSpecifier::ImportSpecifier(specifier) => {
+                        (&specifier.local, Some(swc_atom(&specifier.imported.name())))
+                    }
+                    ImportDeclarationSpecifier::ImportDefaultSpecifier(specifier) => {
+                        (&specifier.local, Some("default".into()))
+                    }
+                    ImportDeclarationSpecifier::ImportNamespaceSpecifier(specifier) => {
+                        (&specifier.local, None)
+                    }
+                };
+                if let Some(symbol_id) = local.symbol_id.get() {
+                    self.import_symbols.insert(symbol_id);
+                }
+                self.collector
+                    .add_import_binding(&request, swc_atom(&local.name), imported);
+            }
+        }
+    }
+    fn scan(&mut self, program: &Program) {
+        for stmt in &program.body {
+            match stmt {
+                Statement::ImportDeclaration(import) => {
+                    self.collector.import_declaration(
+                        &swc_atom(&import.source.value),
+                        import.span.start,
+                        import.span.end,
+                    );
+                }
+                Statement::ExportNamedDeclaration(export) => self.scan_export_named(export),
+                Statement::ExportDefaultDeclaration(export) => {
+                    // `None` for expressions, `Some(None)` for anonymous declarations
+                    let ident = match &export.declaration {
+                        ExportDefaultDeclarationKind::FunctionDeclaration(function) => {
+                            Some(function.id.as_ref())
+                        }
+                        ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                            Some(class.id.as_ref())
+                        }
+                        _ => None,
+                    };
+                    let declaration_span = export.declaration.span();
+                    match ident {
+                        Some(Some(ident)) => self.collector.export_default_declaration(
+                            export.span.start,
+                            declaration_span.start,
+                            swc_atom(&ident.name),
+                        ),
+                        Some(None) => self.collector.export_default_anonymous_declaration(
+                            export.span.start,
+                            declaration_span.start,
+                            declaration_span.source_text(self.source),
+                        ),
+                        None => self.collector.export_default_expression(
+                            export.span.start,
+                            declaration_span.start,
+                        ),
+                    }
+                    self.visit_export_default_declaration_kind(&export.declaration);
+                }
+                Statement::ExportAllDeclaration(export) => {
+                    let request = swc_atom(&export.source.value);
+                    match &export.exported {
+                        Some(exported) => self.collector.export_from(
+                            &request,
+                            export.span.start,
+                            export.span.end,
+                            vec![(None, swc_atom(&exported.name()))],
+                        ),
+                        None => {
+                            self.collector
+                                .export_all(&request, export.span.start, export.span.end)
+                        }
+                    }
+                }
+                _ => self.visit_statement(stmt),
+            }
+        }
+    }
+    fn scan_export_named(&mut self, export: &ExportNamedDeclaration) {
+        if let Some(declaration) = &export.declaration {
+            let mut names = vec![];
+            match declaration {
+                Declaration::VariableDeclaration(variable) => {
+                    for declarator in &variable.declarations {
+                        binding_names(&declarator.id, &mut names);
+                    }
+                }
+                Declaration::FunctionDeclaration(function) => {
+                    names.extend(function.id.as_ref().map(|id| swc_atom(&id.name)))
+                }
+                Declaration::ClassDeclaration(class) => {
+                    names.extend(class.id.as_ref().map(|id| swc_atom(&id.name)))
+                }
+                _ => {}
+            }
+            self.collector
+                .export_declaration(export.span.start, declaration.span().start, names);
+            self.visit_declaration(declaration);
+            return;
+        }
+        let specifiers = export.specifiers.iter().map(|specifier| {
+            (
+                swc_atom(&specifier.local.name()),
+                swc_atom(&specifier.exported.name()),
+            )
+        });
+        match &export.source {
+            Some(source) => self.collector.export_from(
+                &swc_atom(&source.value),
+                export.span.start,
+                export.span.end,
+                specifiers
+                    .map(|(local, exported)| (Some(local), exported))
+                    .collect(),
+            ),
+            None => self.collector.export_specifiers(
+                export.span.start,
+                export.span.end,
+                specifiers.collect(),
+            ),
+        }
+    }
+    fn is_import_binding(&self, ident: &IdentifierReference) -> bool {
+        let Some(symbols) = &self.symbols else {
+            return false;
+        };
+        ident
+            .reference_id
+            .get()
+            .and_then(|reference_id| symbols.get_reference(reference_id).symbol_id())
+            .is_some_and(|symbol_id| self.import_symbols.contains(&symbol_id))
+    }
+    fn add_reference(
+        &mut self,
+        ident: &IdentifierReference,
+        span: Span,
+        kind: ImportReferenceKind,
+    ) {
+        self.collector
+            .import_reference(&swc_atom(&ident.name), span.start, span.end, kind);
+    }
+    fn is_unresolved(&self, ident: &IdentifierReference) -> bool {
+        let Some(symbols) = &self.symbols else {
+            return false;
+        };
+        ident
+            .reference_id
+            .get()
+            .is_some_and(|reference_id| symbols.get_reference(reference_id).symbol_id().is_none())
+    }
+    /// name of a global identifier, `import.meta` or a member expression on them,
+    /// e.g. `process.env.NODE_ENV`
+    fn global_name(&self, expr: &Expression) -> Option<String> {
+        match expr {
+            Expression::Identifier(ident) if self.is_unresolved(ident) => {
+                Some(ident.name.to_string())
+            }
+            Expression::MetaProperty(meta) if is_import_meta_property(meta) => {
+                Some("import.meta".to_string())
+            }
+            Expression::StaticMemberExpression(member) if !member.optional => Some(format!(
+                "{}.{}",
+                self.global_name(&member.object)?,
+                member.property.name
+            )),
+            Expression::ComputedMemberExpression(member) if !member.optional => {
+                match &member.expression {
+                    Expression::StringLiteral(str) => Some(format!(
+                        "{}.{}",
+                        self.global_name(&member.object)?,
+                        str.value
+                    )),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+    /// the request of `new URL("./a.png", import.meta.url)`
+    fn new_url_request<'b>(&self, new: &'b NewExpression) -> Option<&'b str> {
+        match &new.callee {
+            Expression::Identifier(ident) if ident.name == "URL" && self.is_unresolved(ident) => {}
+            _ => return None,
+        }
+        let [Argument::StringLiteral(request), base] = new.arguments.as_slice() else {
+            return None;
+        };
+        if self.global_name(base.as_expression()?)? != "import.meta.url" {
+            return None;
+        }
+        Some(&request.value)
+    }
+    /// the `new URL()` and its request of `new Worker(new URL("./worker.js", import.meta.url))`
+    fn new_worker_url<'b>(
+        &self,
+        new: &'b NewExpression<'b>,
+    ) -> Option<(&'b NewExpression<'b>, &'b str)> {
+        match &new.callee {
+            Expression::Identifier(ident)
+                if (ident.name == "Worker" || ident.name == "SharedWorker")
+                    && self.is_unresolved(ident) => {}
+            _ => return None,
+        }
+        match new.arguments.first()? {
+            Argument::NewExpression(url) => Some((url, self.new_url_request(url)?)),
+            _ => None,
+        }
+    }
+    /// replace the expression if it is defined or is `import.meta`, returns whether it is replaced
+    fn replace_define(&mut self, expr: &Expression) -> bool {
+        let (arg, is_typeof) = match expr {
+            Expression::UnaryExpression(unary) if unary.operator == UnaryOperator::Typeof => {
+                (&unary.argument, true)
+            }
+            _ => (expr, false),
+        };
+        if self.define.is_empty() && !is_import_meta(arg) {
+            return false;
+        }
+        let Some(name) = self.global_name(arg) else {
+            return false;
+        };
+        let span = expr.span();
+        let replacement = if is_typeof {
+            self.define
+                .type_of(&name)
+                .or_else(|| self.import_meta.type_of(&name))
+        } else {
+            self.define
+                .expression(&name)
+                .or_else(|| self.import_meta.expression(&name, span.start, span.end))
+        };
+        let Some(replacement) = replacement else {
+            return false;
+        };
+        self.collector.replace(span.start, span.end, &replacement);
+        true
+    }
+    /// the value of the expression if it only consists of literals and `define`s
+    fn evaluate(&self, expr: &Expression) -> Option<ConstValue> {
+        match expr {
+            Expression::ParenthesizedExpression(paren) => self.evaluate(&paren.expression),
+            Expression::StringLiteral(str) => Some(ConstValue::String(str.value.to_string())),
+            Expression::BooleanLiteral(bool) => Some(ConstValue::Bool(bool.value)),
+            Expression::NumericLiteral(num) => Some(ConstValue::Number(num.value)),
+            Expression::NullLiteral(_) => Some(ConstValue::Null),
+            Expression::UnaryExpression(unary) => match unary.operator {
+                UnaryOperator::LogicalNot => Some(ConstValue::Bool(
+                    !self.evaluate(&unary.argument)?.is_truthy(),
+                )),
+                UnaryOperator::Typeof => match self.global_name(&unary.argument) {
+                    Some(name) => self.define.type_of_value(&name),
+                    None => Some(ConstValue::String(
+                        self.evaluate(&unary.argument)?.type_of().into(),
+                    )),
+                },
+                _ => None,
+            },
+            Expression::LogicalExpression(logical) => {
+                let left = self.evaluate(&logical.left)?;
+                if left.short_circuits(logical.operator.as_str()) {
+                    Some(left)
+                } else {
+                    self.evaluate(&logical.right)
+                }
+            }
+            Expression::BinaryExpression(binary) => {
+                let left = self.evaluate(&binary.left)?;
+                let right = self.evaluate(&binary.right)?;
+                match binary.operator {
+                    BinaryOperator::StrictEquality => {
+                        Some(ConstValue::Bool(left.strict_equals(&right)))
+                    }
+                    BinaryOperator::StrictInequality => {
+                        Some(ConstValue::Bool(!left.strict_equals(&right)))
+                    }
+                    BinaryOperator::Equality => Some(ConstValue::Bool(left.loose_equals(&right)?)),
+                    BinaryOperator::Inequality => {
+                        Some(ConstValue::Bool(!left.loose_equals(&right)?))
+                    }
+                    _ => None,
+                }
+            }
+            _ => self.define.value(&self.global_name(expr)?),
+        }
+    }
+    /// replace the test of a constant condition with `true` or `false`, returns its truthiness
+    fn replace_condition(&mut self, test: &Expression) -> Option<bool> {
+        let is_truthy = self.evaluate(test)?.is_truthy();
+        let span = test.span();
+        self.collector
+            .replace(span.start, span.end, &is_truthy.to_string());
+        Some(is_truthy)
+    }
+    /// a dead branch of `if` is removed with its dependencies, unless it has hoisted
+    /// declarations which are visible outside of it
+    fn visit_branch(&mut self, branch: &Statement, is_live: bool) {
+        if is_live || has_hoisted_declarations(branch) {
+            self.visit_statement(branch);
+        } else {
+            let span = branch.span();
+            self.collector.dead_code(span.start, span.end, "{}");
+        }
+    }
+    /// a dead branch of `?:`, `&&`, `||` or `??` is removed with its dependencies
+    fn visit_expression_branch(&mut self, expr: &Expression, is_live: bool) {
+        if is_live {
+            self.visit_expression(expr);
+        } else {
+            let span = expr.span();
+            self.collector.dead_code(span.start, span.end, "0");
+        }
+    }
+}
+
+impl<'a> Visit<'a> for DependencyScanner<'_> {
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        if !self.replace_define(expr) {
+            walk::walk_expression(self, expr);
+        }
+    }
+    // constant conditions, e.g. `if (process.env.NODE_ENV !== "production")` with `define`,
+    // similar with webpack's ConstPlugin
+    fn visit_if_statement(&mut self, if_stmt: &IfStatement<'a>) {
+        let Some(is_truthy) = self.replace_condition(&if_stmt.test) else {
+            return walk::walk_if_statement(self, if_stmt);
+        };
+        self.visit_branch(&if_stmt.consequent, is_truthy);
+        if let Some(alternate) = &if_stmt.alternate {
+            self.visit_branch(alternate, !is_truthy);
+        }
+    }
+    fn visit_conditional_expression(&mut self, cond: &ConditionalExpression<'a>) {
+        let Some(is_truthy) = self.replace_condition(&cond.test) else {
+            return walk::walk_conditional_expression(self, cond);
+        };
+        self.visit_expression_branch(&cond.consequent, is_truthy);
+        self.visit_expression_branch(&cond.alternate, !is_truthy);
+    }
+    fn visit_logical_expression(&mut self, logical: &LogicalExpression<'a>) {
+        let short_circuits = self
+            .evaluate(&logical.left)
+            .is_some_and(|left| left.short_circuits(logical.operator.as_str()));
+        if !short_circuits {
+            return walk::walk_logical_expression(self, logical);
+        }
+        self.visit_expression(&logical.left);
+        self.visit_expression_branch(&logical.right, false);
+    }
+    fn visit_function(&mut self, function: &Function<'a>, flags: ScopeFlags) {
+        self.function_depth += 1;
+        walk::walk_function(self, function, flags);
+        self.function_depth -= 1;
+    }
+    fn visit_arrow_function_expression(&mut self, arrow: &ArrowFunctionExpression<'a>) {
+        self.function_depth += 1;
+        walk::walk_arrow_function_expression(self, arrow);
+        self.function_depth -= 1;
+    }
+    fn visit_await_expression(&mut self, await_expr: &AwaitExpression<'a>) {
+        if self.function_depth == 0 {
+            self.collector.top_level_await();
+        }
+        walk::walk_await_expression(self, await_expr);
+    }
+    fn visit_for_of_statement(&mut self, for_of: &ForOfStatement<'a>) {
+        if for_of.r#await && self.function_depth == 0 {
+            self.collector.top_level_await();
+        }
+        walk::walk_for_of_statement(self, for_of);
+    }
+    fn visit_new_expression(&mut self, new: &NewExpression<'a>) {
+        if let Some((url, request)) = self.new_worker_url(new) {
+            if self
+                .collector
+                .new_worker(&swc_atom(request), url.span.start, url.span.end)
+            {
+                new.arguments
+                    .iter()
+                    .skip(1)
+                    .for_each(|argument| self.visit_argument(argument));
+                return;
+            }
+        }
+        if let Some(request) = self.new_url_request(new) {
+            if self
+                .collector
+                .new_url(&swc_atom(request), new.span.start, new.span.end)
+            {
+                return;
+            }
+        }
+        walk::walk_new_expression(self, new);
+    }
+    // also covers assignment targets, e.g. `a = 1` and `[a] = []`
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        if self.is_import_binding(ident) {
+            self.add_reference(ident, ident.span, ImportReferenceKind::Normal);
+        }
+    }
+    // jsx is compiled before scanning, element names are not references in swc either
+    fn visit_jsx_element_name(&mut self, _name: &JSXElementName<'a>) {}
+    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
+        match &call.callee {
+            Expression::Identifier(ident) if !call.optional && self.is_import_binding(ident) => {
+                self.add_reference(ident, ident.span, ImportReferenceKind::Call);
+                self.visit_arguments(&call.arguments);
+            }
+            _ => walk::walk_call_expression(self, call),
+        }
+    }
+    fn visit_tagged_template_expression(&mut self, tagged: &TaggedTemplateExpression<'a>) {
+        match &tagged.tag {
+            Expression::Identifier(ident) if self.is_import_binding(ident) => {
+                self.add_reference(ident, ident.span, ImportReferenceKind::Call);
+                self.visit_template_literal(&tagged.quasi);
+            }
+            _ => walk::walk_tagged_template_expression(self, tagged),
+        }
+    }
+    fn visit_object_property(&mut self, property: &ObjectProperty<'a>) {
+        match &property.value {
+            Expression::Identifier(ident)
+                if property.shorthand && self.is_import_binding(ident) =>
+            {
+                self.add_reference(ident, ident.span, ImportReferenceKind::Shorthand);
+            }
+            Expression::Identifier(ident) if property.shorthand && self.is_unresolved(ident) => {
+                if let Some(replacement) = self.define.expression(&ident.name) {
+                    self.collector.replace(
+                        ident.span.start,
+                        ident.span.end,
+                        &format!("{}: {replacement}", ident.name),
+                    );
+                }
+            }
+            _ => walk::walk_object_property(self, property),
+        }
+    }
+    fn visit_assignment_target_property_identifier(
+        &mut self,
+        property: &AssignmentTargetPropertyIdentifier<'a>,
+    ) {
+        if self.is_import_binding(&property.binding) {
+            self.add_reference(
+                &property.binding,
+                property.binding.span,
+                ImportReferenceKind::Shorthand,
+            );
+        }
+        if let Some(init) = &property.init {
+            self.visit_expression(init);
+        }
+    }
+}
+
+/// collects the references to top level bindings and all the used names of an esm module
+struct TopLevelScanner<'a> {
+    collector: &'a mut DependencyCollector,
+    import_symbols: &'a FxHashSet<SymbolId>,
+    symbols: &'a SymbolTable,
+    root_scope_id: ScopeId,
+}
+
+impl TopLevelScanner<'_> {
+    fn add(&mut self, name: &str, symbol_id: Option<SymbolId>, span: Span, shorthand: bool) {
+        // references to imports are always replaced, so their names are not used
+        if symbol_id.is_some_and(|symbol_id| self.import_symbols.contains(&symbol_id)) {
+            return;
+        }
+        let name = swc_atom(name);
+        self.collector.used_name(&name);
+        if symbol_id.is_some_and(|symbol_id| self.symbols.get_scope_id(symbol_id) == self.root_scope_id)
+        {
+            self.collector
+                .top_level_reference(&name, span.start, span.end, shorthand);
+        }
+    }
+    fn add_binding(&mut self, ident: &BindingIdentifier, shorthand: bool) {
+        self.add(&ident.name, ident.symbol_id.get(), ident.span, shorthand);
+    }
+    fn add_reference(&mut self, ident: &IdentifierReference, shorthand: bool) {
+        let symbol_id = ident
+            .reference_id
+            .get()
+            .and_then(|reference_id| self.symbols.get_reference(reference_id).symbol_id());
+        self.add(&ident.name, symbol_id, ident.span, shorthand);
+    }
+    fn is_unresolved(&self, ident: &IdentifierReference) -> bool {
+        ident.reference_id.get().is_some_and(|reference_id| {
+            self.symbols.get_reference(reference_id).symbol_id().is_none()
+        })
+    }
+}
+
+impl<'a> Visit<'a> for TopLevelScanner<'_> {
+    // bindings of imports are replaced by the import references
+    fn visit_import_declaration(&mut self, _import: &ImportDeclaration<'a>) {}
+    // `export { a }` and `export ... from` are removed
+    fn visit_export_named_declaration(&mut self, export: &ExportNamedDeclaration<'a>) {
+        if let Some(declaration) = &export.declaration {
+            self.visit_declaration(declaration);
+        }
+    }
+    fn visit_export_all_declaration(&mut self, _export: &ExportAllDeclaration<'a>) {}
+    // dead branches are removed
+    fn visit_statement(&mut self, stmt: &Statement<'a>) {
+        let span = stmt.span();
+        if !self.collector.is_dead_code(span.start, span.end) {
+            walk::walk_statement(self, stmt);
+        }
+    }
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        let span = expr.span();
+        if !self.collector.is_dead_code(span.start, span.end) {
+            walk::walk_expression(self, expr);
+        }
+    }
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        self.add_binding(ident, false);
+    }
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        self.add_reference(ident, false);
+    }
+    fn visit_object_property(&mut self, property: &ObjectProperty<'a>) {
+        match &property.value {
+            Expression::Identifier(ident) if property.shorthand => self.add_reference(ident, true),
+            _ => walk::walk_object_property(self, property),
+        }
+    }
+    fn visit_binding_property(&mut self, property: &BindingProperty<'a>) {
+        if property.shorthand {
+            match &property.value.kind {
+                BindingPatternKind::BindingIdentifier(ident) => {
+                    self.add_binding(ident, true);
+                    return;
+                }
+                BindingPatternKind::AssignmentPattern(pattern) => {
+                    if let BindingPatternKind::BindingIdentifier(ident) = &pattern.left.kind {
+                        self.add_binding(ident, true);
+                        self.visit_expression(&pattern.right);
+                        return;
+                    }
+                }
+                _ => {}
+            }
+        }
+        walk::walk_binding_property(self, property);
+    }
+    fn visit_assignment_target_property_identifier(
+        &mut self,
+        property: &AssignmentTargetPropertyIdentifier<'a>,
+    ) {
+        self.add_reference(&property.binding, true);
+        if let Some(init) = &property.init {
+            self.visit_expression(init);
+        }
+    }
+    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
+        if matches!(&call.callee, Expression::Identifier(ident)
+            if ident.name == "eval" && self.is_unresolved(ident))
+        {
+            self.collector.module_concatenation_bailout("eval()");
+        }
+        walk::walk_call_expression(self, call);
+    }
+    // jsx element names are not bindings in swc either
+    fn visit_jsx_element_name(&mut self, _name: &JSXElementName<'a>) {}
+}
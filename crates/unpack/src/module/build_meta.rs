@@ -0,0 +1,60 @@
+use swc_core::atoms::Atom;
+
+/// how the exports of a module are exposed to its importers, similar with webpack's ExportsType
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportsType {
+    /// esm module, exports are defined on `__unpack_exports__` by getters
+    Namespace,
+    /// commonjs or unknown module, the default export depends on `__esModule` at runtime
+    #[default]
+    Dynamic,
+    /// e.g. json, the default export is `module.exports` and named exports are its properties
+    DefaultWithNamed,
+}
+
+/// url of an `asset` module, used by `url()` of css
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetUrl {
+    /// emitted file, relative to the output path
+    Filename(String),
+    DataUrl(String),
+}
+
+/// information of a module known before it's built, similar with webpack's FactoryMeta
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FactoryMeta {
+    /// flagged by `sideEffects` of package.json, module rules or plugins, the module is
+    /// left out if none of its exports is used
+    pub side_effect_free: bool,
+}
+
+/// information collected while building a module, used by code generation of the module and its importers
+#[derive(Debug, Clone, Default)]
+pub struct BuildMeta {
+    pub exports_type: ExportsType,
+    pub asset_url: Option<AssetUrl>,
+    /// the module and its importers are evaluated asynchronously
+    pub has_top_level_await: bool,
+    /// names of the esm exports except the ones of `export * from`, which are known after linking
+    pub exports: Vec<Atom>,
+    /// exported names and their local bindings, e.g. `export { a as b }` and `export const a = 1`
+    pub local_exports: Vec<(Atom, Atom)>,
+    /// references to the top level bindings of an esm module sorted by offsets, including the
+    /// declarations, the bindings are renamed by them when the module is concatenated
+    pub top_level_references: Vec<TopLevelReference>,
+    /// sorted names of all the identifiers of an esm module, including nested bindings and globals,
+    /// the new names of bindings in concatenated modules must not collide with them
+    pub used_names: Vec<Atom>,
+    /// why the module can't be concatenated, e.g. `eval()` which may access any binding
+    pub module_concatenation_bailout: Option<String>,
+}
+
+/// reference to a top level binding of a module, e.g. `a` in `const a = 1; a + 1`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopLevelReference {
+    pub name: Atom,
+    pub start: u32,
+    pub end: u32,
+    /// `{ a }` in object literals and patterns, which is renamed as `{ a: b }`
+    pub shorthand: bool,
+}
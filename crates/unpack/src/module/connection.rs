@@ -1,13 +1,20 @@
 use super::ModuleId;
+use crate::dependency::DependencyId;
 use index_vec::define_index_type;
 #[derive(Debug, Clone)]
 pub struct Connection {
+    pub dependency_id: DependencyId,
     pub origin_module_id: Option<ModuleId>,
     pub resolved_module_id: ModuleId,
 }
 impl Connection {
-    pub fn new(origin_module_id: Option<ModuleId>, resolved_module_id: ModuleId) -> Self {
+    pub fn new(
+        dependency_id: DependencyId,
+        origin_module_id: Option<ModuleId>,
+        resolved_module_id: ModuleId,
+    ) -> Self {
         Self {
+            dependency_id,
             origin_module_id,
             resolved_module_id,
         }
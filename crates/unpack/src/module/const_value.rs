@@ -0,0 +1,68 @@
+/// value of an expression evaluated at compile time, only primitives of literals and `define`
+/// are evaluated, similar with webpack's `BasicEvaluatedExpression`
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum ConstValue {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl ConstValue {
+    /// the value of code of `define`, `None` unless it is a primitive literal
+    pub fn from_code(code: &str) -> Option<Self> {
+        let code = code.trim();
+        if code == "undefined" {
+            return Some(ConstValue::Undefined);
+        }
+        Some(
+            match serde_json::from_str::<serde_json::Value>(code).ok()? {
+                serde_json::Value::Null => ConstValue::Null,
+                serde_json::Value::Bool(value) => ConstValue::Bool(value),
+                serde_json::Value::Number(value) => ConstValue::Number(value.as_f64()?),
+                serde_json::Value::String(value) => ConstValue::String(value),
+                _ => return None,
+            },
+        )
+    }
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            ConstValue::Undefined | ConstValue::Null => false,
+            ConstValue::Bool(value) => *value,
+            ConstValue::Number(value) => *value != 0.0 && !value.is_nan(),
+            ConstValue::String(value) => !value.is_empty(),
+        }
+    }
+    pub fn is_nullish(&self) -> bool {
+        matches!(self, ConstValue::Undefined | ConstValue::Null)
+    }
+    pub fn type_of(&self) -> &'static str {
+        match self {
+            ConstValue::Undefined => "undefined",
+            ConstValue::Null => "object",
+            ConstValue::Bool(_) => "boolean",
+            ConstValue::Number(_) => "number",
+            ConstValue::String(_) => "string",
+        }
+    }
+    /// `===`
+    pub fn strict_equals(&self, other: &Self) -> bool {
+        self == other
+    }
+    /// `==`, `None` if the values need type conversion
+    pub fn loose_equals(&self, other: &Self) -> Option<bool> {
+        if self.is_nullish() || other.is_nullish() {
+            return Some(self.is_nullish() && other.is_nullish());
+        }
+        (self.type_of() == other.type_of()).then(|| self.strict_equals(other))
+    }
+    /// whether the right side of `&&`, `||` or `??` is skipped with this value on the left side
+    pub fn short_circuits(&self, operator: &str) -> bool {
+        match operator {
+            "&&" => !self.is_truthy(),
+            "||" => self.is_truthy(),
+            _ => !self.is_nullish(),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use indexmap::IndexMap;
+
+use super::const_value::ConstValue;
+use crate::utils::identifier::{is_identifier, quoted};
+
+/// compile time constants of `define`, keys are global identifiers, member expressions
+/// like `process.env.NODE_ENV`, or `typeof` expressions like `typeof window`
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Define<'a> {
+    definitions: &'a IndexMap<String, String>,
+}
+
+/// the code is wrapped in parentheses unless it is an identifier, a member expression or a literal
+fn to_expression(code: &str) -> String {
+    let code = code.trim();
+    let is_simple = code.split('.').all(is_identifier)
+        || serde_json::from_str::<serde_json::Value>(code).is_ok_and(|value| !value.is_object());
+    if is_simple {
+        code.to_string()
+    } else {
+        format!("({code})")
+    }
+}
+
+/// the result of `typeof` the code, if it is a literal
+fn literal_type(code: &str) -> Option<&'static str> {
+    if code == "undefined" {
+        return Some("undefined");
+    }
+    Some(
+        match serde_json::from_str::<serde_json::Value>(code).ok()? {
+            serde_json::Value::String(_) => "string",
+            serde_json::Value::Number(_) => "number",
+            serde_json::Value::Bool(_) => "boolean",
+            _ => "object",
+        },
+    )
+}
+
+impl<'a> Define<'a> {
+    pub fn new(definitions: &'a IndexMap<String, String>) -> Self {
+        Self { definitions }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+    /// the code replacing the expression of `name`, e.g. `process.env.NODE_ENV`
+    pub fn expression(&self, name: &str) -> Option<String> {
+        self.definitions.get(name).map(|code| to_expression(code))
+    }
+    /// the definitions of direct properties of `object`, e.g. `import.meta.env.MODE` of `import.meta.env`
+    pub fn properties(&self, object: &str) -> Vec<(&'a str, String)> {
+        self.definitions
+            .iter()
+            .filter_map(|(key, code)| {
+                let property = key.strip_prefix(object)?.strip_prefix('.')?;
+                is_identifier(property).then(|| (property, to_expression(code)))
+            })
+            .collect()
+    }
+    /// the code replacing `typeof name`, `None` if it can't be evaluated at compile time
+    pub fn type_of(&self, name: &str) -> Option<String> {
+        if let Some(code) = self.definitions.get(&format!("typeof {name}")) {
+            return Some(to_expression(code));
+        }
+        literal_type(self.definitions.get(name)?.trim()).map(quoted)
+    }
+    /// the value of `name` if it is defined as a primitive literal
+    pub fn value(&self, name: &str) -> Option<ConstValue> {
+        ConstValue::from_code(self.definitions.get(name)?)
+    }
+    /// the value of `typeof name` if it can be evaluated at compile time
+    pub fn type_of_value(&self, name: &str) -> Option<ConstValue> {
+        ConstValue::from_code(&self.type_of(name)?)
+    }
+}
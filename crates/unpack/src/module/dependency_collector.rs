@@ -0,0 +1,348 @@
+use rustc_hash::{FxHashMap, FxHashSet};
+use swc_core::atoms::Atom;
+
+use super::{BuildMeta, ExportsType, ParseResult, TopLevelReference};
+use crate::dependency::{
+    AnonymousDeclaration, BoxDependency, BoxDependencyTemplate, ConstDependency,
+    HarmonyCompatibilityDependency, HarmonyExportExpressionDependency,
+    HarmonyExportImportedSpecifierDependency, HarmonyExportSpecifierDependency,
+    HarmonyImportSideEffectDependency, HarmonyImportSpecifierDependency, ImportReferenceKind,
+    UrlDependency, WorkerDependency, DEFAULT_EXPORT,
+};
+
+fn is_file_url(request: &str) -> bool {
+    !request.contains("://") && !request.starts_with("data:")
+}
+
+/// offset in the code of an anonymous function or class declaration where its name goes,
+/// right after `async function*` or `class`, whitespace and comments are skipped
+fn name_offset(declaration: &str) -> usize {
+    let mut offset = 0;
+    let mut name_offset = 0;
+    loop {
+        let rest = &declaration[offset..];
+        let code = rest.trim_start();
+        offset += rest.len() - code.len();
+        if let Some(comment) = code.strip_prefix("//") {
+            offset += 2 + comment.find('\n').unwrap_or(comment.len());
+        } else if let Some(comment) = code.strip_prefix("/*") {
+            offset += 2 + comment.find("*/").map_or(comment.len(), |end| end + 2);
+        } else if let Some(token) = ["async", "function", "class", "*"]
+            .into_iter()
+            .find(|token| code.starts_with(token))
+        {
+            offset += token.len();
+            name_offset = offset;
+        } else {
+            return name_offset;
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ImportBinding {
+    request: Atom,
+    source_order: u32,
+    /// `None` for namespace import
+    imported: Option<Atom>,
+}
+
+/// turns the module declarations and import references found by a parser backend into dependencies,
+/// so that [super::ast::parse] and [super::ast2::parse2] share the same output.
+/// all offsets are 0-based
+#[derive(Debug, Default)]
+pub(super) struct DependencyCollector {
+    module_dependencies: Vec<BoxDependency>,
+    presentational_dependencies: Vec<BoxDependencyTemplate>,
+    /// import references are sorted by their offsets, the traversal order of backends may differ
+    references: Vec<HarmonyImportSpecifierDependency>,
+    source_orders: FxHashMap<Atom, u32>,
+    imports: FxHashMap<Atom, ImportBinding>,
+    /// exported names except the ones of `export *`
+    exports: Vec<Atom>,
+    local_exports: Vec<(Atom, Atom)>,
+    top_level_references: Vec<TopLevelReference>,
+    used_names: FxHashSet<Atom>,
+    /// ranges of removed dead branches
+    dead_code: FxHashSet<(u32, u32)>,
+    module_concatenation_bailout: Option<String>,
+    is_harmony: bool,
+    has_top_level_await: bool,
+}
+
+impl DependencyCollector {
+    fn source_order(&mut self, request: &Atom) -> u32 {
+        let next = self.source_orders.len() as u32;
+        *self.source_orders.entry(request.clone()).or_insert(next)
+    }
+    fn remove(&mut self, start: u32, end: u32, content: &str) {
+        self.presentational_dependencies
+            .push(Box::new(ConstDependency::new(start, end, content.into())));
+    }
+    fn add_side_effect(&mut self, request: &Atom) -> u32 {
+        self.is_harmony = true;
+        let source_order = self.source_order(request);
+        self.module_dependencies
+            .push(Box::new(HarmonyImportSideEffectDependency {
+                request: request.clone(),
+                source_order,
+            }));
+        source_order
+    }
+    fn export_local(&mut self, local: Atom, name: Atom) {
+        self.exports.push(name.clone());
+        match self.imports.get(&local) {
+            // `import { a } from './a'; export { a }`
+            Some(binding) => {
+                self.module_dependencies
+                    .push(Box::new(HarmonyExportImportedSpecifierDependency {
+                        request: binding.request.clone(),
+                        source_order: binding.source_order,
+                        name: Some(name),
+                        id: binding.imported.clone(),
+                    }))
+            }
+            None => {
+                self.local_exports.push((name.clone(), local.clone()));
+                self.presentational_dependencies
+                    .push(Box::new(HarmonyExportSpecifierDependency { name, local }))
+            }
+        }
+    }
+
+    /// the local bindings of imports, must be registered for all imports before any reference,
+    /// as imports are hoisted. `imported` is `None` for namespace import
+    pub fn add_import_binding(&mut self, request: &Atom, local: Atom, imported: Option<Atom>) {
+        let source_order = self.source_order(request);
+        self.imports.insert(
+            local,
+            ImportBinding {
+                request: request.clone(),
+                source_order,
+                imported,
+            },
+        );
+    }
+    pub fn is_import_binding(&self, local: &Atom) -> bool {
+        self.imports.contains_key(local)
+    }
+    /// whether the module is esm, only esm modules are scanned for top level references
+    pub fn is_harmony(&self) -> bool {
+        self.is_harmony
+    }
+    /// `import ... from './a'`
+    pub fn import_declaration(&mut self, request: &Atom, start: u32, end: u32) {
+        self.add_side_effect(request);
+        self.remove(start, end, "");
+    }
+    /// `export const a = 1`, `export function a() {}`, `export class A {}`
+    pub fn export_declaration(&mut self, start: u32, declaration_start: u32, names: Vec<Atom>) {
+        self.is_harmony = true;
+        self.remove(start, declaration_start, "");
+        for name in names {
+            self.export_local(name.clone(), name);
+        }
+    }
+    /// `export default function a() {}` and `export default class A {}`
+    pub fn export_default_declaration(&mut self, start: u32, declaration_start: u32, name: Atom) {
+        self.is_harmony = true;
+        self.remove(start, declaration_start, "");
+        self.export_local(name, "default".into());
+    }
+    /// `export default expression`
+    pub fn export_default_expression(&mut self, start: u32, expression_start: u32) {
+        self.is_harmony = true;
+        self.presentational_dependencies
+            .push(Box::new(HarmonyExportExpressionDependency {
+                start,
+                end: expression_start,
+                declaration: None,
+            }));
+        self.export_local(DEFAULT_EXPORT.into(), "default".into());
+    }
+    /// `export default function () {}` and `export default class {}`, `declaration` is the code
+    /// of the function or class, which is named after the default export binding to keep it
+    /// a hoisted declaration
+    pub fn export_default_anonymous_declaration(
+        &mut self,
+        start: u32,
+        declaration_start: u32,
+        declaration: &str,
+    ) {
+        self.is_harmony = true;
+        self.presentational_dependencies
+            .push(Box::new(HarmonyExportExpressionDependency {
+                start,
+                end: declaration_start,
+                declaration: Some(AnonymousDeclaration {
+                    name_position: declaration_start + name_offset(declaration) as u32,
+                    end: declaration_start + declaration.len() as u32,
+                }),
+            }));
+        self.export_local(DEFAULT_EXPORT.into(), "default".into());
+    }
+    /// `export { local as exported }`
+    pub fn export_specifiers(&mut self, start: u32, end: u32, specifiers: Vec<(Atom, Atom)>) {
+        self.is_harmony = true;
+        self.remove(start, end, "");
+        for (local, exported) in specifiers {
+            self.export_local(local, exported);
+        }
+    }
+    /// `export { imported as exported } from './a'`, `imported` is `None` for `export * as ns from './a'`
+    pub fn export_from(
+        &mut self,
+        request: &Atom,
+        start: u32,
+        end: u32,
+        specifiers: Vec<(Option<Atom>, Atom)>,
+    ) {
+        let source_order = self.add_side_effect(request);
+        self.remove(start, end, "");
+        for (imported, exported) in specifiers {
+            self.exports.push(exported.clone());
+            self.module_dependencies
+                .push(Box::new(HarmonyExportImportedSpecifierDependency {
+                    request: request.clone(),
+                    source_order,
+                    name: Some(exported),
+                    id: imported,
+                }));
+        }
+    }
+    /// `export * from './a'`
+    pub fn export_all(&mut self, request: &Atom, start: u32, end: u32) {
+        let source_order = self.add_side_effect(request);
+        self.remove(start, end, "");
+        self.module_dependencies
+            .push(Box::new(HarmonyExportImportedSpecifierDependency {
+                request: request.clone(),
+                source_order,
+                name: None,
+                id: None,
+            }));
+    }
+    /// replace the expression with constant code, e.g. `define`
+    pub fn replace(&mut self, start: u32, end: u32, content: &str) {
+        self.remove(start, end, content);
+    }
+    /// remove a dead branch of a constant condition, which is not scanned by the backend
+    pub fn dead_code(&mut self, start: u32, end: u32, content: &str) {
+        self.dead_code.insert((start, end));
+        self.remove(start, end, content);
+    }
+    pub fn is_dead_code(&self, start: u32, end: u32) -> bool {
+        self.dead_code.contains(&(start, end))
+    }
+    /// `new URL("./a.png", import.meta.url)`, returns false if the url is not a file of
+    /// the project, e.g. `https://` and `data:` urls, which are kept as is
+    pub fn new_url(&mut self, request: &Atom, start: u32, end: u32) -> bool {
+        if !is_file_url(request) {
+            return false;
+        }
+        self.module_dependencies.push(Box::new(UrlDependency {
+            request: request.clone(),
+            start,
+            end,
+        }));
+        true
+    }
+    /// `new Worker(new URL("./worker.js", import.meta.url))`, also `SharedWorker`, `start` and `end`
+    /// are the range of `new URL()`. returns false if the url is not a file of the project
+    pub fn new_worker(&mut self, request: &Atom, start: u32, end: u32) -> bool {
+        if !is_file_url(request) {
+            return false;
+        }
+        self.module_dependencies.push(Box::new(WorkerDependency {
+            request: request.clone(),
+            start,
+            end,
+        }));
+        true
+    }
+    /// `await` or `for await` outside of functions, only esm can have it
+    pub fn top_level_await(&mut self) {
+        self.is_harmony = true;
+        self.has_top_level_await = true;
+    }
+    /// reference to an import binding, the backend is responsible for checking
+    /// that `local` is not shadowed
+    pub fn import_reference(
+        &mut self,
+        local: &Atom,
+        start: u32,
+        end: u32,
+        kind: ImportReferenceKind,
+    ) {
+        let Some(binding) = self.imports.get(local) else {
+            return;
+        };
+        self.references.push(HarmonyImportSpecifierDependency {
+            request: binding.request.clone(),
+            source_order: binding.source_order,
+            name: local.clone(),
+            id: binding.imported.clone(),
+            kind,
+            start,
+            end,
+        });
+    }
+    /// reference to a top level binding including its declaration, the backend is responsible
+    /// for checking that `name` is declared at top level. import bindings are skipped
+    pub fn top_level_reference(&mut self, name: &Atom, start: u32, end: u32, shorthand: bool) {
+        if self.imports.contains_key(name) {
+            return;
+        }
+        self.top_level_references.push(TopLevelReference {
+            name: name.clone(),
+            start,
+            end,
+            shorthand,
+        });
+    }
+    /// name of any binding or reference in the module
+    pub fn used_name(&mut self, name: &Atom) {
+        self.used_names.insert(name.clone());
+    }
+    /// the module can't be concatenated with others, only the first reason is kept
+    pub fn module_concatenation_bailout(&mut self, reason: &str) {
+        self.module_concatenation_bailout
+            .get_or_insert_with(|| reason.to_string());
+    }
+
+    pub fn finish(mut self) -> ParseResult {
+        self.references.sort_by_key(|reference| reference.start);
+        self.top_level_references
+            .sort_by_key(|reference| reference.start);
+        let mut used_names = self.used_names.into_iter().collect::<Vec<_>>();
+        used_names.sort();
+        let mut module_dependencies = self.module_dependencies;
+        module_dependencies.extend(
+            self.references
+                .into_iter()
+                .map(|reference| Box::new(reference) as BoxDependency),
+        );
+        let mut presentational_dependencies = self.presentational_dependencies;
+        let exports_type = if self.is_harmony {
+            presentational_dependencies.insert(0, Box::new(HarmonyCompatibilityDependency));
+            ExportsType::Namespace
+        } else {
+            ExportsType::Dynamic
+        };
+        ParseResult {
+            module_dependencies,
+            presentational_dependencies,
+            build_meta: BuildMeta {
+                exports_type,
+                has_top_level_await: self.has_top_level_await,
+                exports: self.exports,
+                local_exports: self.local_exports,
+                top_level_references: self.top_level_references,
+                used_names,
+                module_concatenation_bailout: self.module_concatenation_bailout,
+                ..Default::default()
+            },
+            warnings: vec![],
+        }
+    }
+}
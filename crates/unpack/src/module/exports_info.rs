@@ -0,0 +1,38 @@
+use indexmap::{IndexMap, IndexSet};
+use swc_core::atoms::Atom;
+
+/// an export provided by a module
+#[derive(Debug, Clone, Default)]
+pub struct ProvidedExport {
+    /// provided through `export * from`, local exports and named re-exports take precedence
+    pub from_star: bool,
+}
+
+/// provided and used exports of a module, similar with webpack's ExportsInfo
+#[derive(Debug, Clone, Default)]
+pub struct ExportsInfo {
+    /// exports in the order they are declared, `export *` re-exports are expanded
+    pub provided: IndexMap<Atom, ProvidedExport>,
+    /// the module may provide exports which are unknown statically, e.g. commonjs
+    pub other_exports_provided: bool,
+    /// exports referenced by other modules, including the ones which are not provided
+    pub used: IndexSet<Atom>,
+    /// the exports object is used as a whole, e.g. namespace import, so all the exports are used
+    pub exports_object_used: bool,
+}
+
+impl ExportsInfo {
+    pub fn is_used(&self, name: &Atom) -> bool {
+        self.exports_object_used || self.used.contains(name)
+    }
+    /// whether the export comes from `export * from`, or is not provided by the module itself
+    pub fn is_star_export(&self, name: &Atom) -> bool {
+        self.provided
+            .get(name)
+            .is_none_or(|provided| provided.from_star)
+    }
+    /// provided exports which are not used by any module
+    pub fn unused_exports(&self) -> impl Iterator<Item = &Atom> {
+        self.provided.keys().filter(|name| !self.is_used(name))
+    }
+}
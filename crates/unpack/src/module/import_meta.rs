@@ -0,0 +1,119 @@
+use camino::Utf8Path;
+use miette::{NamedSource, Report};
+
+use super::define::Define;
+use crate::compiler::{CompilerOptions, Target};
+use crate::errors::{Diagnostics, UnknownImportMetaPropertyWarning};
+use crate::utils::identifier::quoted;
+
+/// replacements of `import.meta` and its properties, which are meaningless once the module
+/// is wrapped into a chunk. paths are constants of the module, `import.meta.env.*` comes
+/// from `define`
+pub(super) struct ImportMeta<'a> {
+    resource_path: &'a Utf8Path,
+    source: &'a str,
+    target: Target,
+    define: Define<'a>,
+    pub warnings: Diagnostics,
+}
+
+impl<'a> ImportMeta<'a> {
+    pub fn new(resource_path: &'a Utf8Path, source: &'a str, options: &'a CompilerOptions) -> Self {
+        Self {
+            resource_path,
+            source,
+            target: options.target,
+            define: Define::new(&options.define),
+            warnings: vec![],
+        }
+    }
+    fn url(&self) -> String {
+        let url = url::Url::from_file_path(self.resource_path)
+            .map(String::from)
+            .unwrap_or_else(|_| format!("file://{}", self.resource_path));
+        quoted(&url)
+    }
+    /// `import.meta.dirname` and `import.meta.filename` are only available in node
+    fn path(&self, path: Option<&Utf8Path>) -> String {
+        match (self.target, path) {
+            (Target::Node, Some(path)) => quoted(path.as_str()),
+            _ => "undefined".to_string(),
+        }
+    }
+    fn env(&self) -> String {
+        let properties = self
+            .define
+            .properties("import.meta.env")
+            .into_iter()
+            .map(|(name, code)| format!("{name}: {code}"))
+            .collect::<Vec<_>>();
+        if properties.is_empty() {
+            return "({})".to_string();
+        }
+        format!("({{ {} }})", properties.join(", "))
+    }
+    fn object(&self) -> String {
+        let mut properties = vec![format!("url: {}", self.url())];
+        if self.target == Target::Node {
+            properties.push(format!(
+                "dirname: {}",
+                self.path(self.resource_path.parent())
+            ));
+            properties.push(format!("filename: {}", self.path(Some(self.resource_path))));
+        }
+        properties.push(format!("env: {}", self.env()));
+        format!("({{ {} }})", properties.join(", "))
+    }
+    /// the code replacing `name`, which is `import.meta` or a member expression on it,
+    /// e.g. `import.meta.env.MODE`. `None` if only the object of `name` should be replaced.
+    /// `define` takes precedence over it
+    pub fn expression(&mut self, name: &str, start: u32, end: u32) -> Option<String> {
+        let property = name.strip_prefix("import.meta")?;
+        let Some(property) = property.strip_prefix('.') else {
+            return property.is_empty().then(|| self.object());
+        };
+        Some(match property {
+            "url" => self.url(),
+            "dirname" => self.path(self.resource_path.parent()),
+            "filename" => self.path(Some(self.resource_path)),
+            "env" => self.env(),
+            _ if property.starts_with("env.") => {
+                if property["env.".len()..].contains('.') {
+                    return None;
+                }
+                "undefined".to_string()
+            }
+            _ if property.contains('.') => return None,
+            _ => {
+                self.warnings
+                    .push(Report::new(UnknownImportMetaPropertyWarning {
+                        source_code: NamedSource::new(self.resource_path, self.source.to_string()),
+                        span: (start as usize, (end - start) as usize).into(),
+                        property: property.to_string(),
+                    }));
+                "undefined".to_string()
+            }
+        })
+    }
+    /// the code replacing `typeof name`, same as [Self::expression]
+    pub fn type_of(&self, name: &str) -> Option<String> {
+        let property = name.strip_prefix("import.meta")?;
+        let Some(property) = property.strip_prefix('.') else {
+            return property.is_empty().then(|| quoted("object"));
+        };
+        let type_of = match property {
+            "url" => "string",
+            "dirname" | "filename" if self.target == Target::Node => "string",
+            "env" => "object",
+            // non-literal definitions can't be evaluated
+            _ if property.starts_with("env.") && self.define.expression(name).is_some() => {
+                return None
+            }
+            _ if property.matches('.').count() > usize::from(property.starts_with("env.")) => {
+                return None
+            }
+            _ => "undefined",
+        };
+        Some(quoted(type_of))
+    }
+}
@@ -0,0 +1,51 @@
+use rspack_sources::{BoxSource, ConcatSource, RawSource, SourceExt};
+use rustc_hash::FxHashSet;
+
+/// fragments are rendered by stage first, then by position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum InitFragmentStage {
+    /// `__unpack_require__.r` and export getters, defined before any import is evaluated
+    /// so that circular imports can see them
+    HarmonyExports,
+    HarmonyImports,
+    /// waits for the imported async modules, after all imports have started evaluating
+    AsyncDependencies,
+}
+
+/// code inserted at the top of a module, similar with webpack's InitFragment,
+/// fragments with the same key are only rendered once
+#[derive(Debug, Clone)]
+pub struct InitFragment {
+    pub content: String,
+    pub stage: InitFragmentStage,
+    pub position: u32,
+    pub key: String,
+}
+
+impl InitFragment {
+    pub fn new(content: String, stage: InitFragmentStage, position: u32, key: String) -> Self {
+        Self {
+            content,
+            stage,
+            position,
+            key,
+        }
+    }
+}
+
+pub fn render_init_fragments(source: BoxSource, mut fragments: Vec<InitFragment>) -> BoxSource {
+    if fragments.is_empty() {
+        return source;
+    }
+    // stable sort keeps the order of fragments with the same stage and position
+    fragments.sort_by_key(|fragment| (fragment.stage, fragment.position));
+    let mut keys = FxHashSet::default();
+    let mut concat_source = ConcatSource::default();
+    for fragment in fragments {
+        if keys.insert(fragment.key) {
+            concat_source.add(RawSource::from(fragment.content));
+        }
+    }
+    concat_source.add(source);
+    concat_source.boxed()
+}
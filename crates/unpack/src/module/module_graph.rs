@@ -1,14 +1,17 @@
 use index_vec::IndexVec;
 use indexmap::IndexMap;
 use rustc_hash::FxHashMap;
+use swc_core::atoms::Atom;
 
 mod connection;
 mod dependency;
+mod exports_info;
 mod module;
 mod module_graph_module;
+mod side_effects;
 use crate::{
     dependency::{BoxDependency, DependencyId},
-    module::{BoxModule, ModuleId},
+    module::{BoxModule, ModuleId, ModuleType},
 };
 
 use super::{Connection, ConnectionId, ModuleGraphModule, ModuleGraphModuleId};
@@ -21,6 +24,9 @@ pub struct ModuleGraph {
     pub connections: IndexVec<ConnectionId, Connection>,
     pub dependency_to_connection: IndexMap<DependencyId, ConnectionId>,
     pub module_id_to_module_graph_module_id: FxHashMap<ModuleId, ModuleGraphModuleId>,
+    /// imported export of the dependencies redirected to the module defining the binding,
+    /// `None` for the namespace, e.g. `export * as ns from './a'`
+    pub redirected_ids: FxHashMap<DependencyId, Option<Atom>>,
 }
 
 impl ModuleGraph {
@@ -32,13 +38,19 @@ impl ModuleGraph {
         let connection = self.connection_by_id(*connection_id);
         connection.resolved_module_id
     }
+    /// `None` when the dependency failed to resolve
+    pub fn get_module_id_by_dependency_id(&self, dep_id: DependencyId) -> Option<ModuleId> {
+        self.dependency_to_connection
+            .get(&dep_id)
+            .map(|connection_id| self.connection_by_id(*connection_id).resolved_module_id)
+    }
     pub fn set_resolved_module(
         &mut self,
         origin_module_id: Option<ModuleId>,
         dep_id: DependencyId,
         resolved_module_id: ModuleId,
     ) {
-        let connection = Connection::new(origin_module_id, resolved_module_id);
+        let connection = Connection::new(dep_id, origin_module_id, resolved_module_id);
         let connection_id = self.add_connection(connection);
         self.dependency_to_connection.insert(dep_id, connection_id);
         let resolved_mgm_id = self.module_graph_module_id_by_module_id(resolved_module_id);
@@ -51,6 +63,19 @@ impl ModuleGraph {
             mgm.add_outgoing_connection(connection_id);
         }
     }
+    /// point the connection of the dependency to another module
+    pub fn update_module(&mut self, dep_id: DependencyId, module_id: ModuleId) {
+        let connection_id = self.dependency_to_connection[&dep_id];
+        let connection = &mut self.connections[connection_id];
+        let old_module_id = std::mem::replace(&mut connection.resolved_module_id, module_id);
+        let old_mgm_id = self.module_graph_module_id_by_module_id(old_module_id);
+        self.module_graph_module_by_id_mut(old_mgm_id)
+            .incoming_connections
+            .retain(|&id| id != connection_id);
+        let mgm_id = self.module_graph_module_id_by_module_id(module_id);
+        self.module_graph_module_by_id_mut(mgm_id)
+            .add_incoming_connection(connection_id);
+    }
     pub fn module_graph_module_id_by_module_id(
         &mut self,
         module_id: ModuleId,
@@ -66,6 +91,59 @@ impl ModuleGraph {
         };
         mgm_id
     }
+    pub fn is_async(&self, module_id: ModuleId) -> bool {
+        self.module_id_to_module_graph_module_id
+            .get(&module_id)
+            .is_some_and(|&mgm_id| self.module_graph_module_by_id(mgm_id).is_async)
+    }
+    /// whether the module is only referenced by `url()` and `@import` of css, such modules are
+    /// left out of the javascript of chunks
+    pub fn is_only_referenced_by_css(&self, module_id: ModuleId) -> bool {
+        let Some(&mgm_id) = self.module_id_to_module_graph_module_id.get(&module_id) else {
+            return false;
+        };
+        let incoming_connections = &self.module_graph_module_by_id(mgm_id).incoming_connections;
+        !incoming_connections.is_empty()
+            && incoming_connections.iter().all(|&connection_id| {
+                self.connection_by_id(connection_id)
+                    .origin_module_id
+                    .is_some_and(|origin_module_id| {
+                        matches!(
+                            self.module_by_id(origin_module_id).module_type(),
+                            ModuleType::Css | ModuleType::CssModule
+                        )
+                    })
+            })
+    }
+    /// flag the modules with top level await and all their esm importers as async
+    pub fn set_async_modules(&mut self) {
+        let mut queue = self
+            .modules
+            .iter_enumerated()
+            .filter(|(_, module)| module.build_meta().has_top_level_await)
+            .map(|(module_id, _)| module_id)
+            .collect::<Vec<_>>();
+        while let Some(module_id) = queue.pop() {
+            let mgm_id = self.module_graph_module_id_by_module_id(module_id);
+            let mgm = self.module_graph_module_by_id_mut(mgm_id);
+            if mgm.is_async {
+                continue;
+            }
+            mgm.is_async = true;
+            for connection_id in mgm.incoming_connections.clone() {
+                let connection = self.connection_by_id(connection_id);
+                let is_harmony_import = self
+                    .dependency_by_id(connection.dependency_id)
+                    .as_module_dependency()
+                    .is_some_and(|dependency| dependency.is_harmony_import());
+                if let (true, Some(origin_module_id)) =
+                    (is_harmony_import, connection.origin_module_id)
+                {
+                    queue.push(origin_module_id);
+                }
+            }
+        }
+    }
     pub fn get_outgoing_connections(&mut self, module_id: ModuleId) -> Vec<ConnectionId> {
         let mgm_id = self.module_graph_module_id_by_module_id(module_id);
         let mgm = self.module_graph_module_by_id(mgm_id);
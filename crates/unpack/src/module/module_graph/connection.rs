@@ -9,4 +9,12 @@ impl ModuleGraph {
     pub fn connection_by_id(&self, connection_id: ConnectionId) -> &Connection {
         &self.connections[connection_id]
     }
+    /// sort outgoing connections of modules in the order of their dependencies in the source,
+    /// as connections are added in the order modules finish building
+    pub fn sort_outgoing_connections(&mut self) {
+        for mgm in self.module_graph_modules.iter_mut() {
+            mgm.outgoing_connections
+                .sort_by_key(|&connection_id| self.connections[connection_id].dependency_id);
+        }
+    }
 }
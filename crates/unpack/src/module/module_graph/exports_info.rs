@@ -0,0 +1,134 @@
+use swc_core::atoms::Atom;
+
+use crate::compiler::OptimizationOptions;
+use crate::dependency::ReferencedExports;
+use crate::module::{ExportsInfo, ExportsType, ModuleId, ProvidedExport};
+
+use super::ModuleGraph;
+
+impl ModuleGraph {
+    pub fn exports_info(&self, module_id: ModuleId) -> Option<&ExportsInfo> {
+        self.module_id_to_module_graph_module_id
+            .get(&module_id)
+            .map(|&mgm_id| &self.module_graph_module_by_id(mgm_id).exports_info)
+    }
+    fn exports_info_mut(&mut self, module_id: ModuleId) -> &mut ExportsInfo {
+        let mgm_id = self.module_graph_module_id_by_module_id(module_id);
+        &mut self.module_graph_module_by_id_mut(mgm_id).exports_info
+    }
+    /// exports of modules which are not analyzed are always used
+    pub fn is_export_used(&self, module_id: ModuleId, name: &Atom) -> bool {
+        self.exports_info(module_id)
+            .is_none_or(|exports_info| exports_info.is_used(name))
+    }
+    /// collect the provided exports of every module, redirect re-exports to the modules defining
+    /// them, then flag the exports referenced by dependencies as used, all the exports are used
+    /// if `used_exports` is disabled
+    pub fn set_exports_info(&mut self, optimization: &OptimizationOptions) {
+        self.set_provided_exports();
+        if optimization.side_effects {
+            self.redirect_reexports();
+        }
+        if !optimization.used_exports {
+            for module_id in self.modules.indices() {
+                self.exports_info_mut(module_id).exports_object_used = true;
+            }
+            return;
+        }
+        self.set_used_exports();
+    }
+    fn set_provided_exports(&mut self) {
+        for module_id in self.modules.indices() {
+            let build_meta = self.module_by_id(module_id).build_meta();
+            let is_harmony = build_meta.exports_type == ExportsType::Namespace;
+            let provided = build_meta
+                .exports
+                .iter()
+                .map(|name| (name.clone(), ProvidedExport::default()))
+                .collect();
+            let exports_info = self.exports_info_mut(module_id);
+            exports_info.provided = provided;
+            exports_info.other_exports_provided = !is_harmony;
+        }
+        // `export * from` may be chained or circular, expand them until nothing changes
+        let stars = self
+            .connections
+            .iter()
+            .filter_map(|connection| {
+                let origin_module_id = connection.origin_module_id?;
+                self.dependency_by_id(connection.dependency_id)
+                    .as_module_dependency()
+                    .is_some_and(|dependency| dependency.is_export_star())
+                    .then_some((origin_module_id, connection.resolved_module_id))
+            })
+            .collect::<Vec<_>>();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &(module_id, target) in &stars {
+                let Some(target_exports_info) = self.exports_info(target) else {
+                    continue;
+                };
+                let names = target_exports_info
+                    .provided
+                    .keys()
+                    .filter(|name| name.as_str() != "default")
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let other_exports_provided = target_exports_info.other_exports_provided;
+                let exports_info = self.exports_info_mut(module_id);
+                for name in names {
+                    if !exports_info.provided.contains_key(&name) {
+                        exports_info
+                            .provided
+                            .insert(name, ProvidedExport { from_star: true });
+                        changed = true;
+                    }
+                }
+                if other_exports_provided && !exports_info.other_exports_provided {
+                    exports_info.other_exports_provided = true;
+                    changed = true;
+                }
+            }
+        }
+    }
+    fn set_used_exports(&mut self) {
+        // re-exports reference exports depending on the usage of their own module,
+        // so a module is processed again whenever its usage changes
+        let mut queue = self.modules.indices().collect::<Vec<_>>();
+        while let Some(module_id) = queue.pop() {
+            let Some(exports_info) = self.exports_info(module_id) else {
+                continue;
+            };
+            let mgm_id = self.module_id_to_module_graph_module_id[&module_id];
+            let referenced = self
+                .module_graph_module_by_id(mgm_id)
+                .outgoing_connections
+                .iter()
+                .filter_map(|&connection_id| {
+                    let connection = self.connection_by_id(connection_id);
+                    Some((
+                        connection.resolved_module_id,
+                        self.referenced_exports(connection.dependency_id, exports_info)?,
+                    ))
+                })
+                .collect::<Vec<_>>();
+            for (target, referenced_exports) in referenced {
+                let target_exports_info = self.exports_info_mut(target);
+                let changed = match referenced_exports {
+                    ReferencedExports::ExportsObject => {
+                        !std::mem::replace(&mut target_exports_info.exports_object_used, true)
+                    }
+                    ReferencedExports::Names(names) => {
+                        let len = target_exports_info.used.len();
+                        target_exports_info.used.extend(names);
+                        target_exports_info.used.len() != len
+                    }
+                };
+                if changed {
+                    queue.push(target);
+                }
+            }
+        }
+    }
+}
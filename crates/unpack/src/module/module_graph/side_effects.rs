@@ -0,0 +1,129 @@
+use rustc_hash::FxHashSet;
+use swc_core::atoms::Atom;
+
+use crate::dependency::{DependencyId, ReferencedExports};
+use crate::module::{ConnectionId, ExportsInfo, ModuleId};
+
+use super::ModuleGraph;
+
+impl ModuleGraph {
+    /// exports of the referenced module used by the dependency, taking the redirection of
+    /// re-exports into account
+    pub(super) fn referenced_exports(
+        &self,
+        dependency_id: DependencyId,
+        exports_info: &ExportsInfo,
+    ) -> Option<ReferencedExports> {
+        let dependency = self
+            .dependency_by_id(dependency_id)
+            .as_module_dependency()?;
+        let referenced_exports = dependency.referenced_exports(exports_info);
+        Some(
+            match (referenced_exports, self.redirected_ids.get(&dependency_id)) {
+                (ReferencedExports::Names(names), Some(id)) if !names.is_empty() => match id {
+                    Some(id) => ReferencedExports::Names(vec![id.clone()]),
+                    None => ReferencedExports::ExportsObject,
+                },
+                (referenced_exports, _) => referenced_exports,
+            },
+        )
+    }
+    /// a connection to a side effect free module is inactive if it uses none of the exports,
+    /// the module is left out unless it's referenced by an active connection
+    pub fn is_connection_active(&self, connection_id: ConnectionId) -> bool {
+        let connection = self.connection_by_id(connection_id);
+        let Some(origin_module_id) = connection.origin_module_id else {
+            return true;
+        };
+        let is_entry = self
+            .dependency_by_id(connection.dependency_id)
+            .as_module_dependency()
+            .is_some_and(|dependency| dependency.is_entry());
+        if is_entry
+            || !self
+                .module_by_id(connection.resolved_module_id)
+                .factory_meta()
+                .side_effect_free
+        {
+            return true;
+        }
+        self.exports_info(origin_module_id)
+            .and_then(|exports_info| {
+                self.referenced_exports(connection.dependency_id, exports_info)
+            })
+            .is_none_or(|referenced_exports| referenced_exports != ReferencedExports::Names(vec![]))
+    }
+    /// import re-exported bindings from the modules which define them, so that the side effect
+    /// free modules in between can be left out, similar with webpack's SideEffectsFlagPlugin
+    pub(super) fn redirect_reexports(&mut self) {
+        for dependency_id in self.dependencies.indices() {
+            let Some(id) = self
+                .dependency_by_id(dependency_id)
+                .as_module_dependency()
+                .and_then(|dependency| dependency.imported_id())
+                .cloned()
+            else {
+                continue;
+            };
+            let Some(mut module_id) = self.get_module_id_by_dependency_id(dependency_id) else {
+                continue;
+            };
+            let mut id = Some(id);
+            let mut redirected = false;
+            // re-exports may be circular
+            let mut visited = FxHashSet::default();
+            while let Some(name) = &id {
+                if !self.module_by_id(module_id).factory_meta().side_effect_free
+                    || !visited.insert(module_id)
+                {
+                    break;
+                }
+                let Some((target, target_id)) = self.reexport_target(module_id, name) else {
+                    break;
+                };
+                module_id = target;
+                id = target_id;
+                redirected = true;
+            }
+            if redirected {
+                self.update_module(dependency_id, module_id);
+                self.redirected_ids.insert(dependency_id, id);
+            }
+        }
+    }
+    /// the module and the export which the export `name` of the module is re-exported from,
+    /// `None` if the module defines it or it's unknown
+    fn reexport_target(
+        &self,
+        module_id: ModuleId,
+        name: &Atom,
+    ) -> Option<(ModuleId, Option<Atom>)> {
+        let from_star = self.exports_info(module_id)?.provided.get(name)?.from_star;
+        let mgm_id = self.module_id_to_module_graph_module_id[&module_id];
+        self.module_graph_module_by_id(mgm_id)
+            .outgoing_connections
+            .iter()
+            .find_map(|&connection_id| {
+                let connection = self.connection_by_id(connection_id);
+                let dependency = self
+                    .dependency_by_id(connection.dependency_id)
+                    .as_module_dependency()?;
+                let target = connection.resolved_module_id;
+                if from_star {
+                    let provided = dependency.is_export_star()
+                        && self
+                            .exports_info(target)
+                            .is_some_and(|exports_info| exports_info.provided.contains_key(name));
+                    provided.then(|| (target, Some(name.clone())))
+                } else if dependency.reexported_name() == Some(name) {
+                    let id = match self.redirected_ids.get(&connection.dependency_id) {
+                        Some(id) => id.clone(),
+                        None => dependency.imported_id().cloned(),
+                    };
+                    Some((target, id))
+                } else {
+                    None
+                }
+            })
+    }
+}
@@ -1,11 +1,16 @@
 use index_vec::define_index_type;
 
-use super::ConnectionId;
+use super::{ConnectionId, ExportsInfo};
 
 #[derive(Debug)]
 pub struct ModuleGraphModule {
     pub incoming_connections: Vec<ConnectionId>,
     pub outgoing_connections: Vec<ConnectionId>,
+    /// the module uses top level await or imports an async module
+    pub is_async: bool,
+    pub exports_info: ExportsInfo,
+    /// why optimizations are not applied to the module, e.g. module concatenation
+    pub optimization_bailout: Vec<String>,
 }
 impl Default for ModuleGraphModule {
     fn default() -> Self {
@@ -18,6 +23,9 @@ impl ModuleGraphModule {
         Self {
             incoming_connections: Default::default(),
             outgoing_connections: Default::default(),
+            is_async: false,
+            exports_info: Default::default(),
+            optimization_bailout: Default::default(),
         }
     }
     pub fn add_incoming_connection(&mut self, connection_id: ConnectionId) {
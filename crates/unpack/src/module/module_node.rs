@@ -1,3 +1,6 @@
+use super::BuildMeta;
+use super::FactoryMeta;
+use super::ModuleType;
 use super::CodeGenerationContext;
 use super::NormalModule;
 use camino::Utf8Path;
@@ -10,23 +13,35 @@ use async_trait::async_trait;
 use crate::compiler::CompilerOptions;
 
 use crate::dependency::BoxDependency;
-use crate::dependency::BoxDependencyTemplate;
 use crate::dependency::DependenciesBlock;
 use crate::errors::miette::Result;
+use crate::errors::Diagnostics;
 use crate::plugin::PluginDriver;
+use crate::runtime::RuntimeGlobals;
 
 #[derive(Debug)]
 pub struct BuildResult {
     pub module_dependencies: Vec<BoxDependency>,
-    pub presentational_dependencies: Vec<BoxDependencyTemplate>,
+    /// warnings of the module, errors fail the build instead
+    pub diagnostics: Diagnostics,
 }
 pub struct BuildContext {
     pub options: Arc<CompilerOptions>,
     pub plugin_driver: Arc<PluginDriver>
 }
+/// file emitted along with the chunks which contain the module, e.g. `asset/resource`
+#[derive(Debug, Clone)]
+pub struct ModuleAsset {
+    pub filename: String,
+    pub source: BoxSource,
+}
 #[derive(Debug)]
 pub struct CodeGenerationResult {
     pub source: BoxSource,
+    /// code emitted into the css file of chunk
+    pub css: Option<BoxSource>,
+    pub runtime_requirements: RuntimeGlobals,
+    pub assets: Vec<ModuleAsset>,
 }
 #[async_trait]
 pub trait Module: Debug + DependenciesBlock + Send + Sync {
@@ -35,6 +50,19 @@ pub trait Module: Debug + DependenciesBlock + Send + Sync {
     fn get_context(&self) -> Option<&Utf8Path> {
         None
     }
+    fn build_meta(&self) -> &BuildMeta;
+    /// size of the source in bytes, used to decide whether splitting chunks is worth it
+    fn size(&self) -> usize;
+    /// matched against the conditions of split chunks cache groups
+    fn name_for_condition(&self) -> Option<&str> {
+        None
+    }
+    fn module_type(&self) -> ModuleType {
+        ModuleType::Js
+    }
+    fn factory_meta(&self) -> FactoryMeta {
+        FactoryMeta::default()
+    }
     fn code_generation(
         &self,
         code_generation_context: CodeGenerationContext,
@@ -1,7 +1,7 @@
-use crate::dependency::BoxDependency;
+use crate::dependency::{BoxDependency, DependencyId};
 use crate::errors::miette::{Report, Result};
 use crate::errors::Diagnostics;
-use crate::module::{BuildContext, ModuleId};
+use crate::module::{BuildContext, ModuleId, ModuleType};
 use crate::normal_module_factory::{ModuleFactoryCreateData, NormalModuleFactory};
 use crate::plugin::PluginDriver;
 use crate::task::{BuildTask, FactorizeTask, ProcessDepsTask};
@@ -19,6 +19,7 @@ use crate::{compiler::CompilerOptions, dependency::EntryDependency};
 #[derive(Debug)]
 pub struct EntryData {
     name: Option<String>,
+    pub dependencies: Vec<DependencyId>,
 }
 #[derive(Debug, Clone)]
 pub struct ModuleScanner {
@@ -62,13 +63,15 @@ impl ModuleScanner {
                     entry.import.clone(),
                     self.options.context.clone(),
                 ));
+                let entry_dep_id = state.module_graph.add_dependency(entry_dep);
                 state.entries.insert(
                     entry.name.clone(),
                     EntryData {
                         name: Some(entry.name.clone()),
+                        dependencies: vec![entry_dep_id],
                     },
                 );
-                entry_dep
+                entry_dep_id
             })
             .collect::<Vec<_>>();
 
@@ -77,16 +80,35 @@ impl ModuleScanner {
     pub fn handle_module_creation(
         &self,
         state: &mut ScannerState,
-        dependencies: Vec<BoxDependency>,
+        dependencies: Vec<DependencyId>,
         origin_module_id: Option<ModuleId>,
         context: Option<Utf8PathBuf>,
     ) {
-        dependencies.into_iter().for_each(|dep| {
+        // dependencies with the same request are resolved to the same module, unless they
+        // reference it as different module types, e.g. `import './a.js'` and `new URL('./a.js', import.meta.url)`
+        let mut dependencies_by_request: IndexMap<(String, Option<ModuleType>), Vec<DependencyId>> =
+            IndexMap::default();
+        for dependency_id in dependencies {
+            let dependency = state.module_graph.dependency_by_id(dependency_id);
+            let Some(module_dependency) = dependency.as_module_dependency() else {
+                continue;
+            };
+            dependencies_by_request
+                .entry((
+                    module_dependency.request().to_string(),
+                    module_dependency.module_type(),
+                ))
+                .or_default()
+                .push(dependency_id);
+        }
+        dependencies_by_request.into_values().for_each(|dependencies| {
+            let module_dependency = state.module_graph.dependency_by_id(dependencies[0]).clone();
             state.add_remaining_result();
             state
                 .tx
                 .send(Ok(Task::Factorize(FactorizeTask {
-                    module_dependency: dep,
+                    module_dependency,
+                    dependencies,
                     origin_module_id,
                     origin_module_context: context.clone(),
                 })))
@@ -136,7 +158,7 @@ impl ScannerState {
 }
 /// main loop task
 impl ModuleScanner {
-    pub async fn build_loop(&self, state: &mut ScannerState, dependencies: Vec<BoxDependency>, recv: &mut Receiver<Result<Task>>) {
+    pub async fn build_loop(&self, state: &mut ScannerState, dependencies: Vec<DependencyId>, recv: &mut Receiver<Result<Task>>) {
         // kick off entry dependencies to task_queue
         self.handle_module_creation(state, dependencies, None, Some(self.context.clone()));
         while state.get_remaining_result() > 0 {
@@ -171,7 +193,15 @@ impl ModuleScanner {
             }
             Task::Build(task) => {
                 let scanner = self.clone();
-                if state._modules.contains_key(task.module.identifier()) {
+                if let Some(&module_id) = state._modules.get(task.module.identifier()) {
+                    // already built, only connect the dependencies
+                    for dependency_id in task.dependencies {
+                        state.module_graph.set_resolved_module(
+                            task.origin_module_id,
+                            dependency_id,
+                            module_id,
+                        );
+                    }
                     return;
                 };
                 state.add_remaining_result();
@@ -208,11 +238,11 @@ impl ModuleScanner {
             
         }, self.plugin_driver.clone()).await {
             Ok(factory_result) => {
-                let module = Box::new(factory_result.module);
+                let module = factory_result.module;
                 tx.send(Ok(Task::Build(BuildTask {
                     origin_module_id: task.origin_module_id,
                     module,
-                    module_dependency: task.module_dependency.clone(),
+                    dependencies: task.dependencies,
                 })))
                 .unwrap();
             }
@@ -225,23 +255,45 @@ impl ModuleScanner {
         let module = task.module;
         let original_module_context = module.get_context().map(|x| x.to_owned());
         let identifier = module.identifier().to_string();
+        // the same module may be built concurrently by different requests, keep the first one
+        if let Some(&module_id) = state._modules.get(&identifier) {
+            for dependency_id in task.dependencies {
+                state
+                    .module_graph
+                    .set_resolved_module(task.origin_module_id, dependency_id, module_id);
+            }
+            return;
+        }
+        state.diagnostics.extend(task.diagnostics);
         let module_id = state.module_graph.add_module(module);
-        let dependency_id = state.module_graph.add_dependency(task.module_dependency);
-        state._modules.insert(identifier.to_string(), module_id);
+        state._modules.insert(identifier, module_id);
         // update origin -> self
-        state
-            .module_graph
-            .set_resolved_module(task.origin_module_id, dependency_id, module_id);
+        for dependency_id in task.dependencies {
+            state
+                .module_graph
+                .set_resolved_module(task.origin_module_id, dependency_id, module_id);
+        }
+        let dependency_ids = task
+            .module_dependencies
+            .into_iter()
+            .map(|dependency| {
+                let dependency_id = state.module_graph.add_dependency(dependency);
+                state
+                    .module_graph
+                    .module_by_id_mut(module_id)
+                    .add_dependency_id(dependency_id);
+                dependency_id
+            })
+            .collect::<Vec<_>>();
         self.handle_module_creation(
             state,
-            task.dependencies,
+            dependency_ids,
             Some(module_id),
             original_module_context,
         );
     }
     async fn handle_build(self, tx: Sender<Result<Task>>, task: BuildTask) {
         let mut module = task.module;
-        let module_dependency = task.module_dependency;
 
         match module.build(BuildContext {
             options: self.options.clone(),
@@ -249,10 +301,11 @@ impl ModuleScanner {
         }).await {
             Ok(result) => {
                 tx.send(Ok(Task::ProcessDeps(ProcessDepsTask {
-                    dependencies: result.module_dependencies,
+                    dependencies: task.dependencies,
+                    module_dependencies: result.module_dependencies,
                     origin_module_id: task.origin_module_id,
-                    module_dependency,
                     module,
+                    diagnostics: result.diagnostics,
                 })))
                 .unwrap();
             }
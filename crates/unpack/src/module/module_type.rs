@@ -0,0 +1,79 @@
+use std::str::FromStr;
+
+use camino::Utf8Path;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ModuleType {
+    #[default]
+    Js,
+    Json,
+    /// `asset`, inline or resource depends on the size of the file
+    Asset,
+    /// `asset/resource`, emitted as a file and exports its url
+    AssetResource,
+    /// `asset/inline`, exports a data url
+    AssetInline,
+    /// `asset/source`, exports the content as string
+    AssetSource,
+    /// `css`, emitted into the css file of chunk
+    Css,
+    /// `css/module`, class names are local to the module and exported to javascript
+    CssModule,
+}
+
+impl FromStr for ModuleType {
+    type Err = String;
+    /// webpack's names of module types, e.g. `asset/resource`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value {
+            "javascript/auto" | "javascript/esm" | "javascript/dynamic" => ModuleType::Js,
+            "json" => ModuleType::Json,
+            "asset" => ModuleType::Asset,
+            "asset/resource" => ModuleType::AssetResource,
+            "asset/inline" => ModuleType::AssetInline,
+            "asset/source" => ModuleType::AssetSource,
+            "css" => ModuleType::Css,
+            "css/module" => ModuleType::CssModule,
+            _ => return Err(format!("unknown module type \"{value}\"")),
+        })
+    }
+}
+
+impl ModuleType {
+    /// webpack's name of the module type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModuleType::Js => "javascript/auto",
+            ModuleType::Json => "json",
+            ModuleType::Asset => "asset",
+            ModuleType::AssetResource => "asset/resource",
+            ModuleType::AssetInline => "asset/inline",
+            ModuleType::AssetSource => "asset/source",
+            ModuleType::Css => "css",
+            ModuleType::CssModule => "css/module",
+        }
+    }
+    /// pick the module type of a resource by its extension
+    pub fn from_path(resource_path: &Utf8Path) -> Self {
+        match resource_path.extension() {
+            Some("json") => ModuleType::Json,
+            Some(
+                "png" | "jpg" | "jpeg" | "gif" | "svg" | "webp" | "avif" | "ico" | "bmp" | "woff"
+                | "woff2" | "ttf" | "otf" | "eot" | "mp3" | "mp4" | "webm" | "wav" | "ogg",
+            ) => ModuleType::Asset,
+            Some("txt") => ModuleType::AssetSource,
+            Some("css") if resource_path.as_str().ends_with(".module.css") => {
+                ModuleType::CssModule
+            }
+            Some("css") => ModuleType::Css,
+            _ => ModuleType::Js,
+        }
+    }
+    /// whether the content is kept as bytes instead of text
+    pub fn is_binary(&self) -> bool {
+        matches!(
+            self,
+            ModuleType::Asset | ModuleType::AssetResource | ModuleType::AssetInline
+        )
+    }
+}
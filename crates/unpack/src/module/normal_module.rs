@@ -3,20 +3,40 @@ use async_trait::async_trait;
 use crate::dependency::{
     AsyncDependenciesBlockId, BoxDependency, BoxDependencyTemplate, DependenciesBlock, DependencyId,
 };
+use crate::chunk::ChunkGraph;
+use crate::optimize::ConcatenatedModule;
+use crate::runtime::RuntimeGlobals;
 use crate::errors::miette::Result;
-use crate::errors::Diagnostics;
-use crate::plugin::LoadArgs;
+use crate::errors::{Diagnostics, InputSourceMapWarning};
+use crate::compiler::{CompilerOptions, Devtool, ParserBackend};
+use crate::loader::{run_loaders, BoxLoader, LoaderContext};
+use crate::plugin::{LoadArgs, LoadResult};
+use crate::utils::source_map::{compose_source_maps, read_source_map, split_source_mapping_url};
 use camino::{Utf8Path, Utf8PathBuf};
 use miette::{IntoDiagnostic, Report};
-use rspack_sources::{BoxSource, OriginalSource, ReplaceSource, SourceExt};
+use rspack_sources::{
+    BoxSource, OriginalSource, RawSource, SourceExt, SourceMap, SourceMapSource,
+    WithoutOriginalOptions,
+};
 
 use super::ast::parse;
-use super::{BuildContext, BuildResult, Module};
+use super::ast2::parse2;
+use super::transform::transform;
+use super::{
+    AssetParserAndGenerator, BoxParserAndGenerator, BuildContext, CssParserAndGenerator, BuildMeta, BuildResult, FactoryMeta, GenerateContext,
+    JavaScriptParserAndGenerator, JsonParserAndGenerator, Module, ModuleId, ModuleType,
+    ParseContext,
+};
 use super::{CodeGenerationResult, ModuleGraph};
 #[derive(Debug)]
 pub struct NormalModule {
     context: Option<Utf8PathBuf>,
     resource_path: Utf8PathBuf,
+    /// resource path with query, prefixed with the module type unless javascript,
+    /// e.g. `asset/resource|/a.js`, as a file can be referenced as different types
+    identifier: String,
+    resource_query: String,
+    loaders: Vec<BoxLoader>,
     request: String,
     diagnostics: Diagnostics,
     original_source: Option<BoxSource>,
@@ -24,6 +44,10 @@ pub struct NormalModule {
     presentational_dependencies: Vec<BoxDependencyTemplate>,
     blocks: Vec<AsyncDependenciesBlockId>,
     source: NormalModuleSource,
+    module_type: ModuleType,
+    parser_and_generator: BoxParserAndGenerator,
+    build_meta: BuildMeta,
+    factory_meta: FactoryMeta,
 }
 #[derive(Debug, Clone)]
 enum NormalModuleSource {
@@ -32,11 +56,20 @@ enum NormalModuleSource {
     Failed(Arc<Report>),
 }
 pub struct CodeGenerationContext<'a> {
+    pub options: &'a CompilerOptions,
     pub module_graph: &'a ModuleGraph,
+    pub chunk_graph: &'a ChunkGraph,
+    pub module_id: ModuleId,
+    /// the module is merged into the scope of the concatenated module, its imports reference
+    /// the bindings of other modules directly and its exports are defined by the root
+    pub concatenated_module: Option<&'a ConcatenatedModule>,
 }
+#[derive(Debug)]
 pub struct ParseResult {
     pub(crate) module_dependencies: Vec<BoxDependency>,
     pub(crate) presentational_dependencies: Vec<BoxDependencyTemplate>,
+    pub(crate) build_meta: BuildMeta,
+    pub(crate) warnings: Diagnostics,
 }
 impl DependenciesBlock for NormalModule {
     fn add_block_id(&mut self, block_id: AsyncDependenciesBlockId) {
@@ -58,57 +91,182 @@ impl DependenciesBlock for NormalModule {
 #[async_trait]
 impl Module for NormalModule {
     fn identifier(&self) -> &str {
-        self.resource_path.as_str()
+        &self.identifier
     }
     async fn build(&mut self, build_context: BuildContext) -> Result<BuildResult> {
         let resource_path = self.resource_path.clone();
         let content = build_context.plugin_driver.run_load_hook(LoadArgs {
             path: resource_path.clone(),
         }).await?;
-        let content = match content {
-            Some(content) => String::from_utf8_lossy(content.as_ref()).to_string(),
-            None => tokio::fs::read_to_string(resource_path.clone()).await.into_diagnostic()?,
+        // content is read as bytes, binary assets must not be decoded
+        let (content, source_map) = match content {
+            Some(LoadResult {
+                content,
+                source_map,
+            }) => (content, source_map),
+            None => (
+                tokio::fs::read(resource_path.clone()).await.into_diagnostic()?,
+                None,
+            ),
+        };
+        let mut loader_context = LoaderContext {
+            resource_path: resource_path.clone(),
+            resource_query: self.resource_query.clone(),
+            content,
+            source_map,
+            options: build_context.options.clone(),
+        };
+        run_loaders(&self.loaders, &mut loader_context).await?;
+        let LoaderContext {
+            content,
+            mut source_map,
+            ..
+        } = loader_context;
+        let mut diagnostics = vec![];
+        let source = if self.module_type.is_binary() {
+            RawSource::from(content).boxed()
+        } else {
+            let with_source_map = build_context.options.devtool != Devtool::None;
+            let mut content = String::from_utf8_lossy(&content).into_owned();
+            if self.module_type == ModuleType::Js {
+                // pre-compiled code, e.g. in node_modules, references its map by a comment,
+                // which is removed as the map is merged into the map of the chunk
+                if let Some((url, code)) = split_source_mapping_url(&content) {
+                    let (url, len) = (url.to_string(), code.len());
+                    content.truncate(len);
+                    if with_source_map && source_map.is_none() {
+                        match read_source_map(&url, &resource_path).await {
+                            Ok(map) => source_map = Some(map),
+                            Err(err) => diagnostics.push(
+                                InputSourceMapWarning {
+                                    module: self.identifier.clone(),
+                                    message: err.to_string(),
+                                }
+                                .into(),
+                            ),
+                        }
+                    }
+                }
+                if let Some((code, map)) = transform(
+                    &content,
+                    &resource_path,
+                    &build_context.options.jsx,
+                    with_source_map,
+                )? {
+                    source_map = match (map, source_map) {
+                        (Some(map), Some(input_map)) => {
+                            Some(compose_source_maps(&code, map, input_map))
+                        }
+                        (map, _) => map,
+                    };
+                    content = code;
+                }
+            }
+            Self::create_source(resource_path.to_string(), content, source_map)
         };
-        let source = Self::create_source(resource_path.to_string().clone(), content.clone());
-        let parse_result = Self::parse(content)?;
+        let parse_result = self.parser_and_generator.parse(ParseContext {
+            source: source.clone(),
+            resource_path: &resource_path,
+            options: &build_context.options,
+        })?;
 
         self.source = NormalModuleSource::Succeed(source.clone());
+        self.presentational_dependencies = parse_result.presentational_dependencies;
+        self.build_meta = parse_result.build_meta;
         Ok(BuildResult {
             module_dependencies: parse_result.module_dependencies,
-            presentational_dependencies: parse_result.presentational_dependencies,
+            diagnostics: diagnostics.into_iter().chain(parse_result.warnings).collect(),
         })
     }
 
     fn get_context(&self) -> Option<&Utf8Path> {
         self.context.as_ref().map(|x| x.as_ref())
     }
+    fn build_meta(&self) -> &BuildMeta {
+        &self.build_meta
+    }
+    fn size(&self) -> usize {
+        match &self.source {
+            NormalModuleSource::Succeed(source) => source.size(),
+            NormalModuleSource::UnBuild | NormalModuleSource::Failed(_) => 0,
+        }
+    }
+    fn name_for_condition(&self) -> Option<&str> {
+        Some(self.resource_path.as_str())
+    }
+    fn module_type(&self) -> ModuleType {
+        self.module_type
+    }
+    fn factory_meta(&self) -> FactoryMeta {
+        self.factory_meta
+    }
     fn code_generation(
         &self,
         code_generation_context: CodeGenerationContext,
     ) -> Result<CodeGenerationResult> {
-        let generate_result = match &self.source {
+        let mut runtime_requirements = RuntimeGlobals::default();
+        let mut assets = vec![];
+        let source = match &self.source {
             NormalModuleSource::Failed(_) => {
                 todo!("no implemented yet")
             }
-            NormalModuleSource::Succeed(source) => {
-                self.generate(source.clone(), &code_generation_context)
-            }
+            NormalModuleSource::Succeed(source) => source,
             NormalModuleSource::UnBuild => {
                 panic!("should have source")
             }
         };
+        let mut generate_context = GenerateContext {
+            code_generation_context: &code_generation_context,
+            module_dependencies: &self.module_dependencies,
+            presentational_dependencies: &self.presentational_dependencies,
+            runtime_requirements: &mut runtime_requirements,
+            assets: &mut assets,
+        };
+        let generated = self
+            .parser_and_generator
+            .generate(source, &mut generate_context)?;
+        let css = self
+            .parser_and_generator
+            .generate_css(source, &mut generate_context)?;
 
         Ok(CodeGenerationResult {
-            source: generate_result?,
+            source: generated,
+            css,
+            runtime_requirements,
+            assets,
         })
     }
 }
 impl NormalModule {
-    pub fn new(request: String, resource_path: Utf8PathBuf) -> Self {
+    pub fn new(
+        request: String,
+        resource_path: Utf8PathBuf,
+        resource_query: String,
+        module_type: ModuleType,
+        loaders: Vec<BoxLoader>,
+        factory_meta: FactoryMeta,
+    ) -> Self {
         let context = resource_path.parent().map(|x| x.to_owned());
+        let parser_and_generator: BoxParserAndGenerator = match module_type {
+            ModuleType::Js => Box::new(JavaScriptParserAndGenerator),
+            ModuleType::Json => Box::new(JsonParserAndGenerator),
+            ModuleType::Asset
+            | ModuleType::AssetResource
+            | ModuleType::AssetInline
+            | ModuleType::AssetSource => Box::new(AssetParserAndGenerator::new(module_type)),
+            ModuleType::Css | ModuleType::CssModule => {
+                Box::new(CssParserAndGenerator::new(module_type))
+            }
+        };
         Self {
             request,
+            identifier: match module_type {
+                ModuleType::Js => format!("{resource_path}{resource_query}"),
+                _ => format!("{}|{resource_path}{resource_query}", module_type.as_str()),
+            },
             resource_path,
+            resource_query,
+            loaders,
             diagnostics: vec![],
             original_source: None,
             context,
@@ -116,40 +274,39 @@ impl NormalModule {
             module_dependencies: vec![],
             presentational_dependencies: vec![],
             source: NormalModuleSource::UnBuild,
+            module_type,
+            parser_and_generator,
+            build_meta: Default::default(),
+            factory_meta,
         }
     }
-
-    fn generate(
-        &self,
-        source: BoxSource,
-        code_generation_context: &CodeGenerationContext,
-    ) -> Result<BoxSource> {
-        let mut source = ReplaceSource::new(source);
-        self.module_dependencies.iter().for_each(|dep_id| {
-            if let Some(dependency) = code_generation_context
-                .module_graph
-                .dependency_by_id(*dep_id)
-                .as_dependency_template()
-            {
-                dependency.apply(&mut source, code_generation_context);
-            }
-        });
-        self.presentational_dependencies
-            .iter()
-            .for_each(|dependency| {
-                dependency.apply(&mut source, code_generation_context);
-            });
-
-        Ok(source.boxed())
-    }
 }
 
 impl NormalModule {
-    fn create_source(resource_path: String, content: String) -> BoxSource {
-        OriginalSource::new(content, resource_path).boxed()
+    fn create_source(
+        resource_path: String,
+        content: String,
+        source_map: Option<SourceMap>,
+    ) -> BoxSource {
+        match source_map {
+            Some(source_map) => SourceMapSource::new(WithoutOriginalOptions {
+                value: content,
+                name: resource_path,
+                source_map,
+            })
+            .boxed(),
+            None => OriginalSource::new(content, resource_path).boxed(),
+        }
     }
-    fn parse(content: String) -> Result<ParseResult> {
-        parse(content)
-        //parse2(content)
+    /// parse a javascript module with the backend of options
+    pub fn parse(
+        content: String,
+        resource_path: &Utf8Path,
+        options: &CompilerOptions,
+    ) -> Result<ParseResult> {
+        match options.parser_backend {
+            ParserBackend::Swc => parse(content, resource_path, options),
+            ParserBackend::Oxc => parse2(content, resource_path, options),
+        }
     }
 }
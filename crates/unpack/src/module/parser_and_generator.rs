@@ -0,0 +1,52 @@
+mod asset;
+mod css;
+mod javascript;
+mod json;
+use std::fmt::Debug;
+
+pub use asset::*;
+pub use css::*;
+use camino::Utf8Path;
+pub use javascript::*;
+pub use json::*;
+use rspack_sources::BoxSource;
+
+use super::{CodeGenerationContext, ModuleAsset, ParseResult};
+use crate::compiler::CompilerOptions;
+use crate::dependency::{BoxDependencyTemplate, DependencyId};
+use crate::errors::miette::Result;
+use crate::runtime::RuntimeGlobals;
+
+pub struct ParseContext<'a> {
+    pub source: BoxSource,
+    pub resource_path: &'a Utf8Path,
+    pub options: &'a CompilerOptions,
+}
+
+pub struct GenerateContext<'a, 'b> {
+    pub code_generation_context: &'a CodeGenerationContext<'b>,
+    pub module_dependencies: &'a [DependencyId],
+    pub presentational_dependencies: &'a [BoxDependencyTemplate],
+    pub runtime_requirements: &'a mut RuntimeGlobals,
+    pub assets: &'a mut Vec<ModuleAsset>,
+}
+
+/// parse and generate the code of a [super::NormalModule] by its module type, similar with rspack
+pub trait ParserAndGenerator: Debug + Send + Sync {
+    fn parse(&mut self, parse_context: ParseContext) -> Result<ParseResult>;
+    fn generate(
+        &self,
+        source: &BoxSource,
+        generate_context: &mut GenerateContext,
+    ) -> Result<BoxSource>;
+    /// the code emitted into the css file of chunk, only css modules have it
+    fn generate_css(
+        &self,
+        _source: &BoxSource,
+        _generate_context: &mut GenerateContext,
+    ) -> Result<Option<BoxSource>> {
+        Ok(None)
+    }
+}
+
+pub type BoxParserAndGenerator = Box<dyn ParserAndGenerator>;
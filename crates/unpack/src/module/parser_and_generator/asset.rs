@@ -0,0 +1,127 @@
+use base64::Engine;
+use rspack_sources::{BoxSource, RawSource, SourceExt};
+
+use super::{GenerateContext, ParseContext, ParserAndGenerator};
+use crate::errors::miette::Result;
+use crate::module::{AssetUrl, BuildMeta, ModuleAsset, ModuleType, ParseResult};
+use crate::runtime::RuntimeGlobals;
+use crate::utils::hash::{output_hash, replace_hash, shorten_hash};
+use crate::utils::identifier::quoted;
+
+#[derive(Debug, Clone)]
+enum AssetKind {
+    Resource { filename: String },
+    Inline { data_url: String },
+    Source,
+}
+
+/// parser and generator of `asset`, `asset/resource`, `asset/inline` and `asset/source` modules
+#[derive(Debug)]
+pub struct AssetParserAndGenerator {
+    module_type: ModuleType,
+    kind: Option<AssetKind>,
+}
+
+impl AssetParserAndGenerator {
+    pub fn new(module_type: ModuleType) -> Self {
+        Self {
+            module_type,
+            kind: None,
+        }
+    }
+}
+
+/// replace `[name]`, `[ext]` and `[hash]` or `[contenthash]` of the filename template
+fn asset_filename(template: &str, parse_context: &ParseContext) -> String {
+    let resource_path = parse_context.resource_path;
+    let name = resource_path.file_stem().unwrap_or_default();
+    let ext = resource_path
+        .extension()
+        .map(|ext| format!(".{ext}"))
+        .unwrap_or_default();
+    let output = &parse_context.options.output;
+    let hash = output_hash(output.hash_function, &parse_context.source.buffer());
+    let filename = template.replace("[name]", name).replace("[ext]", &ext);
+    ["hash", "contenthash"]
+        .into_iter()
+        .fold(filename, |filename, placeholder| {
+            replace_hash(
+                &filename,
+                placeholder,
+                output.hash_digest_length,
+                |length| shorten_hash(&hash, length).to_string(),
+            )
+        })
+}
+
+fn data_url(mimetype: &str, source: &BoxSource) -> String {
+    let data = base64::engine::general_purpose::STANDARD.encode(source.buffer());
+    format!("data:{mimetype};base64,{data}")
+}
+
+impl ParserAndGenerator for AssetParserAndGenerator {
+    fn parse(&mut self, parse_context: ParseContext) -> Result<ParseResult> {
+        let options = parse_context.options;
+        let inline = match self.module_type {
+            ModuleType::AssetInline => true,
+            ModuleType::Asset => {
+                parse_context.source.size() <= options.module.parser.asset.data_url_max_size
+            }
+            _ => false,
+        };
+        let (kind, asset_url) = if self.module_type == ModuleType::AssetSource {
+            (AssetKind::Source, None)
+        } else if inline {
+            let mimetype = mime_guess::from_path(parse_context.resource_path)
+                .first_or_octet_stream()
+                .to_string();
+            let data_url = data_url(&mimetype, &parse_context.source);
+            (
+                AssetKind::Inline {
+                    data_url: data_url.clone(),
+                },
+                Some(AssetUrl::DataUrl(data_url)),
+            )
+        } else {
+            let filename = asset_filename(&options.output.asset_module_filename, &parse_context);
+            (
+                AssetKind::Resource {
+                    filename: filename.clone(),
+                },
+                Some(AssetUrl::Filename(filename)),
+            )
+        };
+        self.kind = Some(kind);
+        Ok(ParseResult {
+            module_dependencies: vec![],
+            presentational_dependencies: vec![],
+            build_meta: BuildMeta {
+                asset_url,
+                ..Default::default()
+            },
+            warnings: vec![],
+        })
+    }
+
+    fn generate(
+        &self,
+        source: &BoxSource,
+        generate_context: &mut GenerateContext,
+    ) -> Result<BoxSource> {
+        let exports = match self.kind.as_ref().expect("asset should be parsed") {
+            AssetKind::Resource { filename } => {
+                generate_context.assets.push(ModuleAsset {
+                    filename: filename.clone(),
+                    source: source.clone(),
+                });
+                generate_context
+                    .runtime_requirements
+                    .insert(RuntimeGlobals::PUBLIC_PATH);
+                format!("__unpack_require__.p + {}", quoted(filename))
+            }
+            AssetKind::Inline { data_url } => quoted(data_url),
+            AssetKind::Source => quoted(&source.source()),
+        };
+        Ok(RawSource::from(format!("module.exports = {exports};")).boxed())
+    }
+}
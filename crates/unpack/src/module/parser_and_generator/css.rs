@@ -0,0 +1,309 @@
+use camino::Utf8Path;
+use indexmap::IndexMap;
+use miette::NamedSource;
+use rspack_sources::{BoxSource, RawSource, ReplaceSource, SourceExt};
+use swc_core::atoms::Atom;
+use swc_core::common::{sync::Lrc, FileName, SourceMap, Span};
+use swc_core::css::ast::*;
+use swc_core::css::parser::{parse_file, parser::ParserConfig};
+use swc_core::css::visit::{Visit, VisitWith};
+
+use super::{GenerateContext, ParseContext, ParserAndGenerator};
+use crate::dependency::{
+    BoxDependency, BoxDependencyTemplate, ConstDependency, CssImportDependency, CssUrlDependency,
+    SpanExt, TemplateContext,
+};
+use crate::errors::miette::Result;
+use crate::errors::CssParseError;
+use crate::module::{BuildMeta, ModuleType, ParseResult};
+use crate::utils::hash::{output_hash, shorten_hash};
+use crate::utils::identifier::quoted;
+
+/// parser and generator of `css` and `css/module` modules, the css is emitted into the css file
+/// of chunk, and the javascript part exports the local class names of `css/module`
+#[derive(Debug)]
+pub struct CssParserAndGenerator {
+    module_type: ModuleType,
+    /// local class name to the hashed one, only for `css/module`
+    locals: IndexMap<Atom, String>,
+}
+
+impl CssParserAndGenerator {
+    pub fn new(module_type: ModuleType) -> Self {
+        Self {
+            module_type,
+            locals: Default::default(),
+        }
+    }
+}
+
+/// urls which are not resolved as modules
+fn is_external_url(url: &str) -> bool {
+    url.is_empty()
+        || url.starts_with('#')
+        || url.starts_with('/')
+        || url.starts_with("data:")
+        || url.contains("://")
+}
+
+/// urls in css are relative unless prefixed with `~`, e.g. `url(a.png)` and `url(~pkg/a.png)`
+fn url_to_request(url: &str) -> Atom {
+    match url.strip_prefix('~') {
+        Some(request) => request.into(),
+        None if url.starts_with("./") || url.starts_with("../") => url.into(),
+        None => format!("./{url}").into(),
+    }
+}
+
+/// the at-rules equivalent to `layer()`, `supports()` and media queries of `@import`
+/// from the outermost, which wrap the imported css
+fn import_conditions(import: &ImportPrelude, source: &str) -> Vec<String> {
+    let text = |span: Span| &source[span.real_lo() as usize..span.real_hi() as usize];
+    let mut conditions = vec![];
+    match import.layer_name.as_deref() {
+        Some(ImportLayerName::Ident(_)) => conditions.push("@layer".to_string()),
+        Some(ImportLayerName::Function(layer)) => {
+            let name = text(layer.span)
+                .strip_prefix("layer(")
+                .and_then(|name| name.strip_suffix(')'));
+            conditions.push(format!("@layer {}", name.unwrap_or_default().trim()));
+        }
+        None => {}
+    }
+    if let Some(import_conditions) = &import.import_conditions {
+        if let Some(supports) = &import_conditions.supports {
+            // `supports(display: grid)` to `@supports (display: grid)`
+            let condition = text(supports.span).strip_prefix("supports");
+            conditions.push(format!("@supports {}", condition.unwrap_or_default()));
+        }
+        if let Some(media) = &import_conditions.media {
+            conditions.push(format!("@media {}", text(media.span)));
+        }
+    }
+    conditions
+}
+
+struct CssDependencyScanner<'a> {
+    source: &'a str,
+    module_dependencies: Vec<BoxDependency>,
+    presentational_dependencies: Vec<BoxDependencyTemplate>,
+    /// the suffix of hashed class names, `None` if class names are global
+    local_suffix: Option<String>,
+    locals: IndexMap<Atom, String>,
+    in_global: bool,
+}
+
+impl CssDependencyScanner<'_> {
+    fn remove(&mut self, start: u32, end: u32) {
+        self.presentational_dependencies
+            .push(Box::new(ConstDependency::new(start, end, String::new())));
+    }
+}
+
+impl Visit for CssDependencyScanner<'_> {
+    fn visit_at_rule(&mut self, at_rule: &AtRule) {
+        if let Some(AtRulePrelude::ImportPrelude(import)) = at_rule.prelude.as_deref() {
+            let url = match &*import.href {
+                ImportHref::Str(str) => Some(&str.value),
+                ImportHref::Url(url) => url_value(url),
+            };
+            if let Some(url) = url.filter(|url| !is_external_url(url)) {
+                self.module_dependencies.push(Box::new(CssImportDependency {
+                    request: url_to_request(url),
+                    conditions: import_conditions(import, self.source),
+                    start: at_rule.span.real_lo(),
+                    end: at_rule.span.real_hi(),
+                }));
+            }
+            return;
+        }
+        at_rule.visit_children_with(self);
+    }
+    fn visit_url(&mut self, url: &Url) {
+        if let Some(value) = url_value(url).filter(|url| !is_external_url(url)) {
+            self.module_dependencies.push(Box::new(CssUrlDependency {
+                request: url_to_request(value),
+                start: url.span.real_lo(),
+                end: url.span.real_hi(),
+            }));
+        }
+    }
+    fn visit_class_selector(&mut self, class: &ClassSelector) {
+        let Some(suffix) = &self.local_suffix else {
+            return;
+        };
+        if self.in_global {
+            return;
+        }
+        let local = &class.text.value;
+        let hashed = format!("{local}_{suffix}");
+        self.locals.insert(local.clone(), hashed.clone());
+        self.presentational_dependencies
+            .push(Box::new(ConstDependency::new(
+                class.text.span.real_lo(),
+                class.text.span.real_hi(),
+                hashed,
+            )));
+    }
+    /// `:global(.a)` and `:local(.a)` are unwrapped
+    fn visit_pseudo_class_selector(&mut self, pseudo: &PseudoClassSelector) {
+        let in_global = match &*pseudo.name.value {
+            "global" => true,
+            "local" => false,
+            _ => return pseudo.visit_children_with(self),
+        };
+        let (Some(first), Some(last)) = (
+            pseudo
+                .children
+                .as_ref()
+                .and_then(|children| children.first()),
+            pseudo
+                .children
+                .as_ref()
+                .and_then(|children| children.last()),
+        ) else {
+            return;
+        };
+        self.remove(pseudo.span.real_lo(), span_of(first).real_lo());
+        self.remove(span_of(last).real_hi(), pseudo.span.real_hi());
+        let parent_in_global = std::mem::replace(&mut self.in_global, in_global);
+        pseudo.children.visit_children_with(self);
+        self.in_global = parent_in_global;
+    }
+}
+
+fn url_value(url: &Url) -> Option<&Atom> {
+    match url.value.as_deref()? {
+        UrlValue::Str(str) => Some(&str.value),
+        UrlValue::Raw(raw) => Some(&raw.value),
+    }
+}
+
+fn span_of(children: &PseudoClassSelectorChildren) -> Span {
+    use swc_core::common::Spanned;
+    children.span()
+}
+
+fn css_parse_error(
+    error: swc_core::css::parser::error::Error,
+    resource_path: &Utf8Path,
+    content: String,
+) -> miette::Report {
+    let message = error.message().to_string();
+    let span = error.into_inner().0;
+    let start = span.real_lo() as usize;
+    CssParseError {
+        source_code: NamedSource::new(resource_path.as_str(), content),
+        span: (start, span.real_hi() as usize - start).into(),
+        message,
+    }
+    .into()
+}
+
+impl ParserAndGenerator for CssParserAndGenerator {
+    fn parse(&mut self, parse_context: ParseContext) -> Result<ParseResult> {
+        let content = parse_context.source.source().into_owned();
+        let resource_path = parse_context.resource_path;
+        let is_module = self.module_type == ModuleType::CssModule;
+        let cm: Lrc<SourceMap> = Default::default();
+        let fm = cm.new_source_file(
+            Lrc::new(FileName::Real(resource_path.as_std_path().to_path_buf())),
+            content.clone(),
+        );
+        let mut errors = vec![];
+        let stylesheet = parse_file::<Stylesheet>(
+            &fm,
+            None,
+            ParserConfig {
+                css_modules: is_module,
+                ..Default::default()
+            },
+            &mut errors,
+        );
+        // recoverable errors are ignored like browsers do, the css is emitted as written
+        let stylesheet = match stylesheet {
+            Ok(stylesheet) => stylesheet,
+            Err(error) => return Err(css_parse_error(error, resource_path, content)),
+        };
+        // the hash of the path relative to context keeps class names stable across machines
+        let local_suffix = is_module.then(|| {
+            let path = resource_path
+                .strip_prefix(&parse_context.options.context)
+                .unwrap_or(resource_path)
+                .as_str()
+                .replace('\\', "/");
+            let hash = output_hash(parse_context.options.output.hash_function, path.as_bytes());
+            shorten_hash(&hash, 8).to_string()
+        });
+        let mut scanner = CssDependencyScanner {
+            source: &content,
+            module_dependencies: vec![],
+            presentational_dependencies: vec![],
+            local_suffix,
+            locals: Default::default(),
+            in_global: false,
+        };
+        stylesheet.visit_with(&mut scanner);
+        self.locals = scanner.locals;
+        Ok(ParseResult {
+            module_dependencies: scanner.module_dependencies,
+            presentational_dependencies: scanner.presentational_dependencies,
+            build_meta: BuildMeta::default(),
+            warnings: vec![],
+        })
+    }
+
+    fn generate(
+        &self,
+        _source: &BoxSource,
+        _generate_context: &mut GenerateContext,
+    ) -> Result<BoxSource> {
+        if self.module_type != ModuleType::CssModule {
+            return Ok(RawSource::from("").boxed());
+        }
+        let mut code = String::from("module.exports = {\n");
+        for (local, hashed) in &self.locals {
+            code.push_str(&format!("  {}: {},\n", quoted(local), quoted(hashed)));
+        }
+        code.push_str("};");
+        Ok(RawSource::from(code).boxed())
+    }
+
+    fn generate_css(
+        &self,
+        source: &BoxSource,
+        generate_context: &mut GenerateContext,
+    ) -> Result<Option<BoxSource>> {
+        let mut source = ReplaceSource::new(source.clone());
+        let mut init_fragments = vec![];
+        let module_graph = generate_context.code_generation_context.module_graph;
+        for dependency_id in generate_context.module_dependencies {
+            if let Some(dependency) = module_graph
+                .dependency_by_id(*dependency_id)
+                .as_dependency_template()
+            {
+                dependency.apply(
+                    &mut source,
+                    &mut TemplateContext {
+                        code_generation_context: generate_context.code_generation_context,
+                        dependency_id: Some(*dependency_id),
+                        init_fragments: &mut init_fragments,
+                        runtime_requirements: generate_context.runtime_requirements,
+                    },
+                );
+            }
+        }
+        for dependency in generate_context.presentational_dependencies {
+            dependency.apply(
+                &mut source,
+                &mut TemplateContext {
+                    code_generation_context: generate_context.code_generation_context,
+                    dependency_id: None,
+                    init_fragments: &mut init_fragments,
+                    runtime_requirements: generate_context.runtime_requirements,
+                },
+            );
+        }
+        Ok(Some(source.boxed()))
+    }
+}
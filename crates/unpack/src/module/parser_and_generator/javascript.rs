@@ -0,0 +1,113 @@
+use rspack_sources::{BoxSource, ConcatSource, RawSource, ReplaceSource, SourceExt};
+
+use super::{GenerateContext, ParseContext, ParserAndGenerator};
+use crate::dependency::TemplateContext;
+use crate::errors::miette::Result;
+use crate::module::{
+    render_init_fragments, CodeGenerationContext, NormalModule, ParseResult,
+};
+use crate::optimize::ConcatenatedModule;
+use crate::runtime::RuntimeGlobals;
+
+#[derive(Debug, Default)]
+pub struct JavaScriptParserAndGenerator;
+
+impl ParserAndGenerator for JavaScriptParserAndGenerator {
+    fn parse(&mut self, parse_context: ParseContext) -> Result<ParseResult> {
+        NormalModule::parse(
+            parse_context.source.source().into_owned(),
+            parse_context.resource_path,
+            parse_context.options,
+        )
+    }
+
+    fn generate(
+        &self,
+        source: &BoxSource,
+        generate_context: &mut GenerateContext,
+    ) -> Result<BoxSource> {
+        let mut source = ReplaceSource::new(source.clone());
+        let mut init_fragments = vec![];
+        let module_graph = generate_context.code_generation_context.module_graph;
+        for dependency_id in generate_context.module_dependencies {
+            if let Some(dependency) = module_graph
+                .dependency_by_id(*dependency_id)
+                .as_dependency_template()
+            {
+                dependency.apply(
+                    &mut source,
+                    &mut TemplateContext {
+                        code_generation_context: generate_context.code_generation_context,
+                        dependency_id: Some(*dependency_id),
+                        init_fragments: &mut init_fragments,
+                        runtime_requirements: generate_context.runtime_requirements,
+                    },
+                );
+            }
+        }
+        for dependency in generate_context.presentational_dependencies {
+            dependency.apply(
+                &mut source,
+                &mut TemplateContext {
+                    code_generation_context: generate_context.code_generation_context,
+                    dependency_id: None,
+                    init_fragments: &mut init_fragments,
+                    runtime_requirements: generate_context.runtime_requirements,
+                },
+            );
+        }
+        let code_generation_context = generate_context.code_generation_context;
+        if let Some(concatenated_module) = code_generation_context.concatenated_module {
+            rename_top_level_bindings(&mut source, code_generation_context, concatenated_module);
+        }
+        let source = render_init_fragments(source.boxed(), init_fragments);
+        if !code_generation_context
+            .module_graph
+            .is_async(code_generation_context.module_id)
+        {
+            return Ok(source);
+        }
+        // evaluated asynchronously, importers wait for the settled exports
+        generate_context
+            .runtime_requirements
+            .insert(RuntimeGlobals::ASYNC_MODULE);
+        Ok(ConcatSource::new([
+            RawSource::from(
+                "__unpack_require__.a(__unpack_module__, async (__unpack_async_dependency__, __unpack_async_result__) => { try {\n",
+            )
+            .boxed(),
+            source,
+            RawSource::from(
+                "\n__unpack_async_result__();\n} catch (e) { __unpack_async_result__(e); } });",
+            )
+            .boxed(),
+        ])
+        .boxed())
+    }
+}
+
+/// rename the top level bindings of a module which conflict with the ones of other modules
+/// concatenated into the same scope
+fn rename_top_level_bindings(
+    source: &mut ReplaceSource<BoxSource>,
+    code_generation_context: &CodeGenerationContext,
+    concatenated_module: &ConcatenatedModule,
+) {
+    let module_id = code_generation_context.module_id;
+    let build_meta = code_generation_context
+        .module_graph
+        .module_by_id(module_id)
+        .build_meta();
+    for reference in &build_meta.top_level_references {
+        let name = concatenated_module.binding_name(module_id, &reference.name);
+        if name == reference.name.as_str() {
+            continue;
+        }
+        let content = if reference.shorthand {
+            format!("{}: {name}", reference.name)
+        } else {
+            name.to_string()
+        };
+        source.replace(reference.start, reference.end, &content, None);
+    }
+}
@@ -0,0 +1,76 @@
+use miette::{IntoDiagnostic, NamedSource};
+use rspack_sources::{BoxSource, ConcatSource, RawSource, SourceExt};
+
+use super::{GenerateContext, ParseContext, ParserAndGenerator};
+use crate::errors::miette::Result;
+use crate::errors::JsonParseError;
+use crate::module::{BuildMeta, ExportsType, ParseResult};
+
+#[derive(Debug, Default)]
+pub struct JsonParserAndGenerator;
+
+/// convert the 1-based line and column of serde_json into a byte offset,
+/// column 0 means the error is caused by the line break at the end of previous line
+fn offset_of(source: &str, line: usize, column: usize) -> usize {
+    let line_start = source
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(|line| line.len())
+        .sum::<usize>();
+    let offset = if column == 0 {
+        line_start.saturating_sub(1)
+    } else {
+        line_start + column - 1
+    };
+    offset.min(source.len())
+}
+
+impl ParserAndGenerator for JsonParserAndGenerator {
+    fn parse(&mut self, parse_context: ParseContext) -> Result<ParseResult> {
+        let source = parse_context.source.source();
+        let content = source.strip_prefix('\u{feff}').unwrap_or(&source);
+        if let Err(error) = serde_json::from_str::<serde_json::Value>(content) {
+            let offset =
+                offset_of(content, error.line(), error.column()) + (source.len() - content.len());
+            let message = error.to_string();
+            let position = format!(" at line {} column {}", error.line(), error.column());
+            let message = message
+                .strip_suffix(&position)
+                .unwrap_or(&message)
+                .to_string();
+            return Err(JsonParseError {
+                source_code: NamedSource::new(parse_context.resource_path.as_str(), source.to_string()),
+                span: offset.into(),
+                message,
+            }
+            .into());
+        }
+        Ok(ParseResult {
+            module_dependencies: vec![],
+            presentational_dependencies: vec![],
+            build_meta: BuildMeta {
+                exports_type: ExportsType::DefaultWithNamed,
+                ..Default::default()
+            },
+            warnings: vec![],
+        })
+    }
+
+    fn generate(
+        &self,
+        source: &BoxSource,
+        _generate_context: &mut GenerateContext,
+    ) -> Result<BoxSource> {
+        let source = source.source();
+        let json = source.strip_prefix('\u{feff}').unwrap_or(&source).trim();
+        // an object literal would set the prototype by a `"__proto__"` key, `JSON.parse` keeps it
+        // as an own property
+        let quoted = serde_json::to_string(json).into_diagnostic()?;
+        Ok(ConcatSource::new([
+            RawSource::from("module.exports = JSON.parse("),
+            RawSource::from(quoted),
+            RawSource::from(");"),
+        ])
+        .boxed())
+    }
+}
@@ -0,0 +1,94 @@
+use async_trait::async_trait;
+use rspack_sources::{BoxSource, RawSource, SourceExt};
+
+use crate::dependency::{AsyncDependenciesBlockId, DependenciesBlock, DependencyId};
+use crate::errors::miette::Result;
+
+use super::{
+    BuildContext, BuildMeta, BuildResult, CodeGenerationContext, CodeGenerationResult, Module,
+};
+use crate::runtime::RuntimeGlobals;
+
+/// module whose code is known ahead of time, e.g. a module ignored by `alias: false`
+/// or by the `browser` field of package.json, it never touches the file system
+#[derive(Debug)]
+pub struct RawModule {
+    source: BoxSource,
+    identifier: String,
+    readable_identifier: String,
+    module_dependencies: Vec<DependencyId>,
+    blocks: Vec<AsyncDependenciesBlockId>,
+    build_meta: BuildMeta,
+}
+
+impl RawModule {
+    pub fn new(source: String, identifier: String, readable_identifier: String) -> Self {
+        Self {
+            source: RawSource::from(source).boxed(),
+            identifier,
+            readable_identifier,
+            module_dependencies: vec![],
+            blocks: vec![],
+            build_meta: Default::default(),
+        }
+    }
+    /// empty module for the request which is resolved to `false`
+    pub fn ignored(context: &str, request: &str) -> Self {
+        Self::new(
+            "/* (ignored) */ module.exports = {};".to_string(),
+            format!("ignored|{context}|{request}"),
+            format!("{request} (ignored)"),
+        )
+    }
+    pub fn readable_identifier(&self) -> &str {
+        &self.readable_identifier
+    }
+}
+
+impl DependenciesBlock for RawModule {
+    fn add_block_id(&mut self, block_id: AsyncDependenciesBlockId) {
+        self.blocks.push(block_id);
+    }
+
+    fn get_blocks(&self) -> Vec<AsyncDependenciesBlockId> {
+        self.blocks.clone()
+    }
+
+    fn add_dependency_id(&mut self, dependency_id: DependencyId) {
+        self.module_dependencies.push(dependency_id);
+    }
+
+    fn get_dependencies(&self) -> Vec<DependencyId> {
+        self.module_dependencies.clone()
+    }
+}
+
+#[async_trait]
+impl Module for RawModule {
+    fn identifier(&self) -> &str {
+        &self.identifier
+    }
+    async fn build(&mut self, _build_context: BuildContext) -> Result<BuildResult> {
+        Ok(BuildResult {
+            module_dependencies: vec![],
+            diagnostics: vec![],
+        })
+    }
+    fn build_meta(&self) -> &BuildMeta {
+        &self.build_meta
+    }
+    fn size(&self) -> usize {
+        self.source.size()
+    }
+    fn code_generation(
+        &self,
+        _code_generation_context: CodeGenerationContext,
+    ) -> Result<CodeGenerationResult> {
+        Ok(CodeGenerationResult {
+            source: self.source.clone(),
+            css: None,
+            runtime_requirements: RuntimeGlobals::default(),
+            assets: vec![],
+        })
+    }
+}
@@ -0,0 +1,160 @@
+use camino::Utf8Path;
+use miette::{miette, IntoDiagnostic};
+use rspack_sources::SourceMap;
+use swc_core::common::comments::SingleThreadedComments;
+use swc_core::common::source_map::SourceMapGenConfig;
+use swc_core::common::{sync::Lrc, FileName, Mark, GLOBALS};
+use swc_core::ecma::ast::{EsVersion, JSXElement, JSXFragment, Program};
+use swc_core::ecma::codegen::{text_writer::JsWriter, Config, Emitter};
+use swc_core::ecma::transforms::base::{fixer::fixer, hygiene::hygiene, resolver};
+use swc_core::ecma::transforms::react::{self, react, Runtime};
+use swc_core::ecma::transforms::typescript::{strip, tsx, TsxConfig};
+use swc_core::ecma::visit::{Visit, VisitMutWith, VisitWith};
+
+use super::ast::{parse_program, syntax_by_path};
+use crate::compiler::{JsxOptions, JsxRuntime};
+use crate::errors::miette::Result;
+
+/// compile modules which can't be analyzed as plain javascript (e.g. typescript, jsx) into javascript
+/// and its source map if `source_map` is set, returns `None` when the module doesn't need any transformation
+pub fn transform(
+    content: &str,
+    resource_path: &Utf8Path,
+    jsx_options: &JsxOptions,
+    source_map: bool,
+) -> Result<Option<(String, Option<SourceMap>)>> {
+    let syntax = syntax_by_path(resource_path);
+    // every jsx element or fragment starts with `<`, whether there is any is decided by the ast
+    let maybe_jsx = syntax.jsx() && content.contains('<');
+    if !syntax.typescript() && !maybe_jsx {
+        return Ok(None);
+    }
+    let cm: Lrc<swc_core::common::SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        Lrc::new(FileName::Real(resource_path.as_std_path().to_path_buf())),
+        content.to_string(),
+    );
+    let comments = SingleThreadedComments::default();
+    let mut program = parse_program(&fm, syntax, Some(&comments))?;
+    let has_jsx = maybe_jsx && contains_jsx(&program);
+    if !syntax.typescript() && !has_jsx {
+        return Ok(None);
+    }
+
+    GLOBALS.set(&Default::default(), || {
+        let unresolved_mark = Mark::new();
+        let top_level_mark = Mark::new();
+        program.visit_mut_with(&mut resolver(
+            unresolved_mark,
+            top_level_mark,
+            syntax.typescript(),
+        ));
+        if syntax.typescript() && has_jsx {
+            // the pragma is referenced only after the jsx transform, keep its import
+            program.visit_mut_with(&mut tsx(
+                cm.clone(),
+                Default::default(),
+                TsxConfig {
+                    pragma: Some(jsx_options.pragma.clone()),
+                    pragma_frag: Some(jsx_options.pragma_frag.clone()),
+                },
+                &comments,
+                unresolved_mark,
+                top_level_mark,
+            ));
+        } else if syntax.typescript() {
+            // strip also elides imports which are only used as types
+            program.visit_mut_with(&mut strip(unresolved_mark, top_level_mark));
+        }
+        if has_jsx {
+            // the automatic runtime injects `import { jsx } from "<import_source>/jsx-runtime"`,
+            // which is scanned as a normal import afterwards
+            program.visit_mut_with(&mut react(
+                cm.clone(),
+                Some(&comments),
+                to_swc_jsx_options(jsx_options),
+                top_level_mark,
+                unresolved_mark,
+            ));
+        }
+        program.visit_mut_with(&mut hygiene());
+        program.visit_mut_with(&mut fixer(Some(&comments)));
+    });
+
+    print(&program, cm, &comments, source_map).map(Some)
+}
+
+fn to_swc_jsx_options(options: &JsxOptions) -> react::Options {
+    react::Options {
+        runtime: Some(match options.runtime {
+            JsxRuntime::Classic => Runtime::Classic,
+            JsxRuntime::Automatic => Runtime::Automatic,
+        }),
+        pragma: Some(options.pragma.clone()),
+        pragma_frag: Some(options.pragma_frag.clone()),
+        import_source: Some(options.import_source.clone()),
+        development: Some(options.development),
+        ..Default::default()
+    }
+}
+
+#[derive(Default)]
+struct JsxFinder {
+    found: bool,
+}
+
+impl Visit for JsxFinder {
+    fn visit_jsx_element(&mut self, _: &JSXElement) {
+        self.found = true;
+    }
+    fn visit_jsx_fragment(&mut self, _: &JSXFragment) {
+        self.found = true;
+    }
+}
+
+fn contains_jsx(program: &Program) -> bool {
+    let mut finder = JsxFinder::default();
+    program.visit_with(&mut finder);
+    finder.found
+}
+
+/// keep the original code in the source map, the map is composed into the map of the chunk
+struct SourceMapConfig;
+
+impl SourceMapGenConfig for SourceMapConfig {
+    fn file_name_to_source(&self, f: &FileName) -> String {
+        f.to_string()
+    }
+    fn inline_sources_content(&self, _f: &FileName) -> bool {
+        true
+    }
+}
+
+fn print(
+    program: &Program,
+    cm: Lrc<swc_core::common::SourceMap>,
+    comments: &SingleThreadedComments,
+    source_map: bool,
+) -> Result<(String, Option<SourceMap>)> {
+    let mut buf = vec![];
+    let mut mappings = vec![];
+    {
+        let mut emitter = Emitter {
+            cfg: Config::default().with_target(EsVersion::latest()),
+            cm: cm.clone(),
+            comments: Some(comments),
+            wr: JsWriter::new(cm.clone(), "\n", &mut buf, source_map.then_some(&mut mappings)),
+        };
+        emitter.emit_program(program).into_diagnostic()?;
+    }
+    let code = String::from_utf8(buf).into_diagnostic()?;
+    if !source_map {
+        return Ok((code, None));
+    }
+    let mut map = vec![];
+    cm.build_source_map_with_config(&mappings, None, SourceMapConfig)
+        .to_writer(&mut map)
+        .into_diagnostic()?;
+    let map = SourceMap::from_slice(&map).map_err(|err| miette!("{err}"))?;
+    Ok((code, Some(map)))
+}
@@ -1,8 +1,9 @@
 use crate::{
     compiler::CompilerOptions,
     dependency::BoxDependency,
-    module::NormalModule,
+    module::{BoxModule, FactoryMeta, ModuleType, NormalModule, RawModule},
     plugin::{ResolveArgs, PluginDriver},
+    resolver::{ResolveError, ResolveResult},
     resolver_factory::ResolverFactory,
 };
 use camino::Utf8PathBuf;
@@ -24,7 +25,7 @@ pub struct ModuleFactoryCreateData {
 
 #[derive(Debug)]
 pub struct ModuleFactoryResult {
-    pub module: NormalModule,
+    pub module: BoxModule,
 }
 impl NormalModuleFactory {
     pub async fn create(
@@ -39,20 +40,62 @@ impl NormalModuleFactory {
             context: context.clone(),
             path: Utf8PathBuf::from_str(request).unwrap(),
         }).await?;
-        let resource_path = match load_result {
-            Some(res) => Utf8PathBuf::from(res),
+        let resolve_result = match load_result {
+            Some(res) => res,
             None => {
                 let resolve_result = self
                     .resolver_factory
                     .base_resolver
-                    .resolve(&context, request)
-                    .into_diagnostic()?;
-                
-                resolve_result.path
+                    .resolve(&context, request);
+                match resolve_result {
+                    Ok(resolve_result) => resolve_result,
+                    // `alias: false` or `browser: { "x": false }`
+                    Err(ResolveError::Ignored(_)) => {
+                        let module = RawModule::ignored(context.as_str(), request);
+                        return Ok(ModuleFactoryResult {
+                            module: Box::new(module),
+                        });
+                    }
+                    Err(err) => return Err(err).into_diagnostic(),
+                }
             }
         };
+        let ResolveResult {
+            path: resource_path,
+            query: resource_query,
+            mut side_effects,
+        } = resolve_result;
 
-        let module = NormalModule::new(request.to_string(), resource_path);
-        Ok(ModuleFactoryResult { module })
+        let mut module_type = dependency
+            .module_type()
+            .unwrap_or_else(|| ModuleType::from_path(&resource_path));
+        let mut loaders = vec![];
+        for rule in &self.options.module.rules {
+            if rule.matches(resource_path.as_str(), &resource_query) {
+                if let Some(rule_module_type) = rule.module_type {
+                    module_type = rule_module_type;
+                }
+                if let Some(rule_side_effects) = rule.side_effects {
+                    side_effects = Some(rule_side_effects);
+                }
+                loaders.extend(rule.loaders.iter().cloned());
+            }
+        }
+        let module = NormalModule::new(
+            request.to_string(),
+            resource_path,
+            resource_query,
+            module_type,
+            loaders,
+            // the flag is ignored unless `optimization.side_effects` is enabled, so that every
+            // module is evaluated as written
+            FactoryMeta {
+                side_effect_free: self.options.optimization.side_effects
+                    && side_effects == Some(false),
+            },
+        );
+        Ok(ModuleFactoryResult {
+            module: Box::new(module),
+        })
     }
 }
@@ -0,0 +1,6 @@
+mod minimize;
+mod module_concatenation;
+mod split_chunks;
+pub use minimize::*;
+pub use module_concatenation::*;
+pub use split_chunks::*;
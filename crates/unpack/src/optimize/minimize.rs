@@ -0,0 +1,150 @@
+use indexmap::IndexSet;
+use miette::{miette, IntoDiagnostic, Result};
+use rspack_sources::{
+    BoxSource, ConcatSource, MapOptions, RawSource, SourceExt, SourceMap, SourceMapSource,
+    SourceMapSourceOptions,
+};
+use swc_core::common::comments::{Comment, CommentKind, SingleThreadedComments};
+use swc_core::common::source_map::SourceMapGenConfig;
+use swc_core::common::{sync::Lrc, FileName, Mark, GLOBALS};
+use swc_core::ecma::ast::EsVersion;
+use swc_core::ecma::codegen::{text_writer::JsWriter, Config, Emitter};
+use swc_core::ecma::minifier::optimize;
+use swc_core::ecma::minifier::option::terser::TerserCompressorOptions;
+use swc_core::ecma::minifier::option::{ExtraOptions, MangleOptions, MinifyOptions};
+use swc_core::ecma::parser::{EsSyntax, Syntax};
+use swc_core::ecma::transforms::base::{fixer::fixer, resolver};
+use swc_core::ecma::visit::VisitMutWith;
+
+use crate::compiler::MinimizerOptions;
+use crate::module::parse_program;
+
+/// minify the rendered code of a chunk, the source map of the result maps to the original
+/// sources of the chunk if `source_map` is set. license comments are moved to the top of the
+/// result since the minifier may drop the nodes they are attached to
+pub fn minify(
+    source: BoxSource,
+    filename: &str,
+    options: &MinimizerOptions,
+    source_map: bool,
+) -> Result<BoxSource> {
+    let code = source.source();
+    let cm: Lrc<swc_core::common::SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        Lrc::new(FileName::Custom(filename.to_string())),
+        code.to_string(),
+    );
+    let comments = SingleThreadedComments::default();
+    let program = parse_program(&fm, Syntax::Es(EsSyntax::default()), Some(&comments))?;
+    let license_comments = license_comments(comments);
+
+    let program = GLOBALS.set(&Default::default(), || {
+        let mut program = program;
+        let unresolved_mark = Mark::new();
+        let top_level_mark = Mark::new();
+        program.visit_mut_with(&mut resolver(unresolved_mark, top_level_mark, false));
+        let minify_options = MinifyOptions {
+            compress: options
+                .compress
+                .then(|| TerserCompressorOptions::default().into_config(cm.clone())),
+            mangle: options.mangle.then(MangleOptions::default),
+            ..Default::default()
+        };
+        let mut program = optimize(
+            program,
+            cm.clone(),
+            None,
+            None,
+            &minify_options,
+            &ExtraOptions {
+                unresolved_mark,
+                top_level_mark,
+                mangle_name_cache: None,
+            },
+        );
+        program.visit_mut_with(&mut fixer(None));
+        program
+    });
+
+    let mut buf = vec![];
+    let mut mappings = vec![];
+    {
+        let mut emitter = Emitter {
+            cfg: Config::default()
+                .with_target(EsVersion::latest())
+                .with_minify(true),
+            cm: cm.clone(),
+            comments: None,
+            wr: JsWriter::new(
+                cm.clone(),
+                "\n",
+                &mut buf,
+                source_map.then_some(&mut mappings),
+            ),
+        };
+        emitter.emit_program(&program).into_diagnostic()?;
+    }
+    let mut minified = String::from_utf8(buf).into_diagnostic()?;
+    minified.push('\n');
+    let minified = if source_map {
+        let mut map = vec![];
+        cm.build_source_map_with_config(&mappings, None, ChunkSourceMapConfig)
+            .to_writer(&mut map)
+            .into_diagnostic()?;
+        let map = SourceMap::from_slice(&map).map_err(|err| miette!("{err}"))?;
+        SourceMapSource::new(SourceMapSourceOptions {
+            value: minified,
+            name: filename.to_string(),
+            source_map: map,
+            original_source: Some(code.to_string()),
+            inner_source_map: source.map(&MapOptions::new(true)),
+            remove_original_source: true,
+        })
+        .boxed()
+    } else {
+        RawSource::from(minified).boxed()
+    };
+    if license_comments.is_empty() {
+        return Ok(minified);
+    }
+    Ok(ConcatSource::new([RawSource::from(license_comments).boxed(), minified]).boxed())
+}
+
+/// license comments of the chunk in source order without duplicates, one per line,
+/// the same as terser's `comments: "some"`
+fn license_comments(comments: SingleThreadedComments) -> String {
+    let (leading, trailing) = comments.take_all();
+    let mut comments = leading
+        .take()
+        .into_values()
+        .chain(trailing.take().into_values())
+        .flatten()
+        .filter(is_license_comment)
+        .collect::<Vec<_>>();
+    comments.sort_by_key(|comment| comment.span.lo);
+    comments
+        .into_iter()
+        .map(|comment| match comment.kind {
+            CommentKind::Block => format!("/*{}*/\n", comment.text),
+            CommentKind::Line => format!("//{}\n", comment.text),
+        })
+        .collect::<IndexSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// `/*! ... */` or comments with `@license` or `@preserve`
+fn is_license_comment(comment: &Comment) -> bool {
+    (comment.kind == CommentKind::Block && comment.text.starts_with('!'))
+        || comment.text.contains("@license")
+        || comment.text.contains("@preserve")
+}
+
+/// the map is composed with the map of the rendered chunk, which carries the original sources
+struct ChunkSourceMapConfig;
+
+impl SourceMapGenConfig for ChunkSourceMapConfig {
+    fn file_name_to_source(&self, f: &FileName) -> String {
+        f.to_string()
+    }
+}
@@ -0,0 +1,776 @@
+use camino::Utf8Path;
+use indexmap::IndexSet;
+use rspack_sources::{ConcatSource, RawSource, SourceExt};
+use rustc_hash::{FxHashMap, FxHashSet};
+use swc_core::atoms::Atom;
+
+use crate::chunk::ChunkGraph;
+use crate::compiler::CompilerOptions;
+use crate::dependency::{
+    harmony_import_access, harmony_namespace_cache_name, DependencyId, DEFAULT_EXPORT,
+};
+use crate::errors::miette::Result;
+use crate::module::{
+    CodeGenerationContext, CodeGenerationResult, ExportsType, ModuleGraph, ModuleId,
+};
+use crate::runtime::RuntimeGlobals;
+use crate::utils::identifier::{contextify, is_identifier, quoted, to_identifier};
+
+/// names which are declared by the function wrapping the root module
+const RESERVED_NAMES: [&str; 3] = [
+    "__unpack_module__",
+    "__unpack_exports__",
+    "__unpack_require__",
+];
+
+/// a module evaluated by a concatenated module, in the order of evaluation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConcatenatedItem {
+    /// merged into the scope of the root module
+    Concatenated(ModuleId),
+    /// imported by `__unpack_require__`, e.g. commonjs modules and roots of other groups
+    External(ModuleId),
+}
+
+/// esm modules merged into the scope of the root module, the root replaces all of them in
+/// the chunk, similar with webpack's ConcatenatedModule
+#[derive(Debug)]
+pub struct ConcatenatedModule {
+    pub root: ModuleId,
+    pub items: Vec<ConcatenatedItem>,
+    modules: FxHashSet<ModuleId>,
+    /// top level bindings which are renamed to avoid conflicts
+    names: FxHashMap<(ModuleId, Atom), String>,
+    namespace_object_names: FxHashMap<ModuleId, String>,
+    external_names: FxHashMap<ModuleId, String>,
+}
+
+impl ConcatenatedModule {
+    pub fn contains(&self, module_id: ModuleId) -> bool {
+        self.modules.contains(&module_id)
+    }
+    /// modules merged into the root except the root itself
+    pub fn inner_modules(&self) -> impl Iterator<Item = ModuleId> + '_ {
+        self.items.iter().filter_map(|item| match item {
+            ConcatenatedItem::Concatenated(module_id) if *module_id != self.root => {
+                Some(*module_id)
+            }
+            _ => None,
+        })
+    }
+    /// final name of a top level binding of a module in the group
+    pub fn binding_name<'a>(&'a self, module_id: ModuleId, name: &'a str) -> &'a str {
+        self.names
+            .get(&(module_id, Atom::from(name)))
+            .map_or(name, |name| name.as_str())
+    }
+    /// variable of the namespace object of a module in the group, which is only declared if
+    /// the exports object of the module is used
+    pub fn namespace_object_name(&self, module_id: ModuleId) -> &str {
+        if module_id == self.root {
+            return "__unpack_exports__";
+        }
+        &self.namespace_object_names[&module_id]
+    }
+    /// variable which holds the exports of a module imported by `__unpack_require__`
+    pub fn external_name(&self, module_id: ModuleId) -> Option<&str> {
+        self.external_names
+            .get(&module_id)
+            .map(|name| name.as_str())
+    }
+    /// expression of the export `id` (`None` for namespace) imported by the dependency,
+    /// taking the redirection of re-exports into account
+    pub fn import_expression(
+        &self,
+        module_graph: &ModuleGraph,
+        chunk_graph: &ChunkGraph,
+        dependency_id: DependencyId,
+        id: Option<&str>,
+        runtime_requirements: &mut RuntimeGlobals,
+    ) -> String {
+        let Some(module_id) = module_graph.get_module_id_by_dependency_id(dependency_id) else {
+            return "undefined".to_string();
+        };
+        let id = match module_graph.redirected_ids.get(&dependency_id) {
+            Some(redirected_id) => redirected_id.as_deref(),
+            None => id,
+        };
+        self.export_expression(
+            module_graph,
+            chunk_graph,
+            module_id,
+            id,
+            runtime_requirements,
+        )
+    }
+    /// expression of the export `id` (`None` for namespace) of a module, the bindings of the
+    /// modules in the group are referenced directly. `undefined` if the export is missing
+    pub fn export_expression(
+        &self,
+        module_graph: &ModuleGraph,
+        chunk_graph: &ChunkGraph,
+        module_id: ModuleId,
+        id: Option<&str>,
+        runtime_requirements: &mut RuntimeGlobals,
+    ) -> String {
+        self.resolve(
+            module_graph,
+            chunk_graph,
+            module_id,
+            id,
+            runtime_requirements,
+            &mut FxHashSet::default(),
+        )
+        .unwrap_or_else(|| "undefined".to_string())
+    }
+    /// generate the code of all the modules in the group, the result of the root contains the
+    /// code of all of them and the results of the other modules are only kept for their assets
+    pub fn code_generation(
+        &self,
+        options: &CompilerOptions,
+        module_graph: &ModuleGraph,
+        chunk_graph: &ChunkGraph,
+    ) -> Result<Vec<(ModuleId, CodeGenerationResult)>> {
+        let mut results = vec![];
+        let mut runtime_requirements = RuntimeGlobals::MAKE_NAMESPACE_OBJECT;
+        let mut assets = vec![];
+        let mut source = ConcatSource::default();
+        source.add(RawSource::from(
+            "__unpack_require__.r(__unpack_exports__);\n",
+        ));
+        source.add(RawSource::from(self.render_exports(
+            module_graph,
+            chunk_graph,
+            self.root,
+            &mut runtime_requirements,
+        )));
+        // namespace objects are only declared when the exports object of the module is used,
+        // their getters are lazy so they can be declared before the bindings
+        for module_id in self.inner_modules() {
+            if module_graph
+                .exports_info(module_id)
+                .is_some_and(|exports_info| exports_info.exports_object_used)
+            {
+                let name = self.namespace_object_name(module_id);
+                source.add(RawSource::from(format!(
+                    "var {name} = {{}};\n__unpack_require__.r({name});\n{}",
+                    self.render_exports(
+                        module_graph,
+                        chunk_graph,
+                        module_id,
+                        &mut runtime_requirements
+                    )
+                )));
+            }
+        }
+        for item in &self.items {
+            match *item {
+                ConcatenatedItem::External(module_id) => {
+                    runtime_requirements.insert(RuntimeGlobals::REQUIRE);
+                    let name = &self.external_names[&module_id];
+                    source.add(RawSource::from(format!(
+                        "var {name} = __unpack_require__({});\n",
+                        chunk_graph.get_module_id(module_id).to_js()
+                    )));
+                    // keeps the namespace object created on first access
+                    if module_graph
+                        .module_by_id(module_id)
+                        .build_meta()
+                        .exports_type
+                        != ExportsType::Namespace
+                    {
+                        source.add(RawSource::from(format!(
+                            "var {};\n",
+                            harmony_namespace_cache_name(name)
+                        )));
+                    }
+                }
+                ConcatenatedItem::Concatenated(module_id) => {
+                    let module = module_graph.module_by_id(module_id);
+                    let result = module.code_generation(CodeGenerationContext {
+                        options,
+                        module_graph,
+                        chunk_graph,
+                        module_id,
+                        concatenated_module: Some(self),
+                    })?;
+                    let identifier = contextify(module.identifier(), &options.context);
+                    source.add(RawSource::from(format!(
+                        "\n// CONCATENATED MODULE: {}\n",
+                        identifier.replace('\n', " ")
+                    )));
+                    source.add(result.source.clone());
+                    source.add(RawSource::from("\n"));
+                    runtime_requirements |= result.runtime_requirements;
+                    assets.extend(result.assets.iter().cloned());
+                    if module_id != self.root {
+                        results.push((module_id, result));
+                    }
+                }
+            }
+        }
+        results.push((
+            self.root,
+            CodeGenerationResult {
+                source: source.boxed(),
+                css: None,
+                runtime_requirements,
+                assets,
+            },
+        ));
+        Ok(results)
+    }
+    /// getters of the provided exports of a module on its exports object or namespace object,
+    /// the unused ones are left out
+    fn render_exports(
+        &self,
+        module_graph: &ModuleGraph,
+        chunk_graph: &ChunkGraph,
+        module_id: ModuleId,
+        runtime_requirements: &mut RuntimeGlobals,
+    ) -> String {
+        let Some(exports_info) = module_graph.exports_info(module_id) else {
+            return String::new();
+        };
+        let object = self.namespace_object_name(module_id);
+        let mut content = String::new();
+        for name in exports_info.provided.keys() {
+            if !exports_info.is_used(name) {
+                content += &format!(
+                    "/* unused harmony export {} */\n",
+                    name.replace("*/", "* /")
+                );
+                continue;
+            }
+            runtime_requirements.insert(RuntimeGlobals::DEFINE_PROPERTY_GETTERS);
+            content += &format!(
+                "__unpack_require__.d({object}, {{ {}: () => ({}) }});\n",
+                quoted(name),
+                self.export_expression(
+                    module_graph,
+                    chunk_graph,
+                    module_id,
+                    Some(name),
+                    runtime_requirements
+                )
+            );
+        }
+        content
+    }
+    fn resolve(
+        &self,
+        module_graph: &ModuleGraph,
+        chunk_graph: &ChunkGraph,
+        module_id: ModuleId,
+        id: Option<&str>,
+        runtime_requirements: &mut RuntimeGlobals,
+        visited: &mut FxHashSet<(ModuleId, Option<Atom>)>,
+    ) -> Option<String> {
+        if !self.contains(module_id) {
+            let exports_type = module_graph
+                .module_by_id(module_id)
+                .build_meta()
+                .exports_type;
+            let (var_name, namespace_cache) = match self.external_name(module_id) {
+                Some(var_name) => (
+                    var_name.to_string(),
+                    Some(harmony_namespace_cache_name(var_name)),
+                ),
+                None => (
+                    format!(
+                        "__unpack_require__({})",
+                        chunk_graph.get_module_id(module_id).to_js()
+                    ),
+                    None,
+                ),
+            };
+            return Some(harmony_import_access(
+                &var_name,
+                exports_type,
+                id,
+                namespace_cache.as_deref(),
+                runtime_requirements,
+            ));
+        }
+        let Some(id) = id else {
+            return Some(self.namespace_object_name(module_id).to_string());
+        };
+        let name = Atom::from(id);
+        // re-exports may be circular
+        if !visited.insert((module_id, Some(name.clone()))) {
+            return None;
+        }
+        let build_meta = module_graph.module_by_id(module_id).build_meta();
+        if let Some((_, local)) = build_meta
+            .local_exports
+            .iter()
+            .find(|(exported, _)| *exported == name)
+        {
+            return Some(self.binding_name(module_id, local).to_string());
+        }
+        let from_star = module_graph
+            .exports_info(module_id)?
+            .provided
+            .get(&name)?
+            .from_star;
+        let mgm_id = module_graph.module_id_to_module_graph_module_id[&module_id];
+        for &connection_id in &module_graph
+            .module_graph_module_by_id(mgm_id)
+            .outgoing_connections
+        {
+            let connection = module_graph.connection_by_id(connection_id);
+            let Some(dependency) = module_graph
+                .dependency_by_id(connection.dependency_id)
+                .as_module_dependency()
+            else {
+                continue;
+            };
+            let target = connection.resolved_module_id;
+            if from_star {
+                let provided = dependency.is_export_star()
+                    && module_graph
+                        .exports_info(target)
+                        .is_some_and(|exports_info| {
+                            exports_info.provided.contains_key(&name)
+                                || exports_info.other_exports_provided
+                        });
+                if provided {
+                    return self.resolve(
+                        module_graph,
+                        chunk_graph,
+                        target,
+                        Some(id),
+                        runtime_requirements,
+                        visited,
+                    );
+                }
+            } else if dependency.reexported_name() == Some(&name) {
+                let target_id = match module_graph.redirected_ids.get(&connection.dependency_id) {
+                    Some(redirected_id) => redirected_id.clone(),
+                    None => dependency.imported_id().cloned(),
+                };
+                return self.resolve(
+                    module_graph,
+                    chunk_graph,
+                    target,
+                    target_id.as_deref(),
+                    runtime_requirements,
+                    visited,
+                );
+            }
+        }
+        None
+    }
+}
+
+/// merge the esm modules of each chunk into the scope of a root module, similar with webpack's
+/// ModuleConcatenationPlugin. modules which can't be merged keep their function wrappers, the
+/// reasons are recorded as optimization bailouts of the module graph
+pub fn concatenate_modules(
+    module_graph: &mut ModuleGraph,
+    chunk_graph: &mut ChunkGraph,
+) -> Vec<ConcatenatedModule> {
+    // modules closer to entries are tried as roots first, so that they include the modules
+    // they import rather than being included
+    let chunk_modules = chunk_graph
+        .chunks
+        .indices()
+        .collect::<Vec<_>>()
+        .into_iter()
+        .flat_map(|chunk_id| chunk_graph.get_chunk_modules(chunk_id))
+        .collect::<IndexSet<_>>();
+    let mut concatenation = ModuleConcatenation {
+        module_graph,
+        chunk_graph,
+        bailouts: FxHashMap::default(),
+    };
+    let groups = concatenation.find_groups(chunk_modules);
+    let ModuleConcatenation { bailouts, .. } = concatenation;
+    let concatenated_modules = groups
+        .into_iter()
+        .map(|group| create_concatenated_module(module_graph, group))
+        .collect::<Vec<_>>();
+    for concatenated_module in &concatenated_modules {
+        for module_id in concatenated_module.inner_modules() {
+            for chunk_id in chunk_graph.get_module_chunks(module_id) {
+                chunk_graph.disconnect_chunk_and_module(chunk_id, module_id);
+            }
+        }
+    }
+    let concatenated = concatenated_modules
+        .iter()
+        .flat_map(|concatenated_module| concatenated_module.modules.iter().copied())
+        .collect::<FxHashSet<_>>();
+    for (module_id, reason) in bailouts {
+        if concatenated.contains(&module_id) {
+            continue;
+        }
+        let mgm_id = module_graph.module_graph_module_id_by_module_id(module_id);
+        module_graph
+            .module_graph_module_by_id_mut(mgm_id)
+            .optimization_bailout
+            .push(format!("ModuleConcatenation bailout: {reason}"));
+    }
+    concatenated_modules
+}
+
+struct ModuleConcatenation<'a> {
+    module_graph: &'a ModuleGraph,
+    chunk_graph: &'a ChunkGraph,
+    /// the first reason why a module can't be concatenated
+    bailouts: FxHashMap<ModuleId, String>,
+}
+
+impl ModuleConcatenation<'_> {
+    /// groups of modules which are concatenated, the first module of a group is the root
+    fn find_groups(&mut self, chunk_modules: IndexSet<ModuleId>) -> Vec<IndexSet<ModuleId>> {
+        let mut roots = IndexSet::new();
+        for module_id in chunk_modules {
+            match self.root_bailout(module_id) {
+                Some(reason) => {
+                    self.bailouts.insert(module_id, reason);
+                }
+                None => {
+                    roots.insert(module_id);
+                }
+            }
+        }
+        let mut used = FxHashSet::default();
+        let mut groups = vec![];
+        for root in roots.iter().copied() {
+            if used.contains(&root) {
+                continue;
+            }
+            let mut group = IndexSet::from([root]);
+            let mut failures = FxHashMap::default();
+            for target in self.imported_modules(root) {
+                if !used.contains(&target) {
+                    let _ = self.try_to_add(&roots, &mut group, target, &mut failures);
+                }
+            }
+            for (module_id, reason) in failures {
+                self.bailouts.entry(module_id).or_insert(reason);
+            }
+            if group.len() > 1 {
+                used.extend(group.iter().copied());
+                groups.push(group);
+            }
+        }
+        groups
+    }
+    /// why the module can't be concatenated at all
+    fn root_bailout(&self, module_id: ModuleId) -> Option<String> {
+        let build_meta = self.module_graph.module_by_id(module_id).build_meta();
+        if build_meta.exports_type != ExportsType::Namespace {
+            return Some("Module is not an ECMAScript module".to_string());
+        }
+        if let Some(reason) = &build_meta.module_concatenation_bailout {
+            return Some(format!("Module uses {reason}"));
+        }
+        if self.module_graph.is_async(module_id) {
+            return Some(
+                "Module is async, it uses top level await or imports an async module".to_string(),
+            );
+        }
+        if self
+            .module_graph
+            .exports_info(module_id)
+            .is_none_or(|exports_info| exports_info.other_exports_provided)
+        {
+            return Some("Module exports are unknown".to_string());
+        }
+        None
+    }
+    /// esm modules imported by the module through active connections in the order of evaluation
+    fn imported_modules(&self, module_id: ModuleId) -> Vec<ModuleId> {
+        imported_modules(self.module_graph, module_id)
+    }
+    /// add the module and all of its importers to the group, then try to add the modules it
+    /// imports. the group is restored if the module can't be added
+    fn try_to_add(
+        &self,
+        roots: &IndexSet<ModuleId>,
+        group: &mut IndexSet<ModuleId>,
+        module_id: ModuleId,
+        failures: &mut FxHashMap<ModuleId, String>,
+    ) -> Result<(), String> {
+        if group.contains(&module_id) {
+            return Ok(());
+        }
+        if let Some(reason) = failures.get(&module_id) {
+            return Err(reason.clone());
+        }
+        if let Err(reason) = self.check_inner(roots, group[0], module_id) {
+            failures.insert(module_id, reason.clone());
+            return Err(reason);
+        }
+        let len = group.len();
+        group.insert(module_id);
+        for importer in self.importers(module_id) {
+            if let Err(reason) = self.try_to_add(roots, group, importer, failures) {
+                group.truncate(len);
+                let identifier = self.module_graph.module_by_id(importer).identifier();
+                let reason = format!("Cannot concat with importer {identifier}: {reason}");
+                failures.insert(module_id, reason.clone());
+                return Err(reason);
+            }
+        }
+        for target in self.imported_modules(module_id) {
+            let _ = self.try_to_add(roots, group, target, failures);
+        }
+        Ok(())
+    }
+    /// why the module can't be merged into the group of `root`
+    fn check_inner(
+        &self,
+        roots: &IndexSet<ModuleId>,
+        root: ModuleId,
+        module_id: ModuleId,
+    ) -> Result<(), String> {
+        if !roots.contains(&module_id) {
+            return Err(self
+                .root_bailout(module_id)
+                .unwrap_or_else(|| "Module is not in any chunk".to_string()));
+        }
+        if !self
+            .chunk_graph
+            .get_module_entry_chunks(module_id)
+            .is_empty()
+        {
+            return Err("Module is an entry point".to_string());
+        }
+        if self.chunk_graph.get_module_chunks(module_id) != self.chunk_graph.get_module_chunks(root)
+        {
+            return Err(format!(
+                "Module is not in the same chunks as {}",
+                self.module_graph.module_by_id(root).identifier()
+            ));
+        }
+        let mgm_id = self.module_graph.module_id_to_module_graph_module_id[&module_id];
+        let unsupported = self
+            .module_graph
+            .module_graph_module_by_id(mgm_id)
+            .incoming_connections
+            .iter()
+            .filter(|&&connection_id| self.is_connection_included(connection_id))
+            .filter_map(|&connection_id| {
+                let connection = self.module_graph.connection_by_id(connection_id);
+                let is_harmony_import = self
+                    .module_graph
+                    .dependency_by_id(connection.dependency_id)
+                    .as_module_dependency()
+                    .is_some_and(|dependency| dependency.is_harmony_import());
+                match connection.origin_module_id {
+                    Some(_) if is_harmony_import => None,
+                    Some(origin_module_id) => Some(
+                        self.module_graph
+                            .module_by_id(origin_module_id)
+                            .identifier()
+                            .to_string(),
+                    ),
+                    None => Some("an entry".to_string()),
+                }
+            })
+            .collect::<IndexSet<_>>();
+        if !unsupported.is_empty() {
+            return Err(format!(
+                "Module is referenced from these modules with unsupported syntax: {}",
+                unsupported.into_iter().collect::<Vec<_>>().join(", ")
+            ));
+        }
+        Ok(())
+    }
+    /// connections whose modules are both emitted, the others are left out
+    fn is_connection_included(&self, connection_id: crate::module::ConnectionId) -> bool {
+        let connection = self.module_graph.connection_by_id(connection_id);
+        self.module_graph.is_connection_active(connection_id)
+            && connection.origin_module_id.is_none_or(|origin_module_id| {
+                self.chunk_graph.is_module_in_any_chunk(origin_module_id)
+            })
+    }
+    /// modules importing the module through active connections
+    fn importers(&self, module_id: ModuleId) -> IndexSet<ModuleId> {
+        let mgm_id = self.module_graph.module_id_to_module_graph_module_id[&module_id];
+        self.module_graph
+            .module_graph_module_by_id(mgm_id)
+            .incoming_connections
+            .iter()
+            .filter(|&&connection_id| self.is_connection_included(connection_id))
+            .filter_map(|&connection_id| {
+                self.module_graph
+                    .connection_by_id(connection_id)
+                    .origin_module_id
+            })
+            .collect()
+    }
+}
+
+/// esm modules imported by the module through active connections in the order of evaluation
+fn imported_modules(module_graph: &ModuleGraph, module_id: ModuleId) -> Vec<ModuleId> {
+    let Some(&mgm_id) = module_graph
+        .module_id_to_module_graph_module_id
+        .get(&module_id)
+    else {
+        return vec![];
+    };
+    let mut imports = module_graph
+        .module_graph_module_by_id(mgm_id)
+        .outgoing_connections
+        .iter()
+        .filter(|&&connection_id| module_graph.is_connection_active(connection_id))
+        .filter_map(|&connection_id| {
+            let connection = module_graph.connection_by_id(connection_id);
+            let source_order = module_graph
+                .dependency_by_id(connection.dependency_id)
+                .as_module_dependency()
+                .filter(|dependency| dependency.is_harmony_import())?
+                .source_order()?;
+            Some((source_order, connection.resolved_module_id))
+        })
+        .collect::<Vec<_>>();
+    imports.sort_by_key(|(source_order, _)| *source_order);
+    imports
+        .into_iter()
+        .map(|(_, module_id)| module_id)
+        .collect::<IndexSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+fn create_concatenated_module(
+    module_graph: &ModuleGraph,
+    group: IndexSet<ModuleId>,
+) -> ConcatenatedModule {
+    let root = group[0];
+    let mut items = vec![];
+    let mut visited = FxHashSet::default();
+    add_items(module_graph, &group, root, &mut items, &mut visited);
+
+    let modules = group.iter().copied().collect::<FxHashSet<_>>();
+    // a binding keeps its name unless any other module of the group uses the same name,
+    // as a global, a nested binding or a top level binding
+    let mut name_count = FxHashMap::<Atom, usize>::default();
+    let mut used_names = RESERVED_NAMES
+        .iter()
+        .map(|name| name.to_string())
+        .collect::<FxHashSet<_>>();
+    let mut bindings = vec![];
+    for &module_id in &group {
+        let build_meta = module_graph.module_by_id(module_id).build_meta();
+        let mut module_bindings = build_meta
+            .top_level_references
+            .iter()
+            .map(|reference| reference.name.clone())
+            .chain(
+                build_meta
+                    .local_exports
+                    .iter()
+                    .map(|(_, local)| local.clone()),
+            )
+            .collect::<IndexSet<_>>();
+        module_bindings.sort();
+        for name in build_meta.used_names.iter().chain(
+            module_bindings
+                .iter()
+                .filter(|name| *name == DEFAULT_EXPORT),
+        ) {
+            *name_count.entry(name.clone()).or_default() += 1;
+            used_names.insert(name.to_string());
+        }
+        bindings.push((module_id, module_bindings));
+    }
+    let mut names = FxHashMap::default();
+    let mut namespace_object_names = FxHashMap::default();
+    for (module_id, module_bindings) in bindings {
+        let stem = module_stem(module_graph, module_id);
+        for name in module_bindings {
+            if name_count.get(&name).copied().unwrap_or_default() <= 1
+                && !RESERVED_NAMES.contains(&name.as_str())
+            {
+                continue;
+            }
+            let renamed = if name == DEFAULT_EXPORT {
+                new_name(&mut used_names, format!("{stem}_default"))
+            } else {
+                new_name(&mut used_names, format!("{stem}_{name}"))
+            };
+            names.insert((module_id, name), renamed);
+        }
+        if module_id != root {
+            namespace_object_names.insert(
+                module_id,
+                new_name(&mut used_names, format!("{stem}_namespaceObject")),
+            );
+        }
+    }
+    // numbered in the order of items rather than by module ids, which are assigned later
+    let external_names = items
+        .iter()
+        .filter_map(|item| match item {
+            ConcatenatedItem::External(module_id) => Some(*module_id),
+            ConcatenatedItem::Concatenated(_) => None,
+        })
+        .enumerate()
+        .map(|(index, module_id)| {
+            let name = format!(
+                "{}__unpack_external_module_{index}__",
+                module_stem(module_graph, module_id)
+            );
+            (module_id, name)
+        })
+        .collect();
+    ConcatenatedModule {
+        root,
+        items,
+        modules,
+        names,
+        namespace_object_names,
+        external_names,
+    }
+}
+
+/// a name which is not used by any module of the group, e.g. `a_foo_1`
+fn new_name(used_names: &mut FxHashSet<String>, name: String) -> String {
+    let mut candidate = name.clone();
+    let mut index = 1;
+    while used_names.contains(&candidate) {
+        candidate = format!("{name}_{index}");
+        index += 1;
+    }
+    used_names.insert(candidate.clone());
+    candidate
+}
+
+/// modules in post order of imports, which is the order of evaluation
+fn add_items(
+    module_graph: &ModuleGraph,
+    group: &IndexSet<ModuleId>,
+    module_id: ModuleId,
+    items: &mut Vec<ConcatenatedItem>,
+    visited: &mut FxHashSet<ModuleId>,
+) {
+    if !visited.insert(module_id) {
+        return;
+    }
+    for target in imported_modules(module_graph, module_id) {
+        if group.contains(&target) {
+            add_items(module_graph, group, target, items, visited);
+        } else if visited.insert(target) {
+            items.push(ConcatenatedItem::External(target));
+        }
+    }
+    items.push(ConcatenatedItem::Concatenated(module_id));
+}
+
+/// readable part of the module path used in new names, e.g. `button` of `/src/button.mjs`
+fn module_stem(module_graph: &ModuleGraph, module_id: ModuleId) -> String {
+    let identifier = module_graph.module_by_id(module_id).identifier();
+    let path = identifier.rsplit('|').next().unwrap_or(identifier);
+    let path = path.split('?').next().unwrap_or(path);
+    let stem = to_identifier(Utf8Path::new(path).file_stem().unwrap_or(path));
+    if is_identifier(&stem) {
+        stem
+    } else {
+        format!("_{stem}")
+    }
+}
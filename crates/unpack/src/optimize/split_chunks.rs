@@ -0,0 +1,171 @@
+use indexmap::{IndexMap, IndexSet};
+use rustc_hash::FxHashMap;
+
+use crate::chunk::{ChunkGraph, ChunkId};
+use crate::compiler::SplitChunksOptions;
+use crate::errors::{Diagnostics, SplitChunksNameConflictWarning};
+use crate::module::{ModuleGraph, ModuleId, ModuleType};
+
+/// modules selected by a cache group which go to the same new chunk
+#[derive(Debug)]
+struct ChunksInfoItem {
+    /// key of the cache group
+    cache_group: String,
+    name: Option<String>,
+    priority: i32,
+    min_size: usize,
+    modules: IndexSet<ModuleId>,
+    /// chunks which contain the modules
+    chunks: IndexSet<ChunkId>,
+    size: usize,
+}
+
+/// move modules shared by chunks into new chunks selected by cache groups, the new chunks are
+/// loaded before the chunks they are split from, similar with webpack's SplitChunksPlugin.
+/// css modules are left in their chunks, since the css files of split chunks are not loaded
+pub fn split_chunks(
+    options: &SplitChunksOptions,
+    module_graph: &ModuleGraph,
+    chunk_graph: &mut ChunkGraph,
+    diagnostics: &mut Diagnostics,
+) {
+    let mut items: IndexMap<String, ChunksInfoItem> = IndexMap::default();
+    for module_id in module_graph.modules.indices() {
+        let chunks = chunk_graph.get_module_chunks(module_id);
+        // entry modules are evaluated by the chunks they are entries of
+        if chunks.is_empty() || !chunk_graph.get_module_entry_chunks(module_id).is_empty() {
+            continue;
+        }
+        let module = module_graph.module_by_id(module_id);
+        if matches!(
+            module.module_type(),
+            ModuleType::Css | ModuleType::CssModule
+        ) {
+            continue;
+        }
+        for (key, cache_group) in &options.cache_groups {
+            let matched = cache_group.test.as_ref().is_none_or(|test| {
+                module
+                    .name_for_condition()
+                    .is_some_and(|name| test.matches(name))
+            });
+            if !matched || chunks.len() < cache_group.min_chunks.unwrap_or(options.min_chunks) {
+                continue;
+            }
+            // modules of a named group go to the same chunk, others are grouped by their chunks
+            let item_key = match &cache_group.name {
+                Some(name) => name.clone(),
+                None => {
+                    let mut chunk_ids = chunks
+                        .iter()
+                        .map(|chunk_id| chunk_id.index())
+                        .collect::<Vec<_>>();
+                    chunk_ids.sort_unstable();
+                    format!("{key}|{chunk_ids:?}")
+                }
+            };
+            let item = items.entry(item_key).or_insert_with(|| ChunksInfoItem {
+                cache_group: key.clone(),
+                name: cache_group.name.clone(),
+                priority: cache_group.priority,
+                min_size: cache_group.min_size.unwrap_or(options.min_size),
+                modules: Default::default(),
+                chunks: Default::default(),
+                size: 0,
+            });
+            if item.modules.insert(module_id) {
+                item.size += module.size();
+                item.chunks.extend(chunks.iter().copied());
+            }
+        }
+    }
+
+    // chunks are only reused by items of the same name
+    let mut split_chunks = FxHashMap::default();
+    while let Some(index) = best_item(&items) {
+        let (_, item) = items.swap_remove_index(index).expect("should have item");
+        // a module selected by multiple groups goes to the best one only
+        for other in items.values_mut() {
+            for module_id in &item.modules {
+                if other.modules.shift_remove(module_id) {
+                    other.size -= module_graph.module_by_id(*module_id).size();
+                }
+            }
+        }
+        items.retain(|_, item| !item.modules.is_empty());
+        split_chunk(item, chunk_graph, &mut split_chunks, diagnostics);
+    }
+}
+
+/// the item with the highest priority, then the largest size, among items not smaller than
+/// their `min_size`
+fn best_item(items: &IndexMap<String, ChunksInfoItem>) -> Option<usize> {
+    items
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, item))| item.size >= item.min_size)
+        .max_by(|(_, (a_key, a)), (_, (b_key, b))| {
+            a.priority
+                .cmp(&b.priority)
+                .then(a.size.cmp(&b.size))
+                .then(a.chunks.len().cmp(&b.chunks.len()))
+                .then_with(|| b_key.cmp(a_key))
+        })
+        .map(|(index, _)| index)
+}
+
+fn split_chunk(
+    item: ChunksInfoItem,
+    chunk_graph: &mut ChunkGraph,
+    split_chunks: &mut FxHashMap<String, ChunkId>,
+    diagnostics: &mut Diagnostics,
+) {
+    // chunks of the modules which went to other items are left out
+    let chunks = item
+        .modules
+        .iter()
+        .flat_map(|&module_id| chunk_graph.get_module_chunks(module_id))
+        .collect::<IndexSet<_>>();
+    let name = item.name.unwrap_or_else(|| {
+        let chunk_names = chunks
+            .iter()
+            .filter_map(|&chunk_id| chunk_graph.chunk_by_id(chunk_id).name.as_deref())
+            .collect::<Vec<_>>();
+        format!("{}-{}", item.cache_group, chunk_names.join("-"))
+    });
+    let new_chunk_id = match split_chunks.get(&name) {
+        Some(&chunk_id) => chunk_id,
+        // entry and worker chunks are not merged with split chunks, their modules stay
+        None if chunk_graph.chunk_by_name(&name).is_some() => {
+            diagnostics.push(
+                SplitChunksNameConflictWarning {
+                    name,
+                    cache_group: item.cache_group,
+                }
+                .into(),
+            );
+            return;
+        }
+        None => {
+            let chunk_id = chunk_graph.create_chunk(Some(name.clone()));
+            split_chunks.insert(name, chunk_id);
+            chunk_id
+        }
+    };
+    for &module_id in &item.modules {
+        for chunk_id in chunk_graph.get_module_chunks(module_id) {
+            chunk_graph.disconnect_chunk_and_module(chunk_id, module_id);
+        }
+        chunk_graph.connect_chunk_and_module(new_chunk_id, module_id);
+    }
+    for chunk_id in chunks {
+        if chunk_id == new_chunk_id {
+            continue;
+        }
+        for chunk_group_id in chunk_graph.get_chunk_groups(chunk_id) {
+            chunk_graph
+                .chunk_group_by_id_mut(chunk_group_id)
+                .insert_chunk(new_chunk_id, chunk_id);
+        }
+    }
+}
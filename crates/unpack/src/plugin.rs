@@ -1,6 +1,7 @@
-use crate::{compilation::Compilation, compiler::CompilerOptions};
+use crate::{compilation::Compilation, compiler::CompilerOptions, resolver::ResolveResult};
 use camino::Utf8PathBuf;
 use miette::Result;
+use rspack_sources::SourceMap;
 use std::{cell::UnsafeCell, fmt::Debug, sync::Arc};
 use async_trait::async_trait;
 #[derive(Clone,Debug)]
@@ -17,6 +18,13 @@ pub struct ResolveArgs {
 pub struct LoadArgs {
     pub path: Utf8PathBuf
 }
+/// content of a module provided by a plugin instead of the file system
+#[derive(Debug,Clone)]
+pub struct LoadResult {
+    pub content: Vec<u8>,
+    /// map of `content` back to the original file, if the plugin compiled it
+    pub source_map: Option<SourceMap>
+}
 
 pub struct CompilationCell(UnsafeCell<Compilation>);
 
@@ -26,10 +34,14 @@ impl CompilationCell {
     }
 
     // Safe methods to access the compilation
+    /// # Safety
+    /// The caller must ensure no other reference to the compilation is alive.
     pub unsafe fn get(&self) -> * mut Compilation {
        self.0.get()
     }
 
+    /// # Safety
+    /// The caller must ensure no other reference to the compilation is alive.
     pub unsafe fn get_mut(&mut self) -> &mut Compilation {
         self.0.get_mut()
     }
@@ -47,10 +59,11 @@ pub trait Plugin: Send + Sync +  Debug {
     async fn this_compilation(&self, _ctx: Arc<PluginContext>, _compilation: Arc<CompilationCell>) {
         
     }
-    async fn resolve(&self, _ctx: Arc<PluginContext>, _args: ResolveArgs) -> Result<Option<String>> {
+    /// resolve the request instead of the resolver, `side_effects` of the result flags the module
+    async fn resolve(&self, _ctx: Arc<PluginContext>, _args: ResolveArgs) -> Result<Option<ResolveResult>> {
         Ok(None)
     }
-    async fn load(&self, _ctx: Arc<PluginContext>,_args: LoadArgs) -> Result<Option<Vec<u8>>>{
+    async fn load(&self, _ctx: Arc<PluginContext>,_args: LoadArgs) -> Result<Option<LoadResult>>{
         Ok(None)
     }
 }
@@ -63,7 +76,7 @@ pub struct PluginDriver {
     pub plugin_context: Arc<PluginContext>
 }
 impl PluginDriver {
-    pub async fn run_resolve_hook(&self,args:ResolveArgs)-> Result<Option<String>>{
+    pub async fn run_resolve_hook(&self,args:ResolveArgs)-> Result<Option<ResolveResult>>{
         for plugin in &self.plugins {
             let resolve = plugin.resolve(self.plugin_context.clone(), args.clone()).await?;
             if resolve.is_some() {
@@ -74,7 +87,7 @@ impl PluginDriver {
         }
         Ok(None)
     }
-    pub async fn run_load_hook(&self, args: LoadArgs) -> Result<Option<Vec<u8>>> {
+    pub async fn run_load_hook(&self, args: LoadArgs) -> Result<Option<LoadResult>> {
          for plugin in &self.plugins {
             let load_result = plugin.load(self.plugin_context.clone(), args.clone()).await?;
             if load_result.is_some() {
@@ -1,8 +1,9 @@
 use camino::{Utf8Path, Utf8PathBuf};
-pub use rspack_resolver::ResolveOptions;
-use rspack_resolver::{ResolveError as InnerResolverError, Resolver};
+pub use rspack_resolver::{AliasValue, ResolveOptions, TsconfigOptions, TsconfigReferences};
+use rspack_resolver::{PackageJson, ResolveError as InnerResolverError, Resolver};
+use serde_json::Value;
 
-use crate::utils::path::AssertUtf8;
+use crate::utils::{glob::glob_matches, path::AssertUtf8};
 
 #[derive(Debug)]
 pub struct UnpackResolver {
@@ -12,6 +13,10 @@ pub struct UnpackResolver {
 #[derive(Debug)]
 pub struct ResolveResult {
     pub path: Utf8PathBuf,
+    /// e.g. `?inline`, empty if the request has no query
+    pub query: String,
+    /// `false` if the module is side effect free, e.g. by the `sideEffects` field of package.json
+    pub side_effects: Option<bool>,
 }
 pub type ResolveError = InnerResolverError;
 impl UnpackResolver {
@@ -34,10 +39,38 @@ impl UnpackResolver {
         self.inner_resolver
             .resolve(context, request)
             .map(|resolution| {
-                let full_path = resolution.full_path();
+                let path = resolution.path().to_path_buf().assert_utf8();
+                let side_effects = resolution
+                    .package_json()
+                    .and_then(|package_json| side_effects_of_package_json(package_json, &path));
                 ResolveResult {
-                    path: full_path.assert_utf8(),
+                    path,
+                    query: resolution.query().unwrap_or_default().to_string(),
+                    side_effects,
                 }
             })
     }
 }
+
+/// `sideEffects` of package.json is either a boolean or globs of the files with side effects,
+/// globs without `/` match the file name in any directory, same as webpack
+fn side_effects_of_package_json(package_json: &PackageJson, path: &Utf8Path) -> Option<bool> {
+    let directory = Utf8Path::from_path(package_json.directory())?;
+    let relative_path = path.strip_prefix(directory).ok()?.as_str();
+    let matches = |glob: &Value| {
+        glob.as_str().is_some_and(|glob| {
+            let glob = glob.strip_prefix("./").unwrap_or(glob);
+            if glob.contains('/') {
+                glob_matches(glob, relative_path)
+            } else {
+                glob_matches(&format!("**/{glob}"), relative_path)
+            }
+        })
+    };
+    match package_json.side_effects.as_ref()? {
+        Value::Bool(side_effects) => Some(*side_effects),
+        glob @ Value::String(_) => Some(matches(glob)),
+        Value::Array(globs) => Some(globs.iter().any(matches)),
+        _ => None,
+    }
+}
@@ -0,0 +1,4 @@
+mod runtime_globals;
+mod runtime_template;
+pub use runtime_globals::*;
+pub use runtime_template::*;
@@ -0,0 +1,20 @@
+var unpackAsyncExports = typeof Symbol === "function" ? Symbol("unpack async exports") : "__unpack_async_exports__";
+var unpackAsyncLoading = typeof Symbol === "function" ? Symbol("unpack async loading") : "__unpack_async_loading__";
+__unpack_require__.a = (module, body) => {
+	var resolve, reject;
+	var promise = new Promise((res, rej) => {
+		resolve = res;
+		reject = rej;
+	});
+	var exports = module.exports;
+	promise[unpackAsyncExports] = exports;
+	// an async module imported while its synchronous part is running is in a cycle,
+	// waiting for it would never settle, use its exports directly as esm does
+	promise[unpackAsyncLoading] = true;
+	module.exports = promise;
+	body(
+		(dependency) => dependency[unpackAsyncLoading] ? dependency[unpackAsyncExports] : dependency,
+		(error) => (error ? reject(error) : resolve(exports))
+	);
+	promise[unpackAsyncLoading] = false;
+};
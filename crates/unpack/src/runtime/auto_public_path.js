@@ -0,0 +1,13 @@
+__unpack_require__.p = (function () {
+	var scriptUrl;
+	if (typeof document !== "undefined" && document.currentScript) {
+		scriptUrl = document.currentScript.src;
+	}
+	if (!scriptUrl && typeof self !== "undefined" && self.location) {
+		scriptUrl = self.location.href;
+	}
+	if (!scriptUrl) {
+		return "";
+	}
+	return scriptUrl.replace(/#.*$/, "").replace(/\?.*$/, "").replace(/\/[^\/]+$/, "/");
+})();
@@ -0,0 +1 @@
+__unpack_require__.b = (typeof document !== "undefined" && document.baseURI) || self.location.href;
@@ -0,0 +1,42 @@
+var installedChunks = {};
+var deferredStartups = [];
+var runDeferredStartups = function () {
+	deferredStartups = deferredStartups.filter(function (startup) {
+		var chunkIds = startup[0];
+		if (!chunkIds.every(function (chunkId) { return installedChunks[chunkId]; })) {
+			return true;
+		}
+		startup[1]();
+		return false;
+	});
+};
+// chunks register their modules by pushing to the global array, which may happen before
+// the runtime is evaluated, and pushes are forwarded to the runtimes of other entries
+var installChunk = function (parentPush, data) {
+	var chunkIds = data[0];
+	var moreModules = data[1];
+	for (var moduleId in moreModules) {
+		if (__unpack_require__.o(moreModules, moduleId)) {
+			__unpack_modules__[moduleId] = moreModules[moduleId];
+		}
+	}
+	if (parentPush) {
+		parentPush(data);
+	}
+	chunkIds.forEach(function (chunkId) {
+		installedChunks[chunkId] = true;
+	});
+	runDeferredStartups();
+};
+var chunkLoadingGlobal = (globalThis.__unpack_chunks__ = globalThis.__unpack_chunks__ || []);
+chunkLoadingGlobal.forEach(installChunk.bind(null, 0));
+chunkLoadingGlobal.push = installChunk.bind(null, chunkLoadingGlobal.push.bind(chunkLoadingGlobal));
+__unpack_require__.O = function (chunkIds, filenames, startup) {
+	deferredStartups.push([chunkIds, startup]);
+	chunkIds.forEach(function (chunkId, index) {
+		if (!installedChunks[chunkId]) {
+			loadScript(filenames[index]);
+		}
+	});
+	runDeferredStartups();
+};
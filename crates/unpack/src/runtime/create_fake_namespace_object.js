@@ -0,0 +1,20 @@
+__unpack_require__.t = function (value, mode) {
+	if (mode & 1 && value && value.__esModule) {
+		return value;
+	}
+	var ns = Object.create(null);
+	__unpack_require__.r(ns);
+	var definition = {};
+	if (value !== null && (typeof value === "object" || typeof value === "function")) {
+		Object.keys(value).forEach(function (key) {
+			definition[key] = function () {
+				return value[key];
+			};
+		});
+	}
+	definition["default"] = function () {
+		return value;
+	};
+	__unpack_require__.d(ns, definition);
+	return ns;
+};
@@ -0,0 +1,7 @@
+__unpack_require__.d = function (exports, definition) {
+	for (var key in definition) {
+		if (__unpack_require__.o(definition, key) && !__unpack_require__.o(exports, key)) {
+			Object.defineProperty(exports, key, { enumerable: true, get: definition[key] });
+		}
+	}
+};
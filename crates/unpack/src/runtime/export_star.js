@@ -0,0 +1,13 @@
+__unpack_require__.es = function (from, to) {
+	Object.keys(from).forEach(function (key) {
+		if (key !== "default" && !__unpack_require__.o(to, key)) {
+			Object.defineProperty(to, key, {
+				enumerable: true,
+				get: function () {
+					return from[key];
+				}
+			});
+		}
+	});
+	return from;
+};
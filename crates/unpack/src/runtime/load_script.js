@@ -0,0 +1,10 @@
+var loadScript = function (filename) {
+	var url = __unpack_require__.p + filename;
+	if (typeof importScripts === "function") {
+		importScripts(url);
+		return;
+	}
+	var script = document.createElement("script");
+	script.src = url;
+	document.head.appendChild(script);
+};
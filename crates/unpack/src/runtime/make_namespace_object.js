@@ -0,0 +1,6 @@
+__unpack_require__.r = function (exports) {
+	if (typeof Symbol !== "undefined" && Symbol.toStringTag) {
+		Object.defineProperty(exports, Symbol.toStringTag, { value: "Module" });
+	}
+	Object.defineProperty(exports, "__esModule", { value: true });
+};
@@ -0,0 +1 @@
+__unpack_require__.p = require("url").pathToFileURL(__dirname + "/").href;
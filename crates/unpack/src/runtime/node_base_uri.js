@@ -0,0 +1 @@
+__unpack_require__.b = require("url").pathToFileURL(__filename);
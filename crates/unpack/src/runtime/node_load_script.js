@@ -0,0 +1,3 @@
+var loadScript = function (filename) {
+	require(__dirname + "/" + filename);
+};
@@ -0,0 +1,12 @@
+var __unpack_module_cache__ = {};
+function __unpack_require__(moduleId) {
+	var cachedModule = __unpack_module_cache__[moduleId];
+	if (cachedModule !== undefined) {
+		return cachedModule.exports;
+	}
+	var module = (__unpack_module_cache__[moduleId] = {
+		exports: {}
+	});
+	__unpack_modules__[moduleId](module, module.exports, __unpack_require__);
+	return module.exports;
+}
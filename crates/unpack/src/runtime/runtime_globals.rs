@@ -0,0 +1,47 @@
+use bitflags::bitflags;
+
+bitflags! {
+    /// runtime helpers required by the generated code, similar with webpack's RuntimeGlobals
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct RuntimeGlobals: u32 {
+        /// `__unpack_require__`
+        const REQUIRE = 1 << 0;
+        /// `__unpack_require__.o`
+        const HAS_OWN_PROPERTY = 1 << 1;
+        /// `__unpack_require__.d`
+        const DEFINE_PROPERTY_GETTERS = 1 << 2;
+        /// `__unpack_require__.r`
+        const MAKE_NAMESPACE_OBJECT = 1 << 3;
+        /// `__unpack_require__.es`
+        const EXPORT_STAR = 1 << 4;
+        /// `__unpack_require__.p`
+        const PUBLIC_PATH = 1 << 5;
+        /// `__unpack_require__.b`, base url of `new URL()`
+        const BASE_URI = 1 << 6;
+        /// `__unpack_require__.a`, evaluates a module with top level await or async dependencies
+        const ASYNC_MODULE = 1 << 7;
+        /// `__unpack_require__.O`, loads the chunks split from the entry chunk before startup
+        const CHUNK_LOADING = 1 << 8;
+        /// `__unpack_require__.t`, namespace object of a module which is not esm
+        const CREATE_FAKE_NAMESPACE_OBJECT = 1 << 9;
+    }
+}
+
+impl RuntimeGlobals {
+    /// add the runtime helpers which the required helpers depend on
+    pub fn with_dependencies(self) -> Self {
+        let mut requirements = self | RuntimeGlobals::REQUIRE;
+        if requirements.contains(RuntimeGlobals::CREATE_FAKE_NAMESPACE_OBJECT) {
+            requirements |=
+                RuntimeGlobals::MAKE_NAMESPACE_OBJECT | RuntimeGlobals::DEFINE_PROPERTY_GETTERS;
+        }
+        if requirements.intersects(
+            RuntimeGlobals::DEFINE_PROPERTY_GETTERS
+                | RuntimeGlobals::EXPORT_STAR
+                | RuntimeGlobals::CHUNK_LOADING,
+        ) {
+            requirements |= RuntimeGlobals::HAS_OWN_PROPERTY;
+        }
+        requirements
+    }
+}
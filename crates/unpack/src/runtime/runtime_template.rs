@@ -0,0 +1,80 @@
+use rspack_sources::{BoxSource, RawSource, SourceExt};
+
+use super::RuntimeGlobals;
+use crate::compiler::{CompilerOptions, Target};
+use crate::utils::identifier::quoted;
+
+const REQUIRE: &str = include_str!("require.js");
+const HAS_OWN_PROPERTY: &str = include_str!("has_own_property.js");
+const DEFINE_PROPERTY_GETTERS: &str = include_str!("define_property_getters.js");
+const MAKE_NAMESPACE_OBJECT: &str = include_str!("make_namespace_object.js");
+const EXPORT_STAR: &str = include_str!("export_star.js");
+const CREATE_FAKE_NAMESPACE_OBJECT: &str = include_str!("create_fake_namespace_object.js");
+const ASYNC_MODULE: &str = include_str!("async_module.js");
+const AUTO_PUBLIC_PATH: &str = include_str!("auto_public_path.js");
+const NODE_AUTO_PUBLIC_PATH: &str = include_str!("node_auto_public_path.js");
+const BASE_URI: &str = include_str!("base_uri.js");
+const NODE_BASE_URI: &str = include_str!("node_base_uri.js");
+const CHUNK_LOADING: &str = include_str!("chunk_loading.js");
+const LOAD_SCRIPT: &str = include_str!("load_script.js");
+const NODE_LOAD_SCRIPT: &str = include_str!("node_load_script.js");
+
+/// render the bootstrap code which defines `__unpack_require__` and its helpers
+pub fn render_runtime(runtime_requirements: RuntimeGlobals, options: &CompilerOptions) -> BoxSource {
+    let mut runtime_requirements = runtime_requirements.with_dependencies();
+    // chunks are loaded from the public path on the web, and next to the chunk in node
+    if runtime_requirements.contains(RuntimeGlobals::CHUNK_LOADING)
+        && options.target == Target::Web
+    {
+        runtime_requirements |= RuntimeGlobals::PUBLIC_PATH;
+    }
+    let mut code = String::from(REQUIRE);
+    let helpers = [
+        (RuntimeGlobals::HAS_OWN_PROPERTY, HAS_OWN_PROPERTY),
+        (
+            RuntimeGlobals::DEFINE_PROPERTY_GETTERS,
+            DEFINE_PROPERTY_GETTERS,
+        ),
+        (RuntimeGlobals::MAKE_NAMESPACE_OBJECT, MAKE_NAMESPACE_OBJECT),
+        (RuntimeGlobals::EXPORT_STAR, EXPORT_STAR),
+        (
+            RuntimeGlobals::CREATE_FAKE_NAMESPACE_OBJECT,
+            CREATE_FAKE_NAMESPACE_OBJECT,
+        ),
+        (RuntimeGlobals::ASYNC_MODULE, ASYNC_MODULE),
+    ];
+    for (runtime_global, helper) in helpers {
+        if runtime_requirements.contains(runtime_global) {
+            code.push_str(helper);
+        }
+    }
+    let public_path = options.output.public_path.as_str();
+    if runtime_requirements.contains(RuntimeGlobals::PUBLIC_PATH) {
+        if public_path == "auto" {
+            code.push_str(match options.target {
+                Target::Web => AUTO_PUBLIC_PATH,
+                // the directory of the chunk
+                Target::Node => NODE_AUTO_PUBLIC_PATH,
+            });
+        } else {
+            code.push_str(&format!(
+                "__unpack_require__.p = {};\n",
+                quoted(public_path)
+            ));
+        }
+    }
+    if runtime_requirements.contains(RuntimeGlobals::BASE_URI) {
+        code.push_str(match options.target {
+            Target::Web => BASE_URI,
+            Target::Node => NODE_BASE_URI,
+        });
+    }
+    if runtime_requirements.contains(RuntimeGlobals::CHUNK_LOADING) {
+        code.push_str(match options.target {
+            Target::Web => LOAD_SCRIPT,
+            Target::Node => NODE_LOAD_SCRIPT,
+        });
+        code.push_str(CHUNK_LOADING);
+    }
+    RawSource::from(code).boxed()
+}
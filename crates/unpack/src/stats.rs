@@ -0,0 +1,151 @@
+use std::fmt::{self, Display};
+
+use crate::chunk::{ChunkFile, ChunkGraph, ChunkHashes};
+use crate::module::{ModuleGraph, ModuleId};
+
+/// information about the output of a compilation for tooling, similar with webpack's stats
+#[derive(Debug, Clone, Default)]
+pub struct Stats {
+    /// sorted by identifier
+    pub modules: Vec<StatsModule>,
+    /// in the order the chunks are created
+    pub chunks: Vec<StatsChunk>,
+    /// all the emitted files, sorted
+    pub assets: Vec<String>,
+    /// `[fullhash]` of the compilation
+    pub hash: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct StatsChunk {
+    pub id: String,
+    pub name: Option<String>,
+    /// emitted files of the chunk with the hashes filled in
+    pub files: Vec<String>,
+    /// `[chunkhash]` of the chunk
+    pub hash: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct StatsModule {
+    pub identifier: String,
+    /// `None` if the exports are unknown statically, e.g. commonjs
+    pub provided_exports: Option<Vec<String>>,
+    /// `None` if the exports object is used as a whole, so every export is used
+    pub used_exports: Option<Vec<String>>,
+    /// provided exports which are not imported by any module, their getters are left out
+    pub unused_exports: Vec<String>,
+    /// why optimizations are not applied to the module, e.g. it can't be concatenated
+    pub optimization_bailout: Vec<String>,
+}
+
+impl StatsModule {
+    pub fn new(module_graph: &ModuleGraph, module_id: ModuleId) -> Self {
+        let identifier = module_graph
+            .module_by_id(module_id)
+            .identifier()
+            .to_string();
+        let optimization_bailout = module_graph
+            .module_id_to_module_graph_module_id
+            .get(&module_id)
+            .map(|&mgm_id| {
+                module_graph
+                    .module_graph_module_by_id(mgm_id)
+                    .optimization_bailout
+                    .clone()
+            })
+            .unwrap_or_default();
+        let Some(exports_info) = module_graph.exports_info(module_id) else {
+            return Self {
+                identifier,
+                provided_exports: None,
+                used_exports: None,
+                unused_exports: vec![],
+                optimization_bailout,
+            };
+        };
+        let provided_exports = (!exports_info.other_exports_provided).then(|| {
+            exports_info
+                .provided
+                .keys()
+                .map(|name| name.to_string())
+                .collect()
+        });
+        let used_exports = (!exports_info.exports_object_used).then(|| {
+            exports_info
+                .used
+                .iter()
+                .map(|name| name.to_string())
+                .collect()
+        });
+        let unused_exports = exports_info
+            .unused_exports()
+            .map(|name| name.to_string())
+            .collect();
+        Self {
+            identifier,
+            provided_exports,
+            used_exports,
+            unused_exports,
+            optimization_bailout,
+        }
+    }
+}
+
+impl Stats {
+    pub fn new(module_graph: &ModuleGraph) -> Self {
+        let mut modules = module_graph
+            .modules
+            .indices()
+            .map(|module_id| StatsModule::new(module_graph, module_id))
+            .collect::<Vec<_>>();
+        modules.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+        Self {
+            modules,
+            ..Default::default()
+        }
+    }
+    pub fn set_chunks(
+        &mut self,
+        chunk_graph: &ChunkGraph,
+        chunk_files: &[ChunkFile],
+        hashes: &ChunkHashes,
+    ) {
+        self.chunks = chunk_graph
+            .chunks
+            .iter_enumerated()
+            .map(|(chunk_id, chunk)| StatsChunk {
+                id: chunk_graph.get_chunk_id(chunk_id).to_string(),
+                name: chunk.name.clone(),
+                files: chunk_files
+                    .iter()
+                    .filter(|file| file.chunk_id == chunk_id)
+                    .map(|file| file.filename.clone())
+                    .collect(),
+                hash: hashes.chunk_hashes[&chunk_id].clone(),
+            })
+            .collect();
+        self.hash = hashes.full_hash.clone();
+    }
+    pub fn set_assets<'a>(&mut self, assets: impl IntoIterator<Item = &'a String>) {
+        self.assets = assets.into_iter().cloned().collect();
+        self.assets.sort();
+    }
+}
+
+/// lists the modules with unused exports
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for module in &self.modules {
+            if module.unused_exports.is_empty() {
+                continue;
+            }
+            writeln!(f, "{}", module.identifier)?;
+            writeln!(f, "  unused exports: {}", module.unused_exports.join(", "))?;
+            if let Some(used_exports) = &module.used_exports {
+                writeln!(f, "  used exports: {}", used_exports.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
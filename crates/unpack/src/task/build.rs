@@ -1,5 +1,5 @@
 use crate::{
-    dependency::BoxDependency,
+    dependency::DependencyId,
     module::{BoxModule, ModuleId},
 };
 
@@ -7,5 +7,6 @@ use crate::{
 pub struct BuildTask {
     pub origin_module_id: Option<ModuleId>,
     pub module: BoxModule,
-    pub module_dependency: BoxDependency,
+    /// dependencies resolved to this module
+    pub dependencies: Vec<DependencyId>,
 }
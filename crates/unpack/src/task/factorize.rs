@@ -1,13 +1,16 @@
 use camino::Utf8PathBuf;
 use miette::Report;
 
-use crate::dependency::BoxDependency;
+use crate::dependency::{BoxDependency, DependencyId};
 use crate::module::ModuleId;
 use derive_new::new;
 // port from https://github.com/webpack/webpack/blob/899f06934391baede59da3dcd35b5ef51c675dbe/lib/Compilation.js#L1842
 #[derive(Debug)]
 pub struct FactorizeTask {
+    /// the first one of `dependencies`, used to create the module
     pub module_dependency: BoxDependency,
+    /// dependencies with the same request in the origin module
+    pub dependencies: Vec<DependencyId>,
     pub origin_module_id: Option<ModuleId>,
     pub origin_module_context: Option<Utf8PathBuf>,
 }
@@ -1,12 +1,14 @@
+use crate::errors::Diagnostics;
 use crate::{
-    dependency::BoxDependency,
+    dependency::{BoxDependency, DependencyId},
     module::{BoxModule, ModuleId},
 };
 
 #[derive(Debug)]
 pub struct ProcessDepsTask {
-    pub module: BoxModule,                  // to be added to module_graph
-    pub module_dependency: BoxDependency,   // to be added to module_graph
-    pub dependencies: Vec<BoxDependency>,   // recursively build
-    pub origin_module_id: Option<ModuleId>, // to be added to module_graph
+    pub module: BoxModule,                       // to be added to module_graph
+    pub dependencies: Vec<DependencyId>,         // resolved to module
+    pub module_dependencies: Vec<BoxDependency>, // recursively build
+    pub origin_module_id: Option<ModuleId>,      // to be added to module_graph
+    pub diagnostics: Diagnostics,                // warnings of the module
 }
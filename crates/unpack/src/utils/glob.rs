@@ -0,0 +1,40 @@
+use regex::Regex;
+
+/// whether the relative path matches the glob, `*` and `?` don't match `/`, `**` matches any
+/// number of directories, `{a,b}` matches either of the alternatives
+pub fn glob_matches(glob: &str, path: &str) -> bool {
+    Regex::new(&glob_to_regex(glob)).is_ok_and(|regex| regex.is_match(path))
+}
+
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut chars = glob.chars().peekable();
+    let mut in_group = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '{' if !in_group => {
+                in_group = true;
+                regex.push_str("(?:");
+            }
+            '}' if in_group => {
+                in_group = false;
+                regex.push(')');
+            }
+            ',' if in_group => regex.push('|'),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
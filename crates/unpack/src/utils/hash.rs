@@ -0,0 +1,77 @@
+use md5::{Digest, Md5};
+use regex::{Captures, Regex};
+use sha2::Sha256;
+use xxhash_rust::xxh64::{xxh64, Xxh64};
+
+use crate::compiler::HashFunction;
+
+/// numeric digest of content which is the same on every platform and version, e.g. for
+/// deterministic ids
+pub fn numeric_hash(content: &[u8]) -> u64 {
+    xxh64(content, 0)
+}
+
+/// incremental hasher of `output.hash_function`
+pub enum OutputHasher {
+    Xxhash64(Xxh64),
+    Md5(Md5),
+    Sha256(Sha256),
+}
+
+impl OutputHasher {
+    pub fn new(hash_function: HashFunction) -> Self {
+        match hash_function {
+            HashFunction::Xxhash64 => OutputHasher::Xxhash64(Xxh64::new(0)),
+            HashFunction::Md5 => OutputHasher::Md5(Md5::new()),
+            HashFunction::Sha256 => OutputHasher::Sha256(Sha256::new()),
+        }
+    }
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            OutputHasher::Xxhash64(hasher) => hasher.update(data),
+            OutputHasher::Md5(hasher) => hasher.update(data),
+            OutputHasher::Sha256(hasher) => hasher.update(data),
+        }
+    }
+    /// lowercase hex digest
+    pub fn digest(self) -> String {
+        match self {
+            OutputHasher::Xxhash64(hasher) => format!("{:016x}", hasher.digest()),
+            OutputHasher::Md5(hasher) => format!("{:x}", hasher.finalize()),
+            OutputHasher::Sha256(hasher) => format!("{:x}", hasher.finalize()),
+        }
+    }
+}
+
+/// hex digest of content with `hash_function`
+pub fn output_hash(hash_function: HashFunction, content: &[u8]) -> String {
+    let mut hasher = OutputHasher::new(hash_function);
+    hasher.update(content);
+    hasher.digest()
+}
+
+/// replace `[placeholder]` and `[placeholder:length]` of the filename template with `hash`
+/// shortened to the length, which defaults to `default_length`
+pub fn replace_hash(
+    template: &str,
+    placeholder: &str,
+    default_length: usize,
+    hash: impl Fn(usize) -> String,
+) -> String {
+    let regex =
+        Regex::new(&format!(r"\[{placeholder}(?::(\d+))?\]")).expect("should be valid regex");
+    regex
+        .replace_all(template, |captures: &Captures| {
+            let length = captures
+                .get(1)
+                .and_then(|length| length.as_str().parse().ok())
+                .unwrap_or(default_length);
+            hash(length)
+        })
+        .into_owned()
+}
+
+/// the first `length` characters of the hash
+pub fn shorten_hash(hash: &str, length: usize) -> &str {
+    &hash[..length.min(hash.len())]
+}
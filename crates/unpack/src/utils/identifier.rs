@@ -0,0 +1,54 @@
+use camino::Utf8Path;
+
+/// whether `name` can be used as a javascript identifier without quoting
+pub fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// replace the characters which can't be used in identifiers with `_`, e.g. `./a.js` to `_a_js`
+pub fn to_identifier(value: &str) -> String {
+    let mut name = String::with_capacity(value.len());
+    for c in value.chars() {
+        let c = if c.is_ascii_alphanumeric() || c == '$' {
+            c
+        } else {
+            '_'
+        };
+        if !(c == '_' && name.ends_with('_')) {
+            name.push(c);
+        }
+    }
+    name
+}
+
+/// `.name` for identifiers and `["name"]` for others
+pub fn property_access(name: &str) -> String {
+    if is_identifier(name) {
+        format!(".{name}")
+    } else {
+        format!("[{}]", quoted(name))
+    }
+}
+
+/// json string literal, which is also a valid javascript string literal
+pub fn quoted(value: &str) -> String {
+    serde_json::to_string(value).expect("string should be serializable")
+}
+
+/// module identifier relative to the context, e.g. `./src/a.js` or `asset/resource|./a.png`,
+/// which is the same on different machines
+pub fn contextify(identifier: &str, context: &Utf8Path) -> String {
+    let (prefix, path) = match identifier.rsplit_once('|') {
+        Some((module_type, path)) => (format!("{module_type}|"), path),
+        None => (String::new(), identifier),
+    };
+    match Utf8Path::new(path).strip_prefix(context) {
+        Ok(relative) => format!("{prefix}./{relative}"),
+        Err(_) => identifier.to_string(),
+    }
+}
@@ -1 +1,5 @@
+pub mod glob;
+pub mod hash;
+pub mod identifier;
 pub mod path;
+pub mod source_map;
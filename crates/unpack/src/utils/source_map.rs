@@ -0,0 +1,98 @@
+use base64::Engine;
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
+use miette::{miette, IntoDiagnostic, Result};
+use rspack_sources::{MapOptions, Source, SourceMap, SourceMapSource, SourceMapSourceOptions};
+
+/// map `code` through `map` and then through `inner`, which is the map of the single source of `map`,
+/// e.g. the map of a transform composed with the map of the loader before it
+pub fn compose_source_maps(code: &str, map: SourceMap, inner: SourceMap) -> SourceMap {
+    let [name] = map.sources() else {
+        return map;
+    };
+    let name = name.clone();
+    let source = SourceMapSource::new(SourceMapSourceOptions {
+        value: code,
+        name,
+        source_map: map.clone(),
+        original_source: None,
+        inner_source_map: Some(inner),
+        remove_original_source: true,
+    });
+    source.map(&MapOptions::new(true)).unwrap_or(map)
+}
+
+/// find the trailing `//# sourceMappingURL=` comment, returns the url and the code without the comment
+pub fn split_source_mapping_url(code: &str) -> Option<(&str, &str)> {
+    let code = code.trim_end();
+    let line_start = code.rfind('\n').map_or(0, |index| index + 1);
+    let line = code[line_start..].trim();
+    let comment = match line.strip_prefix("//") {
+        Some(comment) => comment,
+        None => line.strip_prefix("/*")?.strip_suffix("*/")?,
+    };
+    let url = comment
+        .strip_prefix(['#', '@'])?
+        .trim_start()
+        .strip_prefix("sourceMappingURL=")?
+        .trim();
+    (!url.is_empty() && !url.contains(char::is_whitespace)).then_some((url, &code[..line_start]))
+}
+
+/// read the map referenced by a `sourceMappingURL` comment of the module at `resource_path`,
+/// the url is either a data url or a path relative to the module
+pub async fn read_source_map(url: &str, resource_path: &Utf8Path) -> Result<SourceMap> {
+    let dir = resource_path.parent().unwrap_or(resource_path);
+    let Some(data) = url.strip_prefix("data:") else {
+        let path = dir.join(url);
+        let json = tokio::fs::read(&path)
+            .await
+            .map_err(|err| miette!("failed to read {path}: {err}"))?;
+        return parse_source_map(&json, path.parent().unwrap_or(&path));
+    };
+    let (mediatype, data) = data
+        .split_once(',')
+        .ok_or_else(|| miette!("invalid data url"))?;
+    let json = if mediatype.ends_with(";base64") {
+        base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .into_diagnostic()?
+    } else {
+        data.as_bytes().to_vec()
+    };
+    parse_source_map(&json, dir)
+}
+
+/// parse a source map and resolve its relative sources against `dir`, the directory of the map
+fn parse_source_map(json: &[u8], dir: &Utf8Path) -> Result<SourceMap> {
+    let mut map = SourceMap::from_slice(json).map_err(|err| miette!("{err}"))?;
+    let source_root = dir.join(map.source_root().unwrap_or_default());
+    let sources = map
+        .sources()
+        .iter()
+        .map(|source| {
+            if source.contains("://") || Utf8Path::new(source).is_absolute() {
+                source.clone()
+            } else {
+                normalize(&source_root.join(source)).into_string()
+            }
+        })
+        .collect::<Vec<_>>();
+    map.set_sources(sources);
+    map.set_source_root(None::<String>);
+    Ok(map)
+}
+
+/// remove `.` and `..` from the path without touching the file system
+fn normalize(path: &Utf8Path) -> Utf8PathBuf {
+    let mut normalized = Utf8PathBuf::new();
+    for component in path.components() {
+        match component {
+            Utf8Component::CurDir => {}
+            Utf8Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
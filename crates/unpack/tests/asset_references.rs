@@ -0,0 +1,29 @@
+mod common;
+
+use std::fs;
+
+#[test]
+fn assets_referenced_by_css_and_javascript() {
+    let output = common::build("asset_references", |options| {
+        options.output.asset_module_filename = "[name][ext]".to_string();
+    });
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let mut files = fs::read_dir(&output.path)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    files.sort();
+    assert_eq!(
+        files,
+        ["background.png", "image.png", "main.css", "main.js"]
+    );
+    let css = output.read("main.css");
+    assert!(css.contains("url(\"image.png\")"));
+    assert!(css.contains("url(\"background.png\")"));
+    // `image.png` is a single module for both `url()` and `new URL()`, and `background.png`
+    // is only referenced by css so it has no javascript
+    let main = output.read("main.js");
+    assert_eq!(main.matches("\"image.png\"").count(), 1);
+    assert!(!main.contains("background.png"));
+    assert_eq!(output.run("main.js"), "image.png\n");
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use std::fs;
+
+use base64::Engine;
+use regex::Regex;
+use unpack::compiler::{ModuleRule, RuleCondition};
+use unpack::module::ModuleType;
+use xxhash_rust::xxh64::xxh64;
+
+fn build() -> common::Output {
+    common::build("assets", |options| {
+        options.module.rules.push(ModuleRule {
+            resource_query: Some(RuleCondition::Regex(Regex::new("inline").unwrap())),
+            module_type: Some(ModuleType::AssetInline),
+            ..Default::default()
+        });
+    })
+}
+
+#[test]
+fn asset_modules() {
+    let output = build();
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let large = fs::read(common::fixture("assets").join("large.png")).unwrap();
+    // `asset` larger than `data_url_max_size` is emitted as a file named by its hash
+    let emitted = fs::read_dir(&output.path)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|file| file.ends_with(".png"))
+        .collect::<Vec<_>>();
+    assert_eq!(emitted, [format!("{:016x}.png", xxh64(&large, 0))]);
+    assert_eq!(fs::read(output.path.join(&emitted[0])).unwrap(), large);
+    let small = base64::engine::general_purpose::STANDARD
+        .encode(fs::read(common::fixture("assets").join("small.svg")).unwrap());
+    let inlined = base64::engine::general_purpose::STANDARD.encode(&large);
+    assert_eq!(
+        output.run("main.js"),
+        format!(
+            "{}\ndata:image/svg+xml;base64,{small}\n\"plain text\\n\"\ndata:image/png;base64, {}\n",
+            emitted[0],
+            "data:image/png;base64,".len() + inlined.len()
+        )
+    );
+}
+
+#[test]
+fn asset_module_filename() {
+    let output = common::build("assets", |options| {
+        options.output.public_path = "/static/".to_string();
+        options.output.asset_module_filename = "images/[name].[hash:8][ext]".to_string();
+    });
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let large = fs::read(common::fixture("assets").join("large.png")).unwrap();
+    let filename = format!(
+        "images/large.{}.png",
+        &format!("{:016x}", xxh64(&large, 0))[..8]
+    );
+    assert_eq!(fs::read(output.path.join(&filename)).unwrap(), large);
+    let main = output.read("main.js");
+    assert!(main.contains("__unpack_require__.p = \"/static/\";"));
+    assert!(main.contains(&format!(
+        "module.exports = __unpack_require__.p + \"{filename}\";"
+    )));
+}
@@ -0,0 +1,115 @@
+#![allow(dead_code)]
+use std::fs;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use camino::Utf8PathBuf;
+use rspack_sources::SourceMap;
+use unpack::compiler::{Compiler, CompilerOptions, EntryItem, Target};
+use unpack::errors::miette::{GraphicalReportHandler, GraphicalTheme};
+use unpack::stats::Stats;
+
+/// emitted files and stats of a build of a fixture
+pub struct Output {
+    pub path: Utf8PathBuf,
+    pub stats: Stats,
+    /// messages of the errors and warnings
+    pub diagnostics: Vec<String>,
+    /// the errors and warnings rendered with their code frames, without colors
+    pub reports: Vec<String>,
+}
+
+impl Output {
+    pub fn read(&self, filename: &str) -> String {
+        fs::read_to_string(self.path.join(filename))
+            .unwrap_or_else(|err| panic!("failed to read {filename}: {err}"))
+    }
+    /// run the emitted file with node, returns its stdout
+    pub fn run(&self, filename: &str) -> String {
+        let output = Command::new("node")
+            .arg(self.path.join(filename))
+            .current_dir(&self.path)
+            .output()
+            .expect("node should be installed");
+        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+        assert!(
+            output.status.success(),
+            "{filename} failed: {stdout}{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        stdout
+    }
+}
+
+pub fn fixture(name: &str) -> Utf8PathBuf {
+    Utf8PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+/// build `tests/fixtures/{name}` from `./index.mjs` as the `main` chunk for node, `configure`
+/// overrides the options. each build is emitted into its own directory
+pub fn build(name: &str, configure: impl FnOnce(&mut CompilerOptions)) -> Output {
+    static BUILDS: AtomicUsize = AtomicUsize::new(0);
+    let path = Utf8PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!(
+        "{}-{}-{}",
+        name.replace('/', "-"),
+        std::process::id(),
+        BUILDS.fetch_add(1, Ordering::Relaxed)
+    ));
+    let _ = fs::remove_dir_all(&path);
+    let mut options = CompilerOptions {
+        context: fixture(name),
+        entry: vec![EntryItem {
+            name: "main".to_string(),
+            import: "./index.mjs".to_string(),
+        }],
+        target: Target::Node,
+        ..Default::default()
+    };
+    options.output.path = path.clone();
+    configure(&mut options);
+    let mut compiler = Compiler::new(Arc::new(options), vec![]);
+    tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(compiler.build());
+    Output {
+        path,
+        stats: compiler.stats().clone(),
+        diagnostics: compiler
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect(),
+        reports: compiler
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| {
+                let mut report = String::new();
+                GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor())
+                    .render_report(&mut report, diagnostic.as_ref())
+                    .unwrap();
+                report
+            })
+            .collect(),
+    }
+}
+
+/// the original `(source, line)` of the first mapping on the generated line which contains `code`
+pub fn original_position(code: &str, map: &SourceMap, generated: &str) -> (String, u32) {
+    let line = generated
+        .lines()
+        .position(|line| line.contains(code))
+        .unwrap_or_else(|| panic!("{code} is not generated")) as u32
+        + 1;
+    let original = map
+        .decoded_mappings()
+        .find(|mapping| mapping.generated_line == line)
+        .and_then(|mapping| mapping.original)
+        .unwrap_or_else(|| panic!("{code} is not mapped"));
+    (
+        map.sources()[original.source_index as usize].clone(),
+        original.original_line,
+    )
+}
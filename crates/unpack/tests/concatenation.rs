@@ -0,0 +1,52 @@
+mod common;
+
+fn optimization_bailout(output: &common::Output, module: &str) -> Vec<String> {
+    output
+        .stats
+        .modules
+        .iter()
+        .find(|stats| stats.identifier.ends_with(module))
+        .unwrap_or_else(|| panic!("{module} should be in the stats"))
+        .optimization_bailout
+        .clone()
+}
+
+#[test]
+fn concatenated_modules() {
+    let output = common::build("concatenation", |_| {});
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let main = output.read("main.js");
+    for module in [
+        "a.mjs",
+        "b.mjs",
+        "namespace.mjs",
+        "reexport.mjs",
+        "cycle_a.mjs",
+        "cycle_b.mjs",
+    ] {
+        assert!(
+            main.contains(&format!("// CONCATENATED MODULE: ./{module}")),
+            "{module} should be concatenated"
+        );
+    }
+    // reserved names and conflicting top level bindings are renamed
+    assert!(main.contains("const a___unpack_require__ = \"reserved\";"));
+    assert!(main.contains("const a_name = \"a\";"));
+    assert!(main.contains("const b_name = \"b\";"));
+    // commonjs and modules which bail out are required as externals
+    assert!(main.contains("var commonjs__unpack_external_module_0__ = __unpack_require__("));
+    assert!(main.contains("var uses_eval__unpack_external_module_1__ = __unpack_require__("));
+    assert_eq!(
+        optimization_bailout(&output, "uses_eval.mjs"),
+        ["ModuleConcatenation bailout: Module uses eval()"]
+    );
+    assert_eq!(
+        optimization_bailout(&output, "commonjs.js"),
+        ["ModuleConcatenation bailout: Module is not an ECMAScript module"]
+    );
+    assert_eq!(optimization_bailout(&output, "a.mjs"), Vec::<String>::new());
+    assert_eq!(
+        output.run("main.js"),
+        "app a b reserved\none,two 1 Module\n1 2 1\ncommonjs 2 function\n"
+    );
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use xxhash_rust::xxh64::xxh64;
+
+/// suffix of the class names of `button.module.css`, the hash of its path relative to context
+fn local_suffix() -> String {
+    format!("{:016x}", xxh64(b"button.module.css", 0))[..8].to_string()
+}
+
+#[test]
+fn css_is_emitted_in_import_order() {
+    let output = common::build("css", |_| {});
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let css = output.read("main.css");
+    // `@import` is removed and the imported css comes first
+    assert!(!css.contains("@import"));
+    let reset = css.find("box-sizing").unwrap();
+    let global = css.find("body").unwrap();
+    let module = css.find(".button_").unwrap();
+    assert!(reset < global && global < module, "{css}");
+    // css has no javascript to evaluate besides the exports of css modules
+    assert!(!output.read("main.js").contains("box-sizing"));
+}
+
+#[test]
+fn css_modules() {
+    let output = common::build("css", |_| {});
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let suffix = local_suffix();
+    let css = output.read("main.css");
+    assert!(css.contains(&format!(".button_{suffix} {{")));
+    assert!(css.contains(&format!(".button_{suffix}.primary_{suffix} {{")));
+    // `:global()` is unwrapped and its class names are kept
+    assert!(css.contains(&format!(".app .button_{suffix} {{")));
+    assert_eq!(
+        output.run("main.js"),
+        format!("button_{suffix} primary_{suffix} button,primary\n")
+    );
+}
+
+#[test]
+fn conditional_imports_are_wrapped() {
+    let output = common::build("css_conditional_import", |_| {});
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let css = output.read("main.css");
+    assert!(!css.contains("@import"), "{css}");
+    let blocks = [
+        "@media print {\nbody {\n  color: black;\n}\n\n}\n",
+        "@supports (display: grid) {\n.grid {\n  display: grid;\n}\n\n}\n",
+        "@layer base {\na {\n  color: inherit;\n}\n\n}\n",
+        // `layer` first, then `supports()`, then media queries
+        "@layer {\n@supports (not (display: grid)) {\n@media screen and (min-width: 100px) {\n\
+         .wide {\n  float: left;\n}\n\n}\n}\n}\n",
+        // imported without conditions
+        "}\n* {\n  box-sizing: border-box;\n}\n",
+        // imported again with conditions
+        "@media print {\n* {\n  box-sizing: border-box;\n}\n\n}\n",
+        "body {\n  margin: 0;\n}\n",
+    ];
+    let positions = blocks.map(|block| {
+        css.find(block)
+            .unwrap_or_else(|| panic!("{block} is not in {css}"))
+    });
+    assert!(positions.is_sorted(), "{css}");
+}
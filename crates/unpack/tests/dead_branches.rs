@@ -0,0 +1,113 @@
+mod common;
+
+use unpack::compiler::ParserBackend;
+
+fn build(parser_backend: ParserBackend, dev: bool) -> common::Output {
+    common::build("dead_branches", |options| {
+        options.parser_backend = parser_backend;
+        let node_env = if dev { "development" } else { "production" };
+        options.define = [
+            ("process.env.NODE_ENV", format!("\"{node_env}\"")),
+            ("__DEV__", dev.to_string()),
+            ("typeof window", "\"undefined\"".to_string()),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect();
+    })
+}
+
+#[test]
+fn dead_branches_are_removed() {
+    for parser_backend in [ParserBackend::Swc, ParserBackend::Oxc] {
+        let output = build(parser_backend, false);
+        assert_eq!(
+            output.diagnostics,
+            Vec::<String>::new(),
+            "{parser_backend:?}"
+        );
+        // the dependencies in dead branches are not bundled
+        assert_eq!(output.stats.assets, ["main.js"], "{parser_backend:?}");
+        let main = output.read("main.js");
+        assert!(
+            main.contains("if (false) {} else {"),
+            "{parser_backend:?}: {main}"
+        );
+        assert!(
+            main.contains("const asset = false ? 0 : null;"),
+            "{parser_backend:?}: {main}"
+        );
+        assert!(main.contains("false && 0"), "{parser_backend:?}: {main}");
+        assert!(
+            main.contains("true ? \"server\" : 0"),
+            "{parser_backend:?}: {main}"
+        );
+        assert!(!main.contains("development"), "{parser_backend:?}: {main}");
+        assert!(
+            !main.contains("require('./dev-only')"),
+            "{parser_backend:?}: {main}"
+        );
+        // `var` declarations are hoisted out of the branch, so it's kept
+        assert!(
+            main.contains("var hoisted = \"hoisted\";"),
+            "{parser_backend:?}: {main}"
+        );
+        assert_eq!(
+            output.run("main.js"),
+            "production\nnot dev\nundefined\nnull false\nserver\n",
+            "{parser_backend:?}"
+        );
+    }
+}
+
+#[test]
+fn live_branches_are_kept() {
+    for parser_backend in [ParserBackend::Swc, ParserBackend::Oxc] {
+        let output = build(parser_backend, true);
+        assert_eq!(
+            output.diagnostics,
+            Vec::<String>::new(),
+            "{parser_backend:?}"
+        );
+        assert_eq!(output.stats.assets.len(), 3, "{parser_backend:?}");
+        assert!(
+            output
+                .stats
+                .assets
+                .contains(&"dev_worker_mjs.js".to_string()),
+            "{parser_backend:?}"
+        );
+        assert!(
+            output
+                .stats
+                .assets
+                .iter()
+                .any(|asset| asset.ends_with(".txt")),
+            "{parser_backend:?}"
+        );
+        let main = output.read("main.js");
+        assert!(
+            main.contains("if (false) {}\n"),
+            "{parser_backend:?}: {main}"
+        );
+        assert!(!main.contains("production"), "{parser_backend:?}: {main}");
+        // `require()` is not scanned, the call in the live branch is kept as written
+        assert!(
+            main.contains("if (true) require('./dev-only')"),
+            "{parser_backend:?}: {main}"
+        );
+        // `dev-only.txt` is bundled by `new URL()` only
+        let asset = common::fixture("dead_branches").join("dev-only.txt");
+        assert_eq!(
+            output
+                .stats
+                .modules
+                .iter()
+                .filter(|module| module.identifier.contains("dev-only"))
+                .map(|module| module.identifier.as_str())
+                .collect::<Vec<_>>(),
+            [format!("asset/resource|{asset}")],
+            "{parser_backend:?}"
+        );
+    }
+}
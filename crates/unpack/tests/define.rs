@@ -0,0 +1,39 @@
+mod common;
+
+use unpack::compiler::{CompilerOptions, ParserBackend};
+
+const EXPECTED: &str = "\
+production production false 4
+boolean object string
+{\"__DEV__\":false} local
+";
+
+fn define(options: &mut CompilerOptions) {
+    options.define = [
+        ("process.env.NODE_ENV", "\"production\""),
+        ("__DEV__", "false"),
+        ("__VERSION__", "1 + 1"),
+        ("typeof window", "\"object\""),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key.to_string(), value.to_string()))
+    .collect();
+}
+
+#[test]
+fn define_replaces_expressions() {
+    for parser_backend in [ParserBackend::Swc, ParserBackend::Oxc] {
+        let output = common::build("define", |options| {
+            define(options);
+            options.parser_backend = parser_backend;
+        });
+        assert_eq!(output.diagnostics, Vec::<String>::new());
+        let main = output.read("main.js");
+        // code is wrapped in parentheses to keep the precedence
+        assert!(main.contains("(1 + 1) * 2"), "{parser_backend:?}");
+        assert!(main.contains("{ __DEV__: false }"), "{parser_backend:?}");
+        // bindings of the same name are not replaced
+        assert!(main.contains("return __DEV__;"), "{parser_backend:?}");
+        assert_eq!(output.run("main.js"), EXPECTED, "{parser_backend:?}");
+    }
+}
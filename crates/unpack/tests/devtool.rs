@@ -0,0 +1,81 @@
+mod common;
+
+use base64::Engine;
+use rspack_sources::SourceMap;
+use unpack::compiler::Devtool;
+
+fn build(devtool: Devtool) -> common::Output {
+    common::build("devtool", |options| {
+        options.devtool = devtool;
+    })
+}
+
+fn assert_source_map(map: &str, main: &str) {
+    let map = SourceMap::from_json(map).unwrap();
+    assert_eq!(map.file(), Some("main.js"));
+    assert_eq!(
+        map.sources(),
+        ["unpack:///./lib.mjs", "unpack:///./index.mjs"]
+    );
+    let fixture = common::fixture("devtool");
+    assert_eq!(
+        map.sources_content(),
+        [
+            std::fs::read_to_string(fixture.join("lib.mjs")).unwrap(),
+            std::fs::read_to_string(fixture.join("index.mjs")).unwrap(),
+        ]
+    );
+    assert_eq!(
+        common::original_position("const message", &map, main),
+        ("unpack:///./lib.mjs".to_string(), 2)
+    );
+    assert_eq!(
+        common::original_position("console.log", &map, main),
+        ("unpack:///./index.mjs".to_string(), 3)
+    );
+}
+
+#[test]
+fn source_map() {
+    let output = build(Devtool::SourceMap);
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let main = output.read("main.js");
+    assert!(
+        main.ends_with("\n//# sourceMappingURL=main.js.map\n"),
+        "{main}"
+    );
+    assert_source_map(&output.read("main.js.map"), &main);
+    assert_eq!(output.run("main.js"), "hello source map\n");
+}
+
+#[test]
+fn inline_source_map() {
+    let output = build(Devtool::InlineSourceMap);
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let main = output.read("main.js");
+    let (_, data) = main
+        .trim_end()
+        .rsplit_once("\n//# sourceMappingURL=data:application/json;charset=utf-8;base64,")
+        .unwrap_or_else(|| panic!("no inline source map: {main}"));
+    let map = base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .unwrap();
+    assert_source_map(&String::from_utf8(map).unwrap(), &main);
+    assert!(!output.path.join("main.js.map").exists());
+}
+
+#[test]
+fn hidden_source_map() {
+    let output = build(Devtool::HiddenSourceMap);
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let main = output.read("main.js");
+    assert!(!main.contains("sourceMappingURL"), "{main}");
+    assert_source_map(&output.read("main.js.map"), &main);
+}
+
+#[test]
+fn no_source_map() {
+    let output = build(Devtool::None);
+    assert!(!output.read("main.js").contains("sourceMappingURL"));
+    assert!(!output.path.join("main.js.map").exists());
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use unpack::compiler::ParserBackend;
+
+const EXPECTED: &str = "\
+called in a cycle default
+default class default generator
+42
+";
+
+#[test]
+fn anonymous_default_export_declarations() {
+    let output = common::build("export_default", |options| {
+        options.optimization.concatenate_modules = false;
+    });
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    assert_eq!(output.run("main.js"), EXPECTED);
+}
+
+#[test]
+fn anonymous_default_export_declarations_in_concatenated_modules() {
+    let output = common::build("export_default", |_| {});
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    assert!(output
+        .read("main.js")
+        .contains("// CONCATENATED MODULE: ./function.mjs"));
+    assert_eq!(output.run("main.js"), EXPECTED);
+}
+
+#[test]
+fn anonymous_default_export_declarations_with_oxc() {
+    let output = common::build("export_default", |options| {
+        options.parser_backend = ParserBackend::Oxc;
+    });
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    assert_eq!(output.run("main.js"), EXPECTED);
+}
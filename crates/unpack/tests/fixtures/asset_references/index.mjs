@@ -0,0 +1,4 @@
+import "./style.css";
+
+const image = new URL("./image.png", import.meta.url);
+console.log(image.pathname.split("/").pop());
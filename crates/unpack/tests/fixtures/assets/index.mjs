@@ -0,0 +1,9 @@
+import large from "./large.png";
+import small from "./small.svg";
+import note from "./note.txt";
+import inlined from "./large.png?inline";
+
+console.log(large.split("/").pop());
+console.log(small);
+console.log(JSON.stringify(note));
+console.log(inlined.slice(0, 22), inlined.length);
@@ -0,0 +1,5 @@
+const name = "a";
+const __unpack_require__ = "reserved";
+
+export { name };
+export const value = __unpack_require__;
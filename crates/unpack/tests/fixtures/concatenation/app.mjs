@@ -0,0 +1,13 @@
+import { name as aName, value } from "./a.mjs";
+import { name as bName } from "./b.mjs";
+import * as ns from "./namespace.mjs";
+import * as reexported from "./reexport.mjs";
+import commonjs from "./commonjs.js";
+import { evaluated } from "./uses_eval.mjs";
+import { ping } from "./cycle_a.mjs";
+
+const name = "app";
+console.log(name, aName, bName, value);
+console.log(Object.keys(ns).join(), ns.one, ns[Symbol.toStringTag]);
+console.log(reexported.one, reexported.two, reexported.nested.one);
+console.log(commonjs.external, evaluated, ping());
@@ -0,0 +1,3 @@
+const name = "b";
+
+export { name };
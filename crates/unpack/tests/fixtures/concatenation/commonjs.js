@@ -0,0 +1 @@
+exports.external = "commonjs";
@@ -0,0 +1,5 @@
+import { pong } from "./cycle_b.mjs";
+
+export function ping() {
+  return pong();
+}
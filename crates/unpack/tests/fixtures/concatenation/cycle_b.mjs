@@ -0,0 +1,7 @@
+import { ping } from "./cycle_a.mjs";
+
+export const hoisted = typeof ping;
+
+export function pong() {
+  return hoisted;
+}
@@ -0,0 +1,2 @@
+export * from "./namespace.mjs";
+export * as nested from "./namespace.mjs";
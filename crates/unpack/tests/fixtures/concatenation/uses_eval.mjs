@@ -0,0 +1 @@
+export const evaluated = eval("1 + 1");
@@ -0,0 +1,4 @@
+import "./global.css";
+import styles from "./button.module.css";
+
+console.log(styles.button, styles.primary, Object.keys(styles).join());
@@ -0,0 +1,16 @@
+if (process.env.NODE_ENV !== "production") {
+  new Worker(new URL("./dev-worker.mjs", import.meta.url));
+  console.log("development");
+} else {
+  console.log("production");
+}
+if (!__DEV__) console.log("not dev");
+if (__DEV__) {
+  var hoisted = "hoisted";
+}
+console.log(typeof hoisted);
+const asset = __DEV__ ? new URL("./dev-only.txt", import.meta.url) : null;
+console.log(asset, __DEV__ && new URL("./dev-only.txt", import.meta.url));
+console.log(typeof window === "undefined" ? "server" : "browser");
+// `require()` isn't bundled, it is only dropped with the dead branch
+if (process.env.NODE_ENV !== 'production') require('./dev-only')
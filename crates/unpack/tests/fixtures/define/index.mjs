@@ -0,0 +1,8 @@
+import { shadowed } from "./shadowed.mjs";
+
+console.log(process.env.NODE_ENV, process.env["NODE_ENV"], __DEV__, __VERSION__ * 2);
+console.log(typeof __DEV__, typeof window, typeof process.env.NODE_ENV);
+console.log(JSON.stringify({ __DEV__ }), shadowed("local"));
+if (__DEV__) {
+  console.log("development");
+}
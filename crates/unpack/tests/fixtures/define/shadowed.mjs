@@ -0,0 +1,3 @@
+export function shadowed(__DEV__) {
+  return __DEV__;
+}
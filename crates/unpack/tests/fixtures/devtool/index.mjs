@@ -0,0 +1,3 @@
+import { greet } from "./lib.mjs";
+
+console.log(greet("source map"));
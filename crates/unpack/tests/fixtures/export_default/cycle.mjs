@@ -0,0 +1,3 @@
+import callee from "./function.mjs";
+
+export const called = callee();
@@ -0,0 +1,8 @@
+import callee, { called } from "./function.mjs";
+import Anonymous from "./class.mjs";
+import generator from "./generator.mjs";
+import expression from "./expression.mjs";
+
+console.log(called, callee.name);
+console.log(Anonymous.name, new Anonymous().value, generator.name, generator().next().value);
+console.log(expression);
@@ -0,0 +1 @@
+export default 40 + 2;
@@ -0,0 +1,5 @@
+export { called } from "./cycle.mjs";
+
+export default function () {
+  return "called in a cycle";
+}
@@ -0,0 +1 @@
+import "./defaults.mjs";
@@ -0,0 +1,4 @@
+import "./style.css";
+import { shared } from "./shared.mjs";
+
+console.log(shared, VERSION);
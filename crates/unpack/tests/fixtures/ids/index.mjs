@@ -0,0 +1,4 @@
+import { a } from "./a.mjs";
+import { b } from "./b.mjs";
+
+console.log(a, b);
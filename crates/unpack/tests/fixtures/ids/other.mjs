@@ -0,0 +1,4 @@
+import { a } from "./a.mjs";
+import { extra } from "./extra.mjs";
+
+console.log(a, extra);
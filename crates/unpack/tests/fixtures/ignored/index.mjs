@@ -0,0 +1,4 @@
+import nodeOnly from "node-only";
+import pkg from "pkg";
+
+console.log(JSON.stringify(nodeOnly), JSON.stringify(pkg));
@@ -0,0 +1,3 @@
+import * as meta from "./nested/meta.mjs";
+
+console.log(JSON.stringify(meta));
@@ -0,0 +1,9 @@
+export const url = import.meta.url;
+export const dirname = import.meta.dirname;
+export const filename = import.meta.filename;
+export const mode = import.meta.env.MODE;
+export const missing = typeof import.meta.env.MISSING;
+export const types = [typeof import.meta, typeof import.meta.url, typeof import.meta.env.MODE].join();
+export const keys = Object.keys(import.meta).join();
+export const unknown = typeof import.meta.unknown;
+export const unknownValue = import.meta.unknown;
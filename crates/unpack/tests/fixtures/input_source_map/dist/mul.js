@@ -0,0 +1,4 @@
+export function mul(a, b) {
+  return a * b;
+}
+//# sourceMappingURL=data:application/json;charset=utf-8;base64,eyJ2ZXJzaW9uIjogMywgImZpbGUiOiAibXVsLmpzIiwgInNvdXJjZXMiOiBbIi4uL3NyYy9tdWwudHMiXSwgInNvdXJjZXNDb250ZW50IjogWyIvLyBtdWx0aXBsaWVzIHR3byBudW1iZXJzXG5cbmV4cG9ydCBmdW5jdGlvbiBtdWwoYTogbnVtYmVyLCBiOiBudW1iZXIpOiBudW1iZXIge1xuICByZXR1cm4gYSAqIGI7XG59XG4iXSwgIm5hbWVzIjogW10sICJtYXBwaW5ncyI6ICJBQUVBO0VBQ0U7QUFDRiJ9
@@ -0,0 +1,4 @@
+export function sub(a, b) {
+  return a - b;
+}
+//# sourceMappingURL=sub.js.map
@@ -0,0 +1,6 @@
+import { add } from "./dist/add.js";
+import { mul } from "./dist/mul.js";
+import { sub } from "./dist/sub.js";
+import { banner } from "./src/banner.mjs";
+
+console.log(add(1, 2), mul(2, 3), sub(3, 1), banner);
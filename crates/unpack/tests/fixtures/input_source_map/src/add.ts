@@ -0,0 +1,5 @@
+// adds two numbers
+
+export function add(a: number, b: number): number {
+  return a + b;
+}
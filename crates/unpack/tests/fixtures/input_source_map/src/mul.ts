@@ -0,0 +1,5 @@
+// multiplies two numbers
+
+export function mul(a: number, b: number): number {
+  return a * b;
+}
@@ -0,0 +1 @@
+module.exports = { named: "commonjs named" };
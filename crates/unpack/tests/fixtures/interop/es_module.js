@@ -0,0 +1,3 @@
+exports.__esModule = true;
+exports.default = "es module default";
+exports.named = "es module named";
@@ -0,0 +1 @@
+import "./namespaces.mjs";
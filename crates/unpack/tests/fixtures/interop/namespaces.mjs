@@ -0,0 +1,9 @@
+import * as commonjs from "./commonjs.js";
+import * as esModule from "./es_module.js";
+import * as json from "./data.json";
+import commonjsDefault from "./commonjs.js";
+
+console.log(commonjs.default === commonjsDefault, commonjs.default.named, commonjs.named);
+console.log(esModule.default, esModule.named);
+console.log(json.default.named, json.named);
+console.log(commonjs === commonjs, Object.keys(json).sort().join());
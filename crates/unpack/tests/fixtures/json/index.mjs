@@ -0,0 +1,8 @@
+import data from "./data.json";
+
+console.log(
+  Object.keys(data).join(),
+  Object.getPrototypeOf(data) === Object.prototype,
+  data.polluted,
+  JSON.stringify(data),
+);
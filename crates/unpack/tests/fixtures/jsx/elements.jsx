@@ -0,0 +1,5 @@
+import { React } from "./react.mjs";
+
+export { spaced, selfClosing } from "./spaced.jsx";
+export const fragment = <>a{"b"}</>;
+export const comparison = 1 < 2;
@@ -0,0 +1,3 @@
+import { spaced, selfClosing, fragment, comparison } from "./elements.jsx";
+
+console.log(spaced, selfClosing, fragment, comparison);
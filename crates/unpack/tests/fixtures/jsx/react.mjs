@@ -0,0 +1,4 @@
+export const React = {
+  createElement: (type, props, ...children) => `${type}(${children.join(",")})`,
+  Fragment: "fragment",
+};
@@ -0,0 +1,4 @@
+import { React } from "./react.mjs";
+
+export const spaced = <span>spaced< /span>;
+export const selfClosing = <br / >;
@@ -0,0 +1,7 @@
+export const app = (
+  <div id="app">
+    <span>a</span>
+    {"b"}
+  </div>
+);
+export const fragment = <>c</>;
@@ -0,0 +1,3 @@
+import { app, fragment } from "./app.jsx";
+
+console.log(app, fragment);
@@ -0,0 +1 @@
+export const untouched = "__MESSAGE__";
@@ -0,0 +1,5 @@
+import data from "./data.yml";
+import { message } from "./message.mjs";
+import { untouched } from "./excluded/untouched.mjs";
+
+console.log(data.name, data.version, message, untouched);
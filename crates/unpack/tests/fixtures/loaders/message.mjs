@@ -0,0 +1 @@
+export const message = "__MESSAGE__";
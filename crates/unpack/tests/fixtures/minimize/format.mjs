@@ -0,0 +1,5 @@
+/*! format v1.0.0 | MIT */
+export function format(value) {
+  const formattedValueWithALongName = "sum: " + value;
+  return formattedValueWithALongName;
+}
@@ -0,0 +1,8 @@
+// @license MIT
+import { format } from "./format.mjs";
+import { sum } from "./sum.mjs";
+
+if (process.env.NEVER_SET === "yes") {
+  console.log("dead");
+}
+console.log(format(sum([1, 2, 3])));
@@ -0,0 +1,9 @@
+/*! format v1.0.0 | MIT */
+/* a plain comment which is removed */
+export function sum(numbers) {
+  let accumulatedTotalWithALongName = 0;
+  for (const number of numbers) {
+    accumulatedTotalWithALongName += number;
+  }
+  return accumulatedTotalWithALongName;
+}
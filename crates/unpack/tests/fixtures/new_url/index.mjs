@@ -0,0 +1,4 @@
+const asset = new URL("./file.txt", import.meta.url);
+const external = new URL("https://example.com/a.png", import.meta.url);
+
+console.log(asset.protocol, asset.pathname.split("/").pop(), external.href);
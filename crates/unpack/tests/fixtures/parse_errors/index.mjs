@@ -0,0 +1 @@
+import "./broken.mjs";
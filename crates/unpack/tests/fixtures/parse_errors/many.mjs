@@ -0,0 +1,4 @@
+export const a = 1;
+const b = /a/gg;
+const c = 2;
+const d = "\u{110000}";
@@ -0,0 +1,29 @@
+import { devtools, log } from './lib';
+
+if (process.env.NODE_ENV !== "production") {
+  devtools(new URL("./dev-only.png", import.meta.url));
+} else if (typeof window === "undefined") {
+  log("server");
+} else {
+  log(process.env.NODE_ENV);
+}
+if (!__DEV__) log("production");
+if (__DEV__) {
+  var hoisted = devtools;
+}
+if (__DEV__ && log()) {
+  function declared() {}
+}
+if (__DEV__) {
+  const scoped = () => {
+    var nested = devtools;
+  };
+} else if (null == undefined) {
+  log(hoisted);
+}
+const worker = __DEV__ ? new Worker(new URL("./dev-worker.js", import.meta.url)) : null;
+const mode = (import.meta.env.MODE === "production") ? log : devtools;
+export const debug = __DEV__ && devtools(log);
+export const fallback = process.env.NODE_ENV || devtools;
+export const nullish = __DEV__ ?? devtools;
+console.log(typeof __VERSION__ === "number" ? log : devtools, 1 == "1" ? log : devtools);
@@ -0,0 +1,10 @@
+if (process.env.NODE_ENV !== "production") {
+  console.log(__DEV__, process.env["NODE_ENV"], { __DEV__ }, __VERSION__ * 2);
+}
+console.log(typeof process.env.NODE_ENV, typeof __DEV__, typeof window, typeof __VERSION__);
+function shadowed(process, __DEV__) {
+  return [process.env.NODE_ENV, __DEV__];
+}
+process.env.NODE_ENV = "test";
+__DEV__++;
+console.log(process?.env.NODE_ENV, process.env.OTHER);
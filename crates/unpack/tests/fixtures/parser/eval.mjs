@@ -0,0 +1,7 @@
+import { a } from './a';
+
+const local = a;
+export function run(code) {
+  return eval(code);
+}
+export default (0, eval)(local);
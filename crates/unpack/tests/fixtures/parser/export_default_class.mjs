@@ -0,0 +1,3 @@
+export default class extends Object {
+  static value = 42;
+}
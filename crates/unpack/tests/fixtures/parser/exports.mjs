@@ -0,0 +1,5 @@
+import { helper } from './helper';
+export const answer = 42;
+export default function main() {
+  return helper(answer);
+}
@@ -0,0 +1,9 @@
+const url = import.meta.url;
+const { dirname, filename } = import.meta;
+console.log(import.meta.dirname, import.meta.filename, import.meta.url.length);
+console.log(import.meta.env.MODE, import.meta.env.BASE_URL, import.meta.env);
+console.log(import.meta["url"], typeof import.meta.url, typeof import.meta.env.MODE);
+if (import.meta.hot) {
+  import.meta.hot.accept();
+}
+export const worker = new URL("./worker.js", import.meta.url);
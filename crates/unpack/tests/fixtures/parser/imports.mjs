@@ -0,0 +1,7 @@
+import './side-effect';
+import defaultExport from "./default";
+import { named, other as renamed } from './named';
+import * as namespace from './namespace';
+import fallback, { mixed } from './mixed';
+
+console.log(defaultExport, named, renamed, namespace, fallback, mixed);
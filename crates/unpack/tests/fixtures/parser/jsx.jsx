@@ -0,0 +1,8 @@
+import React from 'react';
+import { Button } from './button';
+
+export const App = () => (
+  <div className="app">
+    <Button label="hello" />
+  </div>
+);
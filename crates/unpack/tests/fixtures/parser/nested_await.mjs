@@ -0,0 +1,4 @@
+export async function load() {
+  await Promise.resolve();
+}
+export const lazy = async () => await load();
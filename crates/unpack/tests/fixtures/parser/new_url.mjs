@@ -0,0 +1,9 @@
+const logo = new URL("./logo.png", import.meta.url);
+const data = new URL("data:text/plain,hello", import.meta.url);
+const remote = new URL("https://example.com/a.png", import.meta.url);
+const other = new URL("./other.png", location.href);
+const dynamic = new URL(`./${name}.png`, import.meta.url);
+console.log(new URL('./nested/file.txt', import.meta["url"]).href);
+function shadowed(URL) {
+  return new URL("./shadowed.png", import.meta.url);
+}
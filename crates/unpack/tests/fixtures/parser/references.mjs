@@ -0,0 +1,28 @@
+console.log(hoisted);
+import data, { name as alias, nested } from './data.json';
+import * as ns from './lib';
+import { hoisted, tag, run } from './lib';
+export { alias, ns as namespace };
+export { one, two as three } from './numbers';
+export * from './star';
+export * as starNs from './star';
+export const { a, b: [c, ...rest] } = data;
+export default (alias, nested);
+
+run();
+run?.();
+tag`x${alias}`;
+new ns.Klass(nested);
+const object = { alias, [name]: data, nested: nested.deep };
+({ alias } = object);
+[alias] = [];
+function shadow(alias, data) {
+  const ns = 1;
+  return alias + data + ns;
+}
+class Foo extends ns.Base {
+  static value = run(alias);
+  method() {
+    return { data };
+  }
+}
@@ -0,0 +1,5 @@
+var a = 1;
+function add(x, y) {
+  return x + y;
+}
+module.exports = add(a, 2);
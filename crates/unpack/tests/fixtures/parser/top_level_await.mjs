@@ -0,0 +1,14 @@
+const data = await fetch("./data.json");
+async function load() {
+  return await data.json();
+}
+const lazy = async () => {
+  for await (const chunk of data.body) {
+    console.log(chunk);
+  }
+};
+class Loader {
+  async load() {
+    await load();
+  }
+}
@@ -0,0 +1,36 @@
+import { shared, helper as help } from './shared';
+
+const count = 1;
+let { a, b: renamed, c = count, ...rest } = shared;
+var [first, [second = a]] = [];
+
+function increment(value) {
+  const count = value + 1;
+  return { count, value };
+}
+
+class Counter {
+  count = count;
+  static create() {
+    return new Counter();
+  }
+}
+
+const named = function inner() {
+  return inner;
+};
+
+if (count) {
+  var hoisted = { a, first };
+}
+
+outer: for (const item of [rest]) {
+  ({ a, c = 2 } = item);
+  break outer;
+}
+
+const { nested = { count } } = {};
+
+export { increment as inc, renamed };
+export const exported = help(hoisted, second, Counter, named, nested);
+export default count + undefinedGlobal;
@@ -0,0 +1,6 @@
+async function* numbers() {
+  yield 1;
+}
+for await (const n of numbers()) {
+  console.log(n);
+}
@@ -0,0 +1,6 @@
+// 中文注释 with multi-byte characters before the imports 🎉
+const greeting = "你好, мир";
+import { value } from './value';
+/* ünïcödé */ import './after-comment';
+
+console.log(greeting, value);
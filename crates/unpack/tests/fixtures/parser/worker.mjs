@@ -0,0 +1,7 @@
+const worker = new Worker(new URL("./worker.js", import.meta.url), { type: "module", name: import.meta.env.MODE });
+const shared = new SharedWorker(new URL('./shared.js', import.meta.url));
+const remote = new Worker(new URL("https://example.com/worker.js", import.meta.url));
+const plain = new Worker("./plain.js");
+function local(Worker) {
+  return new Worker(new URL("./local.js", import.meta.url));
+}
@@ -0,0 +1,3 @@
+import { increment } from "./shared.mjs";
+
+export const a = increment();
@@ -0,0 +1,3 @@
+import { increment } from "./nested/../shared.mjs";
+
+export const b = increment();
@@ -0,0 +1,7 @@
+import { a } from "./a.mjs";
+import { b } from "./b.mjs";
+import { c } from "./nested/c.mjs";
+import { count, increment } from "./shared.mjs";
+import { count as sameCount } from "./shared.mjs";
+
+console.log(a, b, c, count, sameCount, increment === globalThis.increment);
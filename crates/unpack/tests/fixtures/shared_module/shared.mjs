@@ -0,0 +1,8 @@
+export let count = 0;
+
+export function increment() {
+  return ++count;
+}
+
+globalThis.increment = increment;
+console.log("shared evaluated");
@@ -0,0 +1,5 @@
+import { a, c } from "pure";
+import "globs";
+import "./local.mjs";
+
+console.log(a, c);
@@ -0,0 +1,6 @@
+import { shared } from "./shared.mjs";
+import { lib } from "lib";
+import { only } from "./only_a.mjs";
+import "./style.css";
+
+console.log("a", shared, lib, only);
@@ -0,0 +1,5 @@
+import { shared } from "./shared.mjs";
+import { lib } from "lib";
+import "./style.css";
+
+console.log("b", shared, lib);
@@ -0,0 +1 @@
+export const only = "only a";
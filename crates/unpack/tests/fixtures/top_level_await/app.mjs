@@ -0,0 +1,7 @@
+import { log } from "./log.mjs";
+import { config } from "./config.mjs";
+import { value } from "./sync.mjs";
+
+log(`app ${config} ${value}`);
+
+export { config };
@@ -0,0 +1,5 @@
+import { log } from "./log.mjs";
+
+log("config start");
+export const config = await new Promise((resolve) => setTimeout(() => resolve("loaded"), 10));
+log("config end");
@@ -0,0 +1,6 @@
+import { log } from "./log.mjs";
+import { b, getCycle } from "./cycle_b.mjs";
+
+await Promise.resolve();
+export const cycle = `a${b}`;
+log(`cycle a ${getCycle()}`);
@@ -0,0 +1,7 @@
+import { log } from "./log.mjs";
+import { cycle } from "./cycle_a.mjs";
+
+await Promise.resolve();
+log("cycle b");
+export const b = "b";
+export const getCycle = () => cycle;
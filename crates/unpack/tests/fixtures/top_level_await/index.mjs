@@ -0,0 +1,5 @@
+import { log } from "./log.mjs";
+import { config } from "./app.mjs";
+import { cycle } from "./cycle_a.mjs";
+
+log(`index ${config} ${cycle}`);
@@ -0,0 +1,6 @@
+export const logs = [];
+
+export function log(message) {
+  logs.push(message);
+  console.log(message);
+}
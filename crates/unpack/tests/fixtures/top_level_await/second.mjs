@@ -0,0 +1,4 @@
+import { log } from "./log.mjs";
+import { config } from "./config.mjs";
+
+log(`second ${config}`);
@@ -0,0 +1,4 @@
+import { log } from "./log.mjs";
+
+log("sync");
+export const value = "sync";
@@ -0,0 +1,8 @@
+import type { Shape } from "./types";
+import { Color } from "./color";
+import { render } from "./view";
+
+const shape: Shape = { kind: "circle", size: 2 };
+const area = (s: Shape): number => s.size * s.size;
+
+console.log(Color.Red, Color.Green, Color[0], area(shape), render(shape));
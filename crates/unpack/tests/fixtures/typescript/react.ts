@@ -0,0 +1,4 @@
+export const React = {
+  createElement: (type: string, props: object | null, ...children: unknown[]): string =>
+    `${type}(${children.join(",")})`,
+};
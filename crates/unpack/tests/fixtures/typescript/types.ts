@@ -0,0 +1,6 @@
+export interface Shape {
+  kind: Kind;
+  size: number;
+}
+
+export type Kind = "circle" | "square";
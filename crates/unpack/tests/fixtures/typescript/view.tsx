@@ -0,0 +1,6 @@
+import { React } from "./react";
+import type { Shape } from "./types";
+
+export function render(shape: Shape): string {
+  return <div kind={shape.kind}>{shape.size as number}</div>;
+}
@@ -0,0 +1,2 @@
+exports.value = "cjs";
+exports.unused = "unused";
@@ -0,0 +1,5 @@
+import { used, reexported } from "./reexport.mjs";
+import * as ns from "./namespace.mjs";
+import cjs from "./cjs.js";
+
+console.log(used, reexported, Object.keys(ns).join(), cjs.value);
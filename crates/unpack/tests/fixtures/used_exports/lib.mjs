@@ -0,0 +1,5 @@
+export const used = "used";
+export const unused = "unused";
+export function unusedFunction() {
+  return "unused function";
+}
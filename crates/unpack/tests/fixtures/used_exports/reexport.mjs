@@ -0,0 +1,2 @@
+export { used, unused } from "./lib.mjs";
+export * from "./star.mjs";
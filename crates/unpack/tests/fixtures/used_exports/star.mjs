@@ -0,0 +1,2 @@
+export const reexported = "reexported";
+export const notReexported = "not reexported";
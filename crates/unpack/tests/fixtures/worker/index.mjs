@@ -0,0 +1,10 @@
+import { greeting } from "./shared.mjs";
+
+// the output is a commonjs script in node, which runs workers with `worker_threads`
+globalThis.Worker = require("node:worker_threads").Worker;
+
+const worker = new Worker(new URL("./worker.mjs", import.meta.url));
+worker.on("message", (message) => {
+  console.log(`${greeting} main, ${message}`);
+  worker.terminate();
+});
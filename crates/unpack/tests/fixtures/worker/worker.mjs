@@ -0,0 +1,3 @@
+import { greeting } from "./shared.mjs";
+
+require("node:worker_threads").parentPort.postMessage(`${greeting} from worker`);
@@ -0,0 +1,147 @@
+mod common;
+
+use std::fs;
+
+use camino::Utf8PathBuf;
+use serde_json::Value;
+use unpack::compiler::{CompilerOptions, EntryItem};
+use xxhash_rust::xxh64::xxh64;
+
+/// `main` with `VERSION` defined as `version` and its css, and `other` which doesn't use it
+fn build(version: &str, configure: impl FnOnce(&mut CompilerOptions)) -> common::Output {
+    common::build("hashes", |options| {
+        options.entry.push(EntryItem {
+            name: "other".to_string(),
+            import: "./other.mjs".to_string(),
+        });
+        options
+            .define
+            .insert("VERSION".to_string(), format!("\"{version}\""));
+        options.output.filename = "[name].[contenthash].js".to_string();
+        options.output.css_filename = "[name].[contenthash].css".to_string();
+        configure(options);
+    })
+}
+
+fn files(output: &common::Output) -> Vec<String> {
+    output
+        .stats
+        .chunks
+        .iter()
+        .flat_map(|chunk| chunk.files.clone())
+        .collect()
+}
+
+#[test]
+fn content_hash() {
+    let output = build("1", |_| {});
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    // the default `hash_digest_length` is longer than the 16 hex digits of xxhash64
+    for file in files(&output) {
+        let (name, hash) = file.rsplit_once('.').unwrap().0.split_once('.').unwrap();
+        let content = output.read(&file);
+        assert_eq!(
+            hash,
+            format!("{:016x}", xxh64(content.as_bytes(), 0)),
+            "{name}"
+        );
+    }
+    let main = output.stats.chunks[0].files[0].clone();
+    assert!(main.starts_with("main."), "{main}");
+    assert_eq!(output.run(&main), "shared 1\n");
+}
+
+#[test]
+fn content_hash_is_stable() {
+    let first = build("1", |_| {});
+    let second = build("1", |_| {});
+    assert_eq!(files(&first), files(&second));
+    assert_eq!(first.stats.hash, second.stats.hash);
+}
+
+#[test]
+fn only_changed_files_get_new_hashes() {
+    let first = build("1", |_| {});
+    let second = build("2", |_| {});
+    let (first_files, second_files) = (files(&first), files(&second));
+    // `main.js` uses `VERSION`, its css and `other.js` are unchanged
+    assert_ne!(first_files[0], second_files[0]);
+    assert_eq!(first_files[1..], second_files[1..]);
+    assert_ne!(first.stats.chunks[0].hash, second.stats.chunks[0].hash);
+    assert_eq!(first.stats.chunks[1].hash, second.stats.chunks[1].hash);
+    assert_ne!(first.stats.hash, second.stats.hash);
+}
+
+#[test]
+fn chunk_hash_and_full_hash() {
+    let output = build("1", |options| {
+        options.output.filename = "[name].[chunkhash:8].[fullhash].js".to_string();
+        options.output.css_filename = "[name].[chunkhash:8].css".to_string();
+    });
+    for chunk in &output.stats.chunks {
+        let name = chunk.name.as_deref().unwrap();
+        // files of a chunk share the chunk hash
+        assert_eq!(
+            chunk.files[0],
+            format!("{name}.{}.{}.js", &chunk.hash[..8], output.stats.hash)
+        );
+        if let Some(css) = chunk.files.get(1) {
+            assert_eq!(css, &format!("{name}.{}.css", &chunk.hash[..8]));
+        }
+    }
+    assert_eq!(output.stats.hash.len(), 16);
+}
+
+#[test]
+fn manifest() {
+    let output = build("1", |options| {
+        options.output.manifest = Some("manifest.json".to_string());
+    });
+    let manifest: Value = serde_json::from_str(&output.read("manifest.json")).unwrap();
+    let files = files(&output);
+    assert_eq!(
+        manifest,
+        serde_json::json!({
+            "main.js": files[0],
+            "main.css": files[1],
+            "other.js": files[2],
+        })
+    );
+    assert!(output.stats.assets.contains(&"manifest.json".to_string()));
+}
+
+/// the `worker` fixture copied with `message` posted by the worker, filenames have content hashes
+fn build_worker(message: &str) -> common::Output {
+    let context = Utf8PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!(
+        "hashes-worker-{}-{}",
+        message.replace(' ', "-"),
+        std::process::id()
+    ));
+    fs::create_dir_all(&context).unwrap();
+    let fixture = common::fixture("worker");
+    for file in ["index.mjs", "shared.mjs", "worker.mjs"] {
+        let code = fs::read_to_string(fixture.join(file)).unwrap();
+        fs::write(context.join(file), code.replace("from worker", message)).unwrap();
+    }
+    common::build("worker", |options| {
+        options.context = context;
+        options.output.filename = "[name].[contenthash].js".to_string();
+    })
+}
+
+#[test]
+fn editing_a_worker_changes_the_hashes_of_its_importers() {
+    let first = build_worker("from worker");
+    let second = build_worker("from the edited worker");
+    assert_eq!(second.diagnostics, Vec::<String>::new());
+    let (first_files, second_files) = (files(&first), files(&second));
+    assert!(first_files[0].starts_with("main."), "{first_files:?}");
+    assert!(first_files[1].starts_with("worker_mjs."), "{first_files:?}");
+    // the worker chunk changes, and so does the filename of it in the main chunk
+    assert_ne!(first_files[0], second_files[0]);
+    assert_ne!(first_files[1], second_files[1]);
+    assert_eq!(
+        second.run(&second_files[0]),
+        "hello main, hello from the edited worker\n"
+    );
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use regex::Regex;
+use unpack::compiler::{ChunkIds, EntryItem, ModuleIds};
+use xxhash_rust::xxh64::xxh64;
+
+fn build(module_ids: ModuleIds, chunk_ids: ChunkIds, other_entry: bool) -> common::Output {
+    common::build("ids", |options| {
+        // keep the factory of each module in the output
+        options.optimization.concatenate_modules = false;
+        options.optimization.module_ids = module_ids;
+        options.optimization.chunk_ids = chunk_ids;
+        if other_entry {
+            options.entry.insert(
+                0,
+                EntryItem {
+                    name: "other".to_string(),
+                    import: "./other.mjs".to_string(),
+                },
+            );
+        }
+    })
+}
+
+/// ids of the module factories in the chunk
+fn module_ids(output: &common::Output, filename: &str) -> Vec<String> {
+    Regex::new(r"(?m)^(\S+): \(function")
+        .unwrap()
+        .captures_iter(&output.read(filename))
+        .map(|captures| captures[1].to_string())
+        .collect()
+}
+
+fn chunk_ids(output: &common::Output) -> Vec<(String, String)> {
+    output
+        .stats
+        .chunks
+        .iter()
+        .map(|chunk| (chunk.name.clone().unwrap(), chunk.id.clone()))
+        .collect()
+}
+
+fn deterministic_id(name: &str) -> String {
+    (xxh64(name.as_bytes(), 0) % 1000).to_string()
+}
+
+#[test]
+fn natural_ids() {
+    let output = build(ModuleIds::Natural, ChunkIds::Natural, true);
+    // numbered in the order the modules are imported from the entries, `./a.mjs` is shared
+    assert_eq!(module_ids(&output, "other.js"), ["0", "1", "2"]);
+    assert_eq!(module_ids(&output, "main.js"), ["1", "3", "4"]);
+    assert_eq!(
+        chunk_ids(&output),
+        [
+            ("other".to_string(), "0".to_string()),
+            ("main".to_string(), "1".to_string())
+        ]
+    );
+    assert_eq!(output.run("main.js"), "a b\n");
+}
+
+#[test]
+fn named_ids() {
+    let output = build(ModuleIds::Named, ChunkIds::Named, false);
+    let main = output.read("main.js");
+    assert_eq!(
+        module_ids(&output, "main.js"),
+        ["\"./a.mjs\"", "\"./b.mjs\"", "\"./index.mjs\""]
+    );
+    assert!(main.contains("__unpack_require__(\"./a.mjs\")"), "{main}");
+    assert_eq!(
+        chunk_ids(&output),
+        [("main".to_string(), "main".to_string())]
+    );
+    assert_eq!(output.run("main.js"), "a b\n");
+}
+
+#[test]
+fn deterministic_ids() {
+    let output = build(ModuleIds::Deterministic, ChunkIds::Deterministic, false);
+    // numeric hashes of the paths relative to context, in the range of 3 digits
+    let mut expected = ["./a.mjs", "./b.mjs", "./index.mjs"]
+        .map(deterministic_id)
+        .to_vec();
+    expected.sort_by_key(|id| id.parse::<u32>().unwrap());
+    let mut ids = module_ids(&output, "main.js");
+    ids.sort_by_key(|id| id.parse::<u32>().unwrap());
+    assert_eq!(ids, expected);
+    assert_eq!(
+        chunk_ids(&output),
+        [("main".to_string(), deterministic_id("main"))]
+    );
+    assert_eq!(output.run("main.js"), "a b\n");
+}
+
+#[test]
+fn deterministic_ids_are_stable() {
+    // adding an entry before `main` shifts the natural ids of its modules, but not the
+    // deterministic ones
+    let natural = build(ModuleIds::Natural, ChunkIds::Natural, false);
+    let natural_with_other = build(ModuleIds::Natural, ChunkIds::Natural, true);
+    assert_ne!(
+        module_ids(&natural, "main.js"),
+        module_ids(&natural_with_other, "main.js")
+    );
+    let deterministic = build(ModuleIds::Deterministic, ChunkIds::Deterministic, false);
+    let deterministic_with_other = build(ModuleIds::Deterministic, ChunkIds::Deterministic, true);
+    assert_eq!(
+        module_ids(&deterministic, "main.js"),
+        module_ids(&deterministic_with_other, "main.js")
+    );
+    assert_eq!(
+        deterministic.read("main.js"),
+        deterministic_with_other.read("main.js")
+    );
+}
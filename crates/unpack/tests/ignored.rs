@@ -0,0 +1,55 @@
+mod common;
+
+use unpack::compiler::CompilerOptions;
+use unpack::resolver::AliasValue;
+
+const IGNORED: &str = "/* (ignored) */ module.exports = {};";
+
+fn ignore_node_only(options: &mut CompilerOptions) {
+    options.resolve.alias = vec![("node-only".to_string(), vec![AliasValue::Ignore])];
+}
+
+/// identifiers of the ignored modules
+fn ignored_modules(output: &common::Output) -> Vec<&str> {
+    output
+        .stats
+        .modules
+        .iter()
+        .map(|module| module.identifier.as_str())
+        .filter(|identifier| identifier.starts_with("ignored|"))
+        .collect()
+}
+
+#[test]
+fn alias_to_false() {
+    let output = common::build("ignored", ignore_node_only);
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let context = common::fixture("ignored");
+    assert_eq!(
+        ignored_modules(&output),
+        [format!("ignored|{context}|node-only")]
+    );
+    let main = output.read("main.js");
+    assert_eq!(main.matches(IGNORED).count(), 1, "{main}");
+    // the `browser` field is not respected without `alias_fields`
+    assert_eq!(output.run("main.js"), "{} \"server\"\n");
+}
+
+#[test]
+fn browser_field_to_false() {
+    let output = common::build("ignored", |options| {
+        ignore_node_only(options);
+        options.resolve.alias_fields = vec![vec!["browser".to_string()]];
+    });
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let package = common::fixture("ignored").join("node_modules/pkg");
+    assert_eq!(ignored_modules(&output).len(), 2);
+    assert!(
+        ignored_modules(&output).contains(&format!("ignored|{package}|./server.mjs").as_str()),
+        "{:?}",
+        ignored_modules(&output)
+    );
+    let main = output.read("main.js");
+    assert_eq!(main.matches(IGNORED).count(), 2, "{main}");
+    assert_eq!(output.run("main.js"), "{} {}\n");
+}
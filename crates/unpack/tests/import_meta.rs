@@ -0,0 +1,45 @@
+mod common;
+
+use unpack::compiler::{ParserBackend, Target};
+
+fn build(target: Target, parser_backend: ParserBackend) -> common::Output {
+    common::build("import_meta", |options| {
+        options.target = target;
+        options.parser_backend = parser_backend;
+        options.define.insert(
+            "import.meta.env.MODE".to_string(),
+            "\"production\"".to_string(),
+        );
+    })
+}
+
+#[test]
+fn import_meta_is_replaced_with_constants_of_the_module() {
+    let nested = common::fixture("import_meta").join("nested");
+    for parser_backend in [ParserBackend::Swc, ParserBackend::Oxc] {
+        let output = build(Target::Node, parser_backend);
+        assert_eq!(
+            output.diagnostics,
+            ["unknown property `import.meta.unknown`, it is replaced with `undefined`"]
+        );
+        assert_eq!(
+            output.run("main.js"),
+            format!(
+                "{{\"url\":\"file://{nested}/meta.mjs\",\"dirname\":\"{nested}\",\"filename\":\"{nested}/meta.mjs\",\"mode\":\"production\",\"missing\":\"undefined\",\"types\":\"object,string,string\",\"keys\":\"url,dirname,filename,env\",\"unknown\":\"undefined\"}}\n"
+            ),
+            "{parser_backend:?}"
+        );
+    }
+}
+
+#[test]
+fn import_meta_paths_are_only_available_in_node() {
+    let nested = common::fixture("import_meta").join("nested");
+    let output = build(Target::Web, ParserBackend::Swc);
+    assert_eq!(
+        output.run("main.js"),
+        format!(
+            "{{\"url\":\"file://{nested}/meta.mjs\",\"mode\":\"production\",\"missing\":\"undefined\",\"types\":\"object,string,string\",\"keys\":\"url,env\",\"unknown\":\"undefined\"}}\n"
+        )
+    );
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use miette::Result;
+use regex::Regex;
+use rspack_sources::SourceMap;
+use unpack::compiler::{Devtool, ModuleRule, RuleCondition};
+use unpack::loader::{Loader, LoaderContext};
+
+/// prepends a banner and returns the map back to the original lines
+#[derive(Debug)]
+struct BannerLoader;
+
+#[async_trait]
+impl Loader for BannerLoader {
+    fn name(&self) -> &str {
+        "banner"
+    }
+    async fn run(&self, loader_context: &mut LoaderContext) -> Result<()> {
+        let content = String::from_utf8(loader_context.content.clone()).unwrap();
+        let map = serde_json::json!({
+            "version": 3,
+            "sources": [loader_context.resource_path.as_str()],
+            "sourcesContent": [content],
+            "names": [],
+            "mappings": ";;AAAA",
+        });
+        loader_context.content = format!("// generated\n// by a loader\n{content}").into_bytes();
+        loader_context.source_map = Some(SourceMap::from_json(&map.to_string()).unwrap());
+        Ok(())
+    }
+}
+
+fn build(devtool: Devtool) -> common::Output {
+    common::build("input_source_map", |options| {
+        options.devtool = devtool;
+        options.module.rules.push(ModuleRule {
+            test: Some(RuleCondition::Regex(Regex::new(r"banner\.mjs$").unwrap())),
+            loaders: vec![Arc::new(BannerLoader)],
+            ..Default::default()
+        });
+    })
+}
+
+fn position(source: &str, line: u32) -> (String, u32) {
+    (format!("unpack:///./{source}"), line)
+}
+
+#[test]
+fn input_source_maps() {
+    let output = build(Devtool::SourceMap);
+    let sub = common::fixture("input_source_map").join("dist/sub.js");
+    assert_eq!(output.diagnostics.len(), 1);
+    assert!(
+        output.diagnostics[0].starts_with(&format!(
+            "failed to load the source map of {sub}: failed to read {sub}.map: "
+        )),
+        "{}",
+        output.diagnostics[0]
+    );
+    let main = output.read("main.js");
+    // the comments of the modules are removed, only the one of the chunk is left
+    assert_eq!(main.matches("sourceMappingURL").count(), 1, "{main}");
+    let map = SourceMap::from_json(&output.read("main.js.map")).unwrap();
+    // a map file next to the module and a data url, the sources are relative to the map
+    assert_eq!(
+        common::original_position("return a + b", &map, &main),
+        position("src/add.ts", 4)
+    );
+    assert_eq!(
+        common::original_position("return a * b", &map, &main),
+        position("src/mul.ts", 4)
+    );
+    // a module whose map failed to load is mapped to itself
+    assert_eq!(
+        common::original_position("return a - b", &map, &main),
+        position("dist/sub.js", 2)
+    );
+    // the map returned by the loader
+    assert_eq!(
+        common::original_position("const banner", &map, &main),
+        position("src/banner.mjs", 1)
+    );
+    let sources_content = map
+        .sources()
+        .iter()
+        .zip(map.sources_content())
+        .find(|(source, _)| source.ends_with("add.ts"))
+        .map(|(_, content)| content.as_str());
+    assert_eq!(
+        sources_content,
+        Some(include_str!("fixtures/input_source_map/src/add.ts"))
+    );
+    assert_eq!(output.run("main.js"), "3 6 2 banner\n");
+}
+
+#[test]
+fn input_source_maps_without_devtool() {
+    let output = build(Devtool::None);
+    // the maps are not loaded, the comments are still removed
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    assert!(!output.read("main.js").contains("sourceMappingURL"));
+}
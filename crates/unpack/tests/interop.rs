@@ -0,0 +1,27 @@
+mod common;
+
+const EXPECTED: &str = "\
+true commonjs named commonjs named
+es module default es module named
+json named json named
+true default,named
+";
+
+#[test]
+fn namespace_imports_of_commonjs_and_json() {
+    let output = common::build("interop", |options| {
+        options.optimization.concatenate_modules = false;
+    });
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    assert_eq!(output.run("main.js"), EXPECTED);
+}
+
+#[test]
+fn namespace_imports_of_commonjs_and_json_in_concatenated_modules() {
+    let output = common::build("interop", |_| {});
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    assert!(output
+        .read("main.js")
+        .contains("// CONCATENATED MODULE: ./namespaces.mjs"));
+    assert_eq!(output.run("main.js"), EXPECTED);
+}
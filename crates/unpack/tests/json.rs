@@ -0,0 +1,20 @@
+mod common;
+
+const EXPECTED: &str =
+    "__proto__,name true undefined {\"__proto__\":{\"polluted\":true},\"name\":\"data\"}\n";
+
+#[test]
+fn proto_key_is_an_own_property() {
+    let output = common::build("json", |options| {
+        options.optimization.concatenate_modules = false;
+    });
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    assert_eq!(output.run("main.js"), EXPECTED);
+}
+
+#[test]
+fn proto_key_is_an_own_property_in_concatenated_modules() {
+    let output = common::build("json", |_| {});
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    assert_eq!(output.run("main.js"), EXPECTED);
+}
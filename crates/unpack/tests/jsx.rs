@@ -0,0 +1,71 @@
+mod common;
+
+use unpack::compiler::{JsxOptions, JsxRuntime, ParserBackend};
+
+const EXPECTED: &str = "span(spaced) br() fragment(a,b) true\n";
+
+#[test]
+fn jsx_is_found_by_the_parser() {
+    let output = common::build("jsx", |_| {});
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    assert_eq!(output.run("main.js"), EXPECTED);
+}
+
+#[test]
+fn jsx_is_found_by_the_parser_with_oxc() {
+    let output = common::build("jsx", |options| {
+        options.parser_backend = ParserBackend::Oxc;
+    });
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    assert_eq!(output.run("main.js"), EXPECTED);
+}
+
+/// `configure` sets the options of the automatic runtime, returns the output of the bundle and
+/// the bundled modules relative to the fixture
+fn build_automatic(configure: impl FnOnce(&mut JsxOptions)) -> (String, Vec<String>) {
+    let output = common::build("jsx_runtime", |options| {
+        options.jsx.runtime = JsxRuntime::Automatic;
+        configure(&mut options.jsx);
+    });
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let context = common::fixture("jsx_runtime");
+    let modules = output
+        .stats
+        .modules
+        .iter()
+        .map(|module| {
+            module
+                .identifier
+                .strip_prefix(&format!("{context}/"))
+                .unwrap_or(&module.identifier)
+                .to_string()
+        })
+        .collect();
+    (output.run("main.js"), modules)
+}
+
+#[test]
+fn automatic_runtime_is_bundled() {
+    let (stdout, modules) = build_automatic(|_| {});
+    assert_eq!(
+        modules,
+        ["app.jsx", "index.mjs", "node_modules/react/jsx-runtime.mjs"]
+    );
+    assert_eq!(stdout, "react:div(react:span(a),b) react:fragment(c)\n");
+}
+
+#[test]
+fn automatic_runtime_with_import_source() {
+    let (stdout, modules) = build_automatic(|jsx| {
+        jsx.import_source = "preact".to_string();
+    });
+    assert_eq!(
+        modules,
+        [
+            "app.jsx",
+            "index.mjs",
+            "node_modules/preact/jsx-runtime.mjs"
+        ]
+    );
+    assert_eq!(stdout, "preact:div(preact:span(a),b) preact:fragment(c)\n");
+}
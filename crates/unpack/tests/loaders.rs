@@ -0,0 +1,120 @@
+mod common;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use miette::{miette, Result};
+use regex::Regex;
+use unpack::compiler::{ModuleRule, RuleCondition};
+use unpack::loader::{Loader, LoaderContext};
+use unpack::module::ModuleType;
+
+/// turns `key: value` lines into a json object
+#[derive(Debug)]
+struct YamlLoader;
+
+#[async_trait]
+impl Loader for YamlLoader {
+    fn name(&self) -> &str {
+        "yaml"
+    }
+    async fn run(&self, loader_context: &mut LoaderContext) -> Result<()> {
+        let content = String::from_utf8(loader_context.content.clone()).unwrap();
+        let object = content
+            .lines()
+            .filter_map(|line| line.split_once(": "))
+            .map(|(key, value)| (key.to_string(), serde_json::Value::from(value)))
+            .collect::<serde_json::Map<_, _>>();
+        loader_context.content = serde_json::to_vec(&object).unwrap();
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct ReplaceLoader {
+    from: &'static str,
+    to: &'static str,
+}
+
+#[async_trait]
+impl Loader for ReplaceLoader {
+    fn name(&self) -> &str {
+        "replace"
+    }
+    async fn run(&self, loader_context: &mut LoaderContext) -> Result<()> {
+        let content = String::from_utf8(loader_context.content.clone()).unwrap();
+        loader_context.content = content.replace(self.from, self.to).into_bytes();
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct FailingLoader;
+
+#[async_trait]
+impl Loader for FailingLoader {
+    fn name(&self) -> &str {
+        "failing"
+    }
+    async fn run(&self, _loader_context: &mut LoaderContext) -> Result<()> {
+        Err(miette!("unsupported content"))
+    }
+}
+
+fn regex(pattern: &str) -> Option<RuleCondition> {
+    Some(RuleCondition::Regex(Regex::new(pattern).unwrap()))
+}
+
+#[test]
+fn loaders_of_matching_rules() {
+    let output = common::build("loaders", |options| {
+        options.module.rules = vec![
+            ModuleRule {
+                test: regex(r"\.yml$"),
+                module_type: Some(ModuleType::Json),
+                loaders: vec![Arc::new(YamlLoader)],
+                ..Default::default()
+            },
+            // loaders run from the last to the first
+            ModuleRule {
+                test: regex(r"\.mjs$"),
+                exclude: regex("excluded"),
+                loaders: vec![
+                    Arc::new(ReplaceLoader {
+                        from: "__MESSAGE__",
+                        to: "hello __NAME__",
+                    }),
+                    Arc::new(ReplaceLoader {
+                        from: "__NAME__",
+                        to: "loaders",
+                    }),
+                ],
+                ..Default::default()
+            },
+        ];
+    });
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    assert_eq!(
+        output.run("main.js"),
+        "unpack 1 hello __NAME__ __MESSAGE__\n"
+    );
+}
+
+#[test]
+fn failing_loaders_are_reported() {
+    let output = common::build("loaders", |options| {
+        options.module.rules = vec![ModuleRule {
+            test: regex(r"\.yml$"),
+            module_type: Some(ModuleType::Json),
+            loaders: vec![Arc::new(YamlLoader), Arc::new(FailingLoader)],
+            ..Default::default()
+        }];
+    });
+    assert_eq!(
+        output.diagnostics,
+        [format!(
+            "loader \"failing\" failed to process {}",
+            common::fixture("loaders").join("data.yml")
+        )]
+    );
+}
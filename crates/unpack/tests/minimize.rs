@@ -0,0 +1,88 @@
+mod common;
+
+use rspack_sources::SourceMap;
+use unpack::compiler::{Devtool, MinimizerOptions};
+
+fn build(minimizer: MinimizerOptions) -> common::Output {
+    common::build("minimize", |options| {
+        options.optimization.minimize = true;
+        options.optimization.minimizer = minimizer;
+    })
+}
+
+#[test]
+fn minimize() {
+    let output = build(MinimizerOptions::default());
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let main = output.read("main.js");
+    // license comments are kept once at the top, the others are removed
+    let (licenses, code) = main.split_at(main.find("(()=>").unwrap());
+    assert_eq!(licenses, "/*! format v1.0.0 | MIT */\n// @license MIT\n");
+    assert_eq!(code.lines().count(), 1, "{main}");
+    assert!(!code.contains("/*"), "{main}");
+    assert!(!code.contains("WithALongName"), "{main}");
+    assert_eq!(output.run("main.js"), "sum: 6\n");
+}
+
+#[test]
+fn minimize_without_compress_and_mangle() {
+    let output = build(MinimizerOptions {
+        compress: false,
+        mangle: false,
+    });
+    let main = output.read("main.js");
+    // only whitespaces and comments are removed
+    assert!(main.contains("formattedValueWithALongName"), "{main}");
+    assert!(main.contains("accumulatedTotalWithALongName"), "{main}");
+    assert!(main.contains("\"yes\""), "{main}");
+    assert!(!main.contains("a plain comment"), "{main}");
+    assert!(!main.contains("\n  "), "{main}");
+    assert_eq!(output.run("main.js"), "sum: 6\n");
+}
+
+#[test]
+fn minimize_with_source_map() {
+    let output = common::build("minimize", |options| {
+        options.optimization.minimize = true;
+        options.devtool = Devtool::SourceMap;
+    });
+    let main = output.read("main.js");
+    assert!(
+        main.ends_with("\n//# sourceMappingURL=main.js.map\n"),
+        "{main}"
+    );
+    let map = SourceMap::from_json(&output.read("main.js.map")).unwrap();
+    // the minified code maps to the original modules rather than the rendered chunk
+    let mut sources = map.sources().to_vec();
+    sources.sort();
+    assert_eq!(
+        sources,
+        [
+            "unpack:///./format.mjs",
+            "unpack:///./index.mjs",
+            "unpack:///./sum.mjs"
+        ]
+    );
+    let (line, code) = main
+        .lines()
+        .enumerate()
+        .find(|(_, line)| line.contains("\"sum: \""))
+        .unwrap();
+    let column = code.find("\"sum: \"").unwrap() as u32;
+    let original = map
+        .decoded_mappings()
+        .filter(|mapping| {
+            mapping.generated_line == line as u32 + 1 && mapping.generated_column <= column
+        })
+        .last()
+        .and_then(|mapping| mapping.original)
+        .unwrap();
+    assert_eq!(
+        (
+            map.sources()[original.source_index as usize].as_str(),
+            original.original_line
+        ),
+        ("unpack:///./format.mjs", 3)
+    );
+    assert_eq!(output.run("main.js"), "sum: 6\n");
+}
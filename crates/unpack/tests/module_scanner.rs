@@ -0,0 +1,43 @@
+mod common;
+
+const EXPECTED: &str = "\
+shared evaluated
+1 2 3 3 3 true
+";
+
+/// `shared.mjs` is imported by different requests from modules which are built concurrently,
+/// it is built once and every import is connected to the same module
+#[test]
+fn modules_imported_concurrently_are_built_once() {
+    for _ in 0..10 {
+        let output = common::build("shared_module", |options| {
+            options.optimization.concatenate_modules = false;
+        });
+        assert_eq!(output.diagnostics, Vec::<String>::new());
+        let identifiers = output
+            .stats
+            .modules
+            .iter()
+            .map(|module| module.identifier.rsplit('/').next().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            identifiers,
+            ["a.mjs", "b.mjs", "index.mjs", "c.mjs", "shared.mjs"]
+        );
+        assert_eq!(output.run("main.js"), EXPECTED);
+    }
+}
+
+#[test]
+fn modules_imported_concurrently_are_concatenated_once() {
+    let output = common::build("shared_module", |_| {});
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    assert_eq!(
+        output
+            .read("main.js")
+            .matches("// CONCATENATED MODULE: ./shared.mjs")
+            .count(),
+        1
+    );
+    assert_eq!(output.run("main.js"), EXPECTED);
+}
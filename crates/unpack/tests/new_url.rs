@@ -0,0 +1,25 @@
+mod common;
+
+use std::fs;
+
+#[test]
+fn new_url_references_emitted_assets() {
+    let output = common::build("new_url", |options| {
+        options.output.asset_module_filename = "[name][ext]".to_string();
+    });
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    // the file is always emitted, even if `asset/source` is inferred from the extension
+    assert_eq!(
+        fs::read_to_string(output.path.join("file.txt")).unwrap(),
+        "text asset\n"
+    );
+    let main = output.read("main.js");
+    assert!(main.contains("new URL(__unpack_require__("));
+    // external urls are left as they are
+    assert!(main.contains("new URL(\"https://example.com/a.png\", "));
+    // the url is resolved against the output directory at runtime
+    assert_eq!(
+        output.run("main.js"),
+        "file: file.txt https://example.com/a.png\n"
+    );
+}
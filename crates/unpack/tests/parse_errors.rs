@@ -0,0 +1,78 @@
+mod common;
+
+use unpack::compiler::ParserBackend;
+
+#[test]
+fn js_parse_error() {
+    for parser_backend in [ParserBackend::Swc, ParserBackend::Oxc] {
+        let output = common::build("parse_errors", |options| {
+            options.parser_backend = parser_backend;
+        });
+        assert_eq!(output.diagnostics.len(), 1, "{parser_backend:?}");
+        assert!(
+            output.diagnostics[0].starts_with("js parse error: "),
+            "{parser_backend:?}: {}",
+            output.diagnostics[0]
+        );
+        // the code frame points at the missing expression in the imported module
+        let path = common::fixture("parse_errors").join("broken.mjs");
+        let report = &output.reports[0];
+        assert!(report.contains(&format!("[{path}:2:18]")), "{report}");
+        assert!(report.contains(" 2 │ export const b = ;"), "{report}");
+    }
+}
+
+#[test]
+fn every_js_parse_error_is_labeled() {
+    let path = common::fixture("parse_errors").join("many.mjs");
+    for (parser_backend, messages) in [
+        (
+            ParserBackend::Swc,
+            [
+                "Duplicated regular expression flag 'g'.",
+                "Bad character escape sequence, expected 1-6 hex characters in the range 0 to 10FFFF.",
+            ],
+        ),
+        (
+            ParserBackend::Oxc,
+            [
+                "Flag g is mentioned twice in regular expression literal",
+                "Invalid escape sequence",
+            ],
+        ),
+    ] {
+        let output = common::build("parse_errors", |options| {
+            options.parser_backend = parser_backend;
+            options.entry[0].import = "./many.mjs".to_string();
+        });
+        // one diagnostic for the module, with a label for each error
+        assert_eq!(
+            output.diagnostics,
+            [format!("js parse error: {}", messages[0])],
+            "{parser_backend:?}"
+        );
+        let report = &output.reports[0];
+        assert!(report.contains(&format!("[{path}:2:")), "{report}");
+        let positions = messages.map(|message| {
+            // long messages are wrapped in the code frame
+            let label = format!("╰── {}", message.split(',').next().unwrap());
+            assert_eq!(report.matches(&label).count(), 1, "{report}");
+            report.find(&label).unwrap()
+        });
+        assert!(positions[0] < positions[1], "{report}");
+        assert!(report.contains(" 2 │ const b = /a/gg;"), "{report}");
+        assert!(report.contains(" 4 │ const d = "), "{report}");
+    }
+}
+
+#[test]
+fn json_parse_error() {
+    let output = common::build("parse_errors", |options| {
+        options.entry[0].import = "./json.mjs".to_string();
+    });
+    assert_eq!(output.diagnostics, ["json parse error: expected value"]);
+    let path = common::fixture("parse_errors").join("broken.json");
+    let report = &output.reports[0];
+    assert!(report.contains(&format!("[{path}:4:1]")), "{report}");
+    assert!(report.contains(" 4 │ }"), "{report}");
+}
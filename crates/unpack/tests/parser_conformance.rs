@@ -0,0 +1,76 @@
+use std::fs;
+
+use camino::Utf8PathBuf;
+use unpack::compiler::{CompilerOptions, ParserBackend};
+use unpack::errors::JsParseError;
+use unpack::module::NormalModule;
+
+fn options(parser_backend: ParserBackend) -> CompilerOptions {
+    CompilerOptions {
+        parser_backend,
+        define: [
+            ("process.env.NODE_ENV", "\"production\""),
+            ("__DEV__", "false"),
+            ("__VERSION__", "1 + 1"),
+            ("typeof window", "\"object\""),
+            ("import.meta.env.MODE", "\"production\""),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect(),
+        ..Default::default()
+    }
+}
+
+fn fixtures() -> Vec<Utf8PathBuf> {
+    let dir = Utf8PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/parser");
+    let mut fixtures = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| Utf8PathBuf::try_from(entry.unwrap().path()).unwrap())
+        .collect::<Vec<_>>();
+    fixtures.sort();
+    fixtures
+}
+
+#[test]
+fn swc_and_oxc_produce_identical_parse_results() {
+    let fixtures = fixtures();
+    assert!(!fixtures.is_empty(), "no parser fixtures found");
+    for path in fixtures {
+        let content = fs::read_to_string(&path).unwrap();
+        let swc = NormalModule::parse(content.clone(), &path, &options(ParserBackend::Swc))
+            .unwrap_or_else(|err| panic!("swc failed to parse {path}: {err:?}"));
+        let oxc = NormalModule::parse(content, &path, &options(ParserBackend::Oxc))
+            .unwrap_or_else(|err| panic!("oxc failed to parse {path}: {err:?}"));
+        assert_eq!(
+            format!("{swc:#?}"),
+            format!("{oxc:#?}"),
+            "parse results of {path} differ between swc and oxc"
+        );
+    }
+}
+
+#[test]
+fn both_backends_reject_invalid_syntax() {
+    let path = Utf8PathBuf::from("invalid.js");
+    for backend in [ParserBackend::Swc, ParserBackend::Oxc] {
+        let result =
+            NormalModule::parse("import { from './a';".to_string(), &path, &options(backend));
+        assert!(result.is_err(), "{backend:?} should fail to parse");
+    }
+}
+
+#[test]
+fn parse_errors_point_at_the_source() {
+    let path = Utf8PathBuf::from("src/invalid.js");
+    let source = "const a = 1;\nlet b = (;\n";
+    for backend in [ParserBackend::Swc, ParserBackend::Oxc] {
+        let report = NormalModule::parse(source.to_string(), &path, &options(backend)).unwrap_err();
+        let error = report
+            .downcast_ref::<JsParseError>()
+            .unwrap_or_else(|| panic!("{backend:?} should report a js parse error"));
+        assert_eq!(error.source_code.name(), "src/invalid.js");
+        let label = error.labels.first().expect("parse error should be labeled");
+        assert_eq!(label.offset(), source.rfind(';').unwrap(), "{backend:?}");
+    }
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use regex::Regex;
+use unpack::compiler::{ModuleRule, RuleCondition};
+
+/// the modules concatenated into the entry module
+fn concatenated_modules(main: &str) -> Vec<&str> {
+    main.lines()
+        .filter_map(|line| line.strip_prefix("// CONCATENATED MODULE: "))
+        .collect()
+}
+
+#[test]
+fn side_effect_free_modules_are_left_out() {
+    let output = common::build("side_effects", |_| {});
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let main = output.read("main.js");
+    // the re-exports of `pure` are imported from the modules defining them, so neither the
+    // package entry nor the module of the unused export is bundled
+    assert_eq!(
+        concatenated_modules(&main),
+        [
+            "./node_modules/pure/a.mjs",
+            "./node_modules/pure/c.mjs",
+            "./node_modules/globs/index.mjs",
+            "./index.mjs"
+        ]
+    );
+    // only the files matching the globs of `sideEffects` are kept when imported for their
+    // side effects
+    assert!(main.contains("console.log(\"effect\")"), "{main}");
+    assert!(!main.contains("console.log(\"globs pure\")"), "{main}");
+    assert!(!main.contains("console.log(\"b\")"), "{main}");
+    assert!(!main.contains("console.log(\"pure\")"), "{main}");
+    assert_eq!(output.run("main.js"), "effect\nlocal\na c\n");
+}
+
+#[test]
+fn side_effects_of_module_rules() {
+    let output = common::build("side_effects", |options| {
+        options.module.rules.push(ModuleRule {
+            test: Some(RuleCondition::Regex(Regex::new(r"local\.mjs$").unwrap())),
+            side_effects: Some(false),
+            ..Default::default()
+        });
+    });
+    assert_eq!(output.run("main.js"), "effect\na c\n");
+}
+
+#[test]
+fn side_effects_disabled() {
+    for concatenate_modules in [true, false] {
+        let output = common::build("side_effects", |options| {
+            options.optimization.side_effects = false;
+            options.optimization.concatenate_modules = concatenate_modules;
+        });
+        // every module is evaluated in the same order as node
+        assert_eq!(
+            output.run("main.js"),
+            "b\npure\neffect\nglobs pure\nlocal\na c\n",
+            "concatenate_modules: {concatenate_modules}"
+        );
+    }
+}
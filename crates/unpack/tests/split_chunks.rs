@@ -0,0 +1,136 @@
+mod common;
+
+use indexmap::IndexMap;
+use regex::Regex;
+use unpack::compiler::{CacheGroup, CompilerOptions, EntryItem, RuleCondition, SplitChunksOptions};
+
+/// entries `a` and `b` share `shared.mjs`, `lib` from node_modules and `style.css`
+fn build(split_chunks: SplitChunksOptions) -> common::Output {
+    common::build("split_chunks", |options: &mut CompilerOptions| {
+        options.entry = ["a", "b"]
+            .into_iter()
+            .map(|name| EntryItem {
+                name: name.to_string(),
+                import: format!("./{name}.mjs"),
+            })
+            .collect();
+        options.optimization.split_chunks = Some(split_chunks);
+    })
+}
+
+fn chunk_names(output: &common::Output) -> Vec<&str> {
+    output
+        .stats
+        .chunks
+        .iter()
+        .map(|chunk| chunk.name.as_deref().unwrap_or_default())
+        .collect()
+}
+
+fn assert_runs(output: &common::Output) {
+    assert_eq!(output.run("a.js"), "a shared lib only a\n");
+    assert_eq!(output.run("b.js"), "b shared lib\n");
+}
+
+#[test]
+fn default_cache_groups() {
+    let output = build(SplitChunksOptions {
+        min_size: 0,
+        ..Default::default()
+    });
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    // node_modules go to `vendors` which has a higher priority than `default`, `only_a.mjs`
+    // is in fewer chunks than `min_chunks` of `default`
+    assert_eq!(
+        chunk_names(&output),
+        ["a", "b", "vendors-a-b", "default-a-b"]
+    );
+    assert!(output.read("vendors-a-b.js").contains("\"lib\""));
+    assert!(output.read("default-a-b.js").contains("\"shared\""));
+    assert!(output.read("a.js").contains("\"only a\""));
+    // css stays in the chunks which load it
+    assert!(output.read("a.css").contains(".shared"));
+    assert!(output.read("b.css").contains(".shared"));
+    assert!(!output.path.join("default-a-b.css").exists());
+    assert_runs(&output);
+}
+
+#[test]
+fn chunks_smaller_than_min_size_are_not_created() {
+    let output = build(SplitChunksOptions::default());
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    assert_eq!(chunk_names(&output), ["a", "b"]);
+    assert_runs(&output);
+}
+
+#[test]
+fn min_chunks() {
+    let output = build(SplitChunksOptions {
+        min_size: 0,
+        min_chunks: 1,
+        cache_groups: IndexMap::from_iter([(
+            "all".to_string(),
+            CacheGroup {
+                min_chunks: Some(1),
+                ..Default::default()
+            },
+        )]),
+    });
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    // modules are grouped by the chunks they are in
+    assert_eq!(chunk_names(&output), ["a", "b", "all-a-b", "all-a"]);
+    assert!(output.read("all-a.js").contains("\"only a\""));
+    assert_runs(&output);
+}
+
+#[test]
+fn groups_with_higher_priority_win() {
+    let output = build(SplitChunksOptions {
+        min_size: 0,
+        min_chunks: 2,
+        cache_groups: IndexMap::from_iter([
+            (
+                "common".to_string(),
+                CacheGroup {
+                    name: Some("common".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "shared".to_string(),
+                CacheGroup {
+                    test: Some(RuleCondition::Regex(Regex::new(r"shared\.mjs$").unwrap())),
+                    priority: 10,
+                    ..Default::default()
+                },
+            ),
+        ]),
+    });
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    assert_eq!(chunk_names(&output), ["a", "b", "shared-a-b", "common"]);
+    assert!(output.read("shared-a-b.js").contains("\"shared\""));
+    assert!(!output.read("common.js").contains("\"shared\""));
+    assert!(output.read("common.js").contains("\"lib\""));
+    assert_runs(&output);
+}
+
+#[test]
+fn names_of_existing_chunks_are_not_reused() {
+    let output = build(SplitChunksOptions {
+        min_size: 0,
+        min_chunks: 2,
+        cache_groups: IndexMap::from_iter([(
+            "common".to_string(),
+            CacheGroup {
+                name: Some("a".to_string()),
+                ..Default::default()
+            },
+        )]),
+    });
+    assert_eq!(
+        output.diagnostics,
+        ["the name `a` of split chunks cache group `common` conflicts with an existing chunk, its modules are not split"]
+    );
+    assert_eq!(chunk_names(&output), ["a", "b"]);
+    assert_runs(&output);
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use unpack::compiler::{EntryItem, SplitChunksOptions};
+
+/// the same as running `index.mjs` with node, `cycle_a.mjs` and `cycle_b.mjs` are async modules
+/// importing each other
+const EXPECTED: &str = "\
+config start
+sync
+cycle b
+cycle a ab
+config end
+app loaded sync
+index loaded ab
+";
+
+fn optimization_bailout(output: &common::Output, module: &str) -> Vec<String> {
+    output
+        .stats
+        .modules
+        .iter()
+        .find(|stats| stats.identifier.ends_with(module))
+        .unwrap_or_else(|| panic!("{module} should be in the stats"))
+        .optimization_bailout
+        .clone()
+}
+
+#[test]
+fn async_modules_are_evaluated_in_order() {
+    let output = common::build("top_level_await", |options| {
+        options.optimization.concatenate_modules = false;
+    });
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let main = output.read("main.js");
+    assert!(main.contains("__unpack_require__.a = "));
+    assert_eq!(
+        main.matches("__unpack_require__.a(__unpack_module__, async (")
+            .count(),
+        5
+    );
+    assert_eq!(output.run("main.js"), EXPECTED);
+}
+
+#[test]
+fn async_modules_are_not_concatenated() {
+    let output = common::build("top_level_await", |_| {});
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let reason = "ModuleConcatenation bailout: Module is async, it uses top level await or imports an async module";
+    for module in [
+        "index.mjs",
+        "app.mjs",
+        "config.mjs",
+        "cycle_a.mjs",
+        "cycle_b.mjs",
+    ] {
+        assert_eq!(optimization_bailout(&output, module), [reason], "{module}");
+    }
+    assert_eq!(output.run("main.js"), EXPECTED);
+}
+
+#[test]
+fn async_modules_in_split_chunks() {
+    let output = common::build("top_level_await", |options| {
+        options.entry.push(EntryItem {
+            name: "second".to_string(),
+            import: "./second.mjs".to_string(),
+        });
+        options.optimization.split_chunks = Some(SplitChunksOptions {
+            min_size: 0,
+            ..Default::default()
+        });
+    });
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    assert!(output
+        .read("default-main-second.js")
+        .contains("await new Promise"));
+    assert_eq!(output.run("main.js"), EXPECTED);
+    assert_eq!(
+        output.run("second.js"),
+        "config start\nconfig end\nsecond loaded\n"
+    );
+}
+
+#[test]
+fn top_level_await_requires_async_functions() {
+    let output = common::build("top_level_await", |options| {
+        options.output.environment.async_function = false;
+    });
+    let mut diagnostics = output.diagnostics.clone();
+    diagnostics.sort();
+    let context = common::fixture("top_level_await");
+    assert_eq!(
+        diagnostics,
+        ["config.mjs", "cycle_a.mjs", "cycle_b.mjs"].map(|module| format!(
+            "top level await in {context}/{module} requires async functions, which are disabled by output.environment.asyncFunction"
+        ))
+    );
+}
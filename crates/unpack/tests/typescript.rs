@@ -0,0 +1,70 @@
+mod common;
+
+use rspack_sources::SourceMap;
+use unpack::compiler::{Devtool, EntryItem};
+
+fn build() -> common::Output {
+    common::build("typescript", |options| {
+        options.entry = vec![EntryItem {
+            name: "main".to_string(),
+            import: "./index.ts".to_string(),
+        }];
+        options.resolve.extensions = vec![".ts".to_string(), ".tsx".to_string()];
+        options.devtool = Devtool::SourceMap;
+    })
+}
+
+#[test]
+fn types_and_enums_are_compiled() {
+    let output = build();
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let main = output.read("main.js");
+    assert!(!main.contains(": Shape"), "{main}");
+    assert!(!main.contains("as number"), "{main}");
+    assert_eq!(output.run("main.js"), "0 green Red 4 div(2)\n");
+}
+
+#[test]
+fn type_only_imports_are_not_dependencies() {
+    let output = build();
+    let context = common::fixture("typescript");
+    let modules: Vec<_> = output
+        .stats
+        .modules
+        .iter()
+        .map(|module| module.identifier.clone())
+        .collect();
+    assert_eq!(
+        modules,
+        ["color.ts", "index.ts", "react.ts", "view.tsx"].map(|file| context.join(file).to_string())
+    );
+}
+
+#[test]
+fn source_map_points_to_typescript() {
+    let output = build();
+    let main = output.read("main.js");
+    let map = SourceMap::from_json(&output.read("main.js.map")).unwrap();
+    let context = common::fixture("typescript");
+    let index = map
+        .sources()
+        .iter()
+        .position(|source| source == "unpack:///./index.ts")
+        .unwrap_or_else(|| panic!("{:?}", map.sources()));
+    assert_eq!(
+        map.sources_content()[index],
+        std::fs::read_to_string(context.join("index.ts")).unwrap()
+    );
+    assert_eq!(
+        common::original_position("console.log", &map, &main),
+        ("unpack:///./index.ts".to_string(), 8)
+    );
+    assert_eq!(
+        common::original_position("const area", &map, &main),
+        ("unpack:///./index.ts".to_string(), 6)
+    );
+    assert_eq!(
+        common::original_position("React.createElement(\"div\"", &map, &main),
+        ("unpack:///./view.tsx".to_string(), 5)
+    );
+}
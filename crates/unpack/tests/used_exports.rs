@@ -0,0 +1,94 @@
+mod common;
+
+use unpack::stats::StatsModule;
+
+fn module<'a>(output: &'a common::Output, name: &str) -> &'a StatsModule {
+    let identifier = common::fixture("used_exports").join(name);
+    output
+        .stats
+        .modules
+        .iter()
+        .find(|module| module.identifier == identifier)
+        .unwrap_or_else(|| panic!("{name} is not built"))
+}
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+fn build(used_exports: bool) -> common::Output {
+    common::build("used_exports", |options| {
+        options.optimization.used_exports = used_exports;
+        // keep the getters of each module in the output
+        options.optimization.concatenate_modules = false;
+    })
+}
+
+#[test]
+fn used_exports_stats() {
+    let output = build(true);
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let lib = module(&output, "lib.mjs");
+    assert_eq!(
+        lib.provided_exports,
+        Some(names(&["used", "unused", "unusedFunction"]))
+    );
+    assert_eq!(lib.used_exports, Some(names(&["used"])));
+    assert_eq!(lib.unused_exports, names(&["unused", "unusedFunction"]));
+    // exports from `export *` are used through the re-exporting module
+    let reexport = module(&output, "reexport.mjs");
+    assert_eq!(reexport.used_exports, Some(names(&["used", "reexported"])));
+    assert_eq!(reexport.unused_exports, names(&["unused", "notReexported"]));
+    let star = module(&output, "star.mjs");
+    assert_eq!(star.used_exports, Some(names(&["reexported"])));
+    assert_eq!(star.unused_exports, names(&["notReexported"]));
+    // a namespace object used as a whole uses every export
+    let namespace = module(&output, "namespace.mjs");
+    assert_eq!(namespace.used_exports, None);
+    assert_eq!(namespace.unused_exports, Vec::<String>::new());
+    // exports of commonjs are unknown statically
+    let cjs = module(&output, "cjs.js");
+    assert_eq!(cjs.provided_exports, None);
+    assert_eq!(cjs.unused_exports, Vec::<String>::new());
+    let fixture = common::fixture("used_exports");
+    assert_eq!(
+        output.stats.to_string(),
+        format!(
+            "{fixture}/lib.mjs\n  unused exports: unused, unusedFunction\n  used exports: used\n\
+             {fixture}/reexport.mjs\n  unused exports: unused, notReexported\n  used exports: \
+             used, reexported\n{fixture}/star.mjs\n  unused exports: notReexported\n  used \
+             exports: reexported\n"
+        )
+    );
+}
+
+#[test]
+fn unused_exports_are_left_out() {
+    let output = build(true);
+    let main = output.read("main.js");
+    for name in ["unused", "unusedFunction", "notReexported"] {
+        assert!(
+            main.contains(&format!("/* unused harmony export {name} */")),
+            "{main}"
+        );
+        assert!(!main.contains(&format!("\"{name}\": () =>")), "{main}");
+    }
+    assert!(main.contains("\"used\": () =>"), "{main}");
+    assert!(main.contains("\"reexported\": () =>"), "{main}");
+    assert_eq!(output.run("main.js"), "used reexported a,b cjs\n");
+}
+
+#[test]
+fn used_exports_disabled() {
+    let output = build(false);
+    assert_eq!(output.stats.to_string(), "");
+    let lib = module(&output, "lib.mjs");
+    assert_eq!(lib.used_exports, None);
+    assert_eq!(lib.unused_exports, Vec::<String>::new());
+    let main = output.read("main.js");
+    assert!(!main.contains("unused harmony export"), "{main}");
+    for name in ["unused", "unusedFunction", "notReexported"] {
+        assert!(main.contains(&format!("\"{name}\": () =>")), "{main}");
+    }
+    assert_eq!(output.run("main.js"), "used reexported a,b cjs\n");
+}
@@ -0,0 +1,28 @@
+mod common;
+
+#[test]
+fn workers_are_entries_of_their_own_chunks() {
+    let output = common::build("worker", |_| {});
+    assert_eq!(output.diagnostics, Vec::<String>::new());
+    let chunks = output
+        .stats
+        .chunks
+        .iter()
+        .map(|chunk| (chunk.name.as_deref().unwrap(), chunk.files.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        chunks,
+        [
+            ("main", vec!["main.js".to_string()]),
+            ("worker_mjs", vec!["worker_mjs.js".to_string()])
+        ]
+    );
+    assert!(output.read("main.js").contains(
+        "new Worker(new URL(__unpack_require__.p + \"worker_mjs.js\", __unpack_require__.b))"
+    ));
+    // the worker has its own runtime, modules shared with the main chunk are in both chunks
+    let worker = output.read("worker_mjs.js");
+    assert!(worker.contains("function __unpack_require__(moduleId)"));
+    assert!(worker.contains("const greeting = \"hello\";"));
+    assert_eq!(output.run("main.js"), "hello main, hello from worker\n");
+}